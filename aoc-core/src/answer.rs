@@ -0,0 +1,150 @@
+//! What a solver returns. Most days answer with a count or a sum, but some
+//! puzzles ask for a negative number or a word.
+
+use alloc::string::{String, ToString};
+use core::{fmt, time::Duration};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The answer to one part of a puzzle. Serialized as a bare JSON number or
+/// string, so machine readable output stays the same for numeric answers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum Answer {
+    U64(u64),
+    I64(i64),
+    /// only for answers too big for a `u64`, under
+    /// [`OverflowPolicy::Widen`](crate::overflow::OverflowPolicy::Widen)
+    U128(u128),
+    String(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::U64(answer) => answer.fmt(f),
+            Answer::I64(answer) => answer.fmt(f),
+            Answer::U128(answer) => answer.fmt(f),
+            Answer::String(answer) => answer.fmt(f),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(answer: u64) -> Answer {
+        Answer::U64(answer)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(answer: i64) -> Answer {
+        Answer::I64(answer)
+    }
+}
+
+/// a `u64` when it fits, so answers only widen when they have to
+impl From<u128> for Answer {
+    fn from(answer: u128) -> Answer {
+        match u64::try_from(answer) {
+            Ok(answer) => Answer::U64(answer),
+            Err(_) => Answer::U128(answer),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(answer: String) -> Answer {
+        Answer::String(answer)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(answer: &str) -> Answer {
+        Answer::String(answer.to_string())
+    }
+}
+
+/// Both parts' answers to a puzzle, and how long each step took to get them.
+/// Solvers return these and leave presenting them to the caller.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Answers {
+    pub part1: Answer,
+    pub part2: Answer,
+    pub timings: Timings,
+}
+
+/// Wall-clock time spent parsing the input and solving each part from it
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Timings {
+    #[cfg_attr(feature = "serde", serde(rename = "parse_ns", with = "nanos"))]
+    pub parse: Duration,
+    #[cfg_attr(feature = "serde", serde(rename = "part1_ns", with = "nanos"))]
+    pub part1: Duration,
+    #[cfg_attr(feature = "serde", serde(rename = "part2_ns", with = "nanos"))]
+    pub part2: Duration,
+}
+
+/// durations as a whole number of nanoseconds
+#[cfg(feature = "serde")]
+mod nanos {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+/// lets `assert_eq!(answer, 13)` work for the common case
+impl PartialEq<u64> for Answer {
+    fn eq(&self, other: &u64) -> bool {
+        matches!(self, Answer::U64(answer) if answer == other)
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_serialize_as_bare_values() -> serde_json::Result<()> {
+        let answers = vec![Answer::U64(13), Answer::I64(-2), Answer::from("LPN")];
+        let json = serde_json::to_string(&answers)?;
+        assert_eq!(json, r#"[13,-2,"LPN"]"#);
+        assert_eq!(serde_json::from_str::<Vec<Answer>>(&json)?, answers);
+        assert_eq!(answers[1].to_string(), "-2");
+        assert_eq!(answers[0], 13);
+
+        assert_eq!(Answer::from(13u128), Answer::U64(13));
+        let wide = Answer::from(u128::from(u64::MAX) + 1);
+        assert_eq!(serde_json::to_string(&wide)?, "18446744073709551616");
+        Ok(())
+    }
+
+    #[test]
+    fn should_serialize_timings_in_nanoseconds() -> serde_json::Result<()> {
+        let answers = Answers {
+            part1: Answer::U64(13),
+            part2: Answer::from("LPN"),
+            timings: Timings {
+                parse: Duration::from_micros(2),
+                part1: Duration::from_nanos(30),
+                part2: Duration::ZERO,
+            },
+        };
+        assert_eq!(
+            serde_json::to_string(&answers)?,
+            r#"{"part1":13,"part2":"LPN","timings":{"parse_ns":2000,"part1_ns":30,"part2_ns":0}}"#
+        );
+        let json = serde_json::to_string(&answers)?;
+        assert_eq!(serde_json::from_str::<Answers>(&json)?, answers);
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+//! Hash maps and sets for the day crates: the standard library's with `std`,
+//! and hashbrown's without it, so solvers don't need to care which
+//!
+//! Solvers should reach for [`FastMap`] and [`FastSet`]. Their keys are ids
+//! and coordinates from the puzzle input, which don't need the standard
+//! library's protection against keys picked to collide, and FxHash hashes
+//! them several times faster. Swapping [`FastHasher`] here swaps it for every
+//! day.
+use core::hash::BuildHasherDefault;
+
+#[cfg(not(feature = "std"))]
+pub use hashbrown::{hash_map, HashMap, HashSet};
+use rustc_hash::FxHasher;
+#[cfg(feature = "std")]
+pub use std::collections::{hash_map, HashMap, HashSet};
+
+/// builds the hasher behind [`FastMap`] and [`FastSet`]
+pub type FastHasher = BuildHasherDefault<FxHasher>;
+
+/// a [`HashMap`] with a hasher for small keys, made with `FastMap::default()`
+pub type FastMap<K, V> = HashMap<K, V, FastHasher>;
+
+/// a [`HashSet`] with a hasher for small keys, made with `FastSet::default()`
+pub type FastSet<T> = HashSet<T, FastHasher>;
@@ -0,0 +1,422 @@
+//! Cancelling a solve and giving it a deadline.
+//!
+//! A [`SolveContext`] is everything a caller wants a solve to run with: the
+//! [`ExecutionStrategy`], a [`CancellationToken`] another thread can trip, a
+//! [`Progress`] sink, a deadline, the [`OverflowPolicy`] its arithmetic
+//! follows and the seed its random draws come from.
+//! [`DynSolver::solve_with`] installs it for the length of the solve with
+//! [`scoped`], and solvers call [`checkpoint`] between units of work to give
+//! up cleanly once it's been cancelled or has run out of time. Solvers about
+//! to build something sized by the input call [`reserve`] first, and once a
+//! solve's reservations add up to more than the context's memory budget it
+//! fails with a [`MemoryExceeded`] error rather than letting a giant input
+//! exhaust the machine. The context is installed on the calling thread, and
+//! carried to the threads the solve hands work to through
+//! [`ExecutionStrategy`], so solves running side by side each follow their
+//! own. Checkpoints and reservations do nothing unless a context is
+//! installed, and always do nothing without the `std` feature.
+//!
+//! [`DynSolver::solve_with`]: crate::solver::DynSolver::solve_with
+//! [`MemoryExceeded`]: crate::error::MemoryExceeded
+
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use core::cell::RefCell;
+#[cfg(feature = "std")]
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::{
+    error::{AocError, Result},
+    explain::Explain,
+    overflow::OverflowPolicy,
+    progress::Progress,
+    random::DEFAULT_SEED,
+    strategy::ExecutionStrategy,
+    visualize::Visualize,
+};
+
+/// A flag shared between the solve and whoever may want to stop it. Clones
+/// share the flag, so cancelling any of them cancels them all.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// ask the solve to stop at its next checkpoint
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// What a solve runs with, built up from [`SolveContext::new`]
+#[derive(Clone)]
+pub struct SolveContext {
+    pub strategy: ExecutionStrategy,
+    pub cancellation: CancellationToken,
+    /// receives the solver's progress, when set
+    pub progress: Option<Arc<dyn Progress>>,
+    /// the solve fails at its next checkpoint after this
+    #[cfg(feature = "std")]
+    pub deadline: Option<Instant>,
+    /// the most bytes a solve's [`reserve`]s may add up to
+    pub memory_budget: Option<usize>,
+    /// how arithmetic that outgrows a `u64` is handled
+    pub overflow: OverflowPolicy,
+    /// what the solve's [`random`](crate::random) draws are seeded from
+    pub seed: u64,
+    /// receives the frames the solver emits, when set
+    pub visualize: Option<Arc<dyn Visualize>>,
+    /// receives the steps the solver explains, when set
+    pub explain: Option<Arc<dyn Explain>>,
+}
+
+impl Default for SolveContext {
+    fn default() -> SolveContext {
+        SolveContext {
+            strategy: ExecutionStrategy::default(),
+            cancellation: CancellationToken::default(),
+            progress: None,
+            #[cfg(feature = "std")]
+            deadline: None,
+            memory_budget: None,
+            overflow: OverflowPolicy::default(),
+            seed: DEFAULT_SEED,
+            visualize: None,
+            explain: None,
+        }
+    }
+}
+
+impl SolveContext {
+    pub fn new(strategy: ExecutionStrategy) -> SolveContext {
+        SolveContext {
+            strategy,
+            ..SolveContext::default()
+        }
+    }
+
+    pub fn with_cancellation(self, cancellation: CancellationToken) -> SolveContext {
+        SolveContext {
+            cancellation,
+            ..self
+        }
+    }
+
+    pub fn with_progress(self, progress: Arc<dyn Progress>) -> SolveContext {
+        SolveContext {
+            progress: Some(progress),
+            ..self
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn with_deadline(self, deadline: Instant) -> SolveContext {
+        SolveContext {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+
+    pub fn with_memory_budget(self, memory_budget: usize) -> SolveContext {
+        SolveContext {
+            memory_budget: Some(memory_budget),
+            ..self
+        }
+    }
+
+    pub fn with_overflow(self, overflow: OverflowPolicy) -> SolveContext {
+        SolveContext { overflow, ..self }
+    }
+
+    pub fn with_seed(self, seed: u64) -> SolveContext {
+        SolveContext { seed, ..self }
+    }
+
+    pub fn with_visualize(self, visualize: Arc<dyn Visualize>) -> SolveContext {
+        SolveContext {
+            visualize: Some(visualize),
+            ..self
+        }
+    }
+
+    pub fn with_explain(self, explain: Arc<dyn Explain>) -> SolveContext {
+        SolveContext {
+            explain: Some(explain),
+            ..self
+        }
+    }
+
+    /// an error if the solve should stop now
+    pub fn check(&self) -> Result<()> {
+        if self.cancellation.is_cancelled() {
+            return Err(AocError::new("cancelled"));
+        }
+        #[cfg(feature = "std")]
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(AocError::new("ran past its deadline"));
+        }
+        Ok(())
+    }
+}
+
+/// A context as installed for one solve, with how many bytes the solve has
+/// reserved so far
+#[cfg(feature = "std")]
+struct Installed {
+    context: SolveContext,
+    reserved: Arc<AtomicUsize>,
+}
+
+#[cfg(feature = "std")]
+impl Installed {
+    fn new(context: SolveContext) -> Arc<Installed> {
+        Arc::new(Installed {
+            context,
+            reserved: Arc::default(),
+        })
+    }
+
+    /// add `bytes` to what the solve has reserved, failing once that's more
+    /// than the budget
+    fn reserve(&self, bytes: usize, what: &str) -> Result<()> {
+        let Some(budget) = self.context.memory_budget else {
+            return Ok(());
+        };
+        let before = self
+            .reserved
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |reserved| {
+                Some(reserved.saturating_add(bytes))
+            })
+            .unwrap_or_default();
+        match before.saturating_add(bytes) {
+            total if total > budget => Err(AocError::memory_budget_exceeded(what, total, budget)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// the context installed on this thread, shared with the threads it
+    /// hands work to
+    static CURRENT: RefCell<Option<Arc<Installed>>> = const { RefCell::new(None) };
+}
+
+/// the context installed on this thread
+#[cfg(feature = "std")]
+fn installed() -> Option<Arc<Installed>> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// fail if the context installed on this thread has been cancelled or is
+/// past its deadline
+#[cfg(not(feature = "std"))]
+pub fn checkpoint() -> Result<()> {
+    Ok(())
+}
+
+/// fail if the context installed on this thread has been cancelled or is
+/// past its deadline
+#[cfg(feature = "std")]
+pub fn checkpoint() -> Result<()> {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_deref()
+            .map_or(Ok(()), |installed| installed.context.check())
+    })
+}
+
+/// count `bytes` more towards the solve's memory budget before allocating
+/// `what`, failing if the solve's reservations now add up to more than the
+/// budget of the context installed on this thread
+#[cfg(not(feature = "std"))]
+pub fn reserve(_bytes: usize, _what: &str) -> Result<()> {
+    Ok(())
+}
+
+/// count `bytes` more towards the solve's memory budget before allocating
+/// `what`, failing if the solve's reservations now add up to more than the
+/// budget of the context installed on this thread
+#[cfg(feature = "std")]
+pub fn reserve(bytes: usize, what: &str) -> Result<()> {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_deref()
+            .map_or(Ok(()), |installed| installed.reserve(bytes, what))
+    })
+}
+
+/// [`reserve`] room for `count` values of `T`
+pub fn reserve_for<T>(count: usize, what: &str) -> Result<()> {
+    reserve(count.saturating_mul(core::mem::size_of::<T>()), what)
+}
+
+/// the progress sink of the context installed on this thread, if it has one
+#[cfg(feature = "std")]
+pub(crate) fn progress() -> Option<Arc<dyn Progress>> {
+    CURRENT.with(|current| current.borrow().as_ref()?.context.progress.clone())
+}
+
+/// `read` from the context installed on this thread, if there is one
+#[cfg(feature = "std")]
+pub(crate) fn current<T>(read: impl FnOnce(&SolveContext) -> T) -> Option<T> {
+    CURRENT.with(|current| Some(read(&current.borrow().as_ref()?.context)))
+}
+
+/// run `f` with `context` installed on this thread, so [`checkpoint`]
+/// follows it and its progress sink, if it has one, receives any progress
+/// reported. Other solves, on other threads, keep their own. Nothing has
+/// been reserved against its memory budget yet.
+#[cfg(feature = "std")]
+pub fn scoped<T>(context: &SolveContext, f: impl FnOnce() -> T) -> T {
+    within(Some(Installed::new(context.clone())), f)
+}
+
+/// run `f` as a solve of its own
+#[cfg(not(feature = "std"))]
+pub fn fresh<T>(f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+/// run `f` as a solve of its own, under the context installed on this
+/// thread but with nothing reserved against its memory budget yet, so a
+/// context installed for several solves budgets each of them separately
+#[cfg(feature = "std")]
+pub fn fresh<T>(f: impl FnOnce() -> T) -> T {
+    let context = installed().map(|installed| Installed::new(installed.context.clone()));
+    within(context, f)
+}
+
+/// run `f` with the context installed on this thread, or a default one if
+/// there isn't one, changed by `change`. The solve carries on counting its
+/// reservations towards the same budget.
+#[cfg(feature = "std")]
+pub(crate) fn amended<T>(change: impl FnOnce(&mut SolveContext), f: impl FnOnce() -> T) -> T {
+    let installed = installed();
+    let mut context = installed
+        .as_ref()
+        .map_or_else(SolveContext::default, |installed| installed.context.clone());
+    change(&mut context);
+    let reserved = installed.map_or_else(Arc::default, |installed| installed.reserved.clone());
+    within(Some(Arc::new(Installed { context, reserved })), f)
+}
+
+/// run `f` with `context` installed on this thread, putting back whatever
+/// was installed before, even if `f` panics
+#[cfg(feature = "std")]
+fn within<T>(context: Option<Arc<Installed>>, f: impl FnOnce() -> T) -> T {
+    struct Reset(Option<Arc<Installed>>);
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _reset = Reset(CURRENT.with(|current| current.replace(context)));
+    f()
+}
+
+/// `f`, made to run with the context installed on this thread whichever
+/// thread calls it, for handing work to rayon's workers or threads of one's
+/// own
+#[cfg(feature = "std")]
+pub fn carry<A, R>(f: impl Fn(A) -> R + Send + Sync) -> impl Fn(A) -> R + Send + Sync {
+    let context = installed();
+    move |arg| within(context.clone(), || f(arg))
+}
+
+/// [`carry`] for work that only runs once, such as inside a thread pool
+#[cfg(feature = "std")]
+pub(crate) fn carry_once<R>(f: impl FnOnce() -> R + Send) -> impl FnOnce() -> R + Send {
+    let context = installed();
+    move || within(context, f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::MemoryExceeded;
+    use std::{thread, time::Duration, vec::Vec};
+
+    #[test]
+    fn should_stop_once_cancelled() {
+        let token = CancellationToken::new();
+        let context = SolveContext::default().with_cancellation(token.clone());
+        scoped(&context, || {
+            assert_eq!(checkpoint(), Ok(()));
+            token.cancel();
+            assert_eq!(checkpoint(), Err(AocError::new("cancelled")));
+        });
+        assert_eq!(checkpoint(), Ok(()));
+        assert!(context.clone().cancellation.is_cancelled());
+    }
+
+    #[test]
+    fn should_keep_side_by_side_solves_apart() {
+        let cancelled = SolveContext::default();
+        cancelled.cancellation.cancel();
+        let strategy = ExecutionStrategy::Rayon { threads: Some(2) };
+        let items: Vec<u64> = (0..100).collect();
+        let checked = |context: &SolveContext| {
+            scoped(context, || {
+                strategy.install(|| strategy.try_map(&items, |_, _| checkpoint()))
+            })
+        };
+        thread::scope(|scope| {
+            let other = scope.spawn(|| checked(&SolveContext::default()));
+            assert_eq!(
+                checked(&cancelled).map(|_| ()),
+                Err(AocError::new("cancelled"))
+            );
+            assert_eq!(other.join().map(|checked| checked.is_ok()).ok(), Some(true));
+        });
+    }
+
+    #[test]
+    fn should_stop_past_the_deadline() {
+        let context = SolveContext::default().with_deadline(Instant::now());
+        assert_eq!(context.check(), Err(AocError::new("ran past its deadline")));
+
+        let context =
+            SolveContext::default().with_deadline(Instant::now() + Duration::from_secs(60));
+        assert_eq!(context.check(), Ok(()));
+    }
+
+    #[test]
+    fn should_refuse_reservations_adding_up_to_more_than_the_budget() {
+        let context = SolveContext::default().with_memory_budget(1024);
+        scoped(&context, || {
+            assert_eq!(reserve(400, "rows"), Ok(()));
+            assert_eq!(reserve_for::<u64>(50, "values"), Ok(()));
+            let error = reserve(400, "columns").unwrap_err();
+            assert_eq!(
+                error.memory,
+                Some(MemoryExceeded {
+                    needed: 1200,
+                    budget: 1024
+                })
+            );
+            // each solve under the context has a budget of its own
+            assert_eq!(fresh(|| reserve(1024, "grid")), Ok(()));
+        });
+        scoped(&SolveContext::default(), || {
+            assert_eq!(reserve(usize::MAX, "grid"), Ok(()));
+        });
+        assert_eq!(reserve(usize::MAX, "grid"), Ok(()));
+    }
+}
@@ -0,0 +1,102 @@
+//! Problems found while checking a puzzle input, without solving it.
+
+use alloc::string::String;
+use core::fmt;
+
+/// How bad a problem is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// the solvers cope with it, but the input probably isn't what was meant
+    Warning,
+    /// the solvers will fail or give a wrong answer
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found in a puzzle input, pointing at the line and column (both
+/// counted from 1) where it was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// an error at `line` and `column`, see [`Diagnostic::warning`] for
+    /// problems the solvers cope with
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// a diagnostic pointing at `span`, which should be a slice of `text`, the
+    /// contents of line `line`. Spans from elsewhere point at the first column.
+    pub fn at(line: usize, text: &str, span: &str, message: impl Into<String>) -> Diagnostic {
+        let start = text.as_ptr() as usize;
+        let offset = (span.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| *offset <= text.len() && text.is_char_boundary(*offset))
+            .unwrap_or(0);
+        Diagnostic::new(line, text[..offset].chars().count() + 1, message)
+    }
+
+    /// downgrade the diagnostic to a warning
+    pub fn warning(self) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..self
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.line, self.column, self.severity, self.message
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_point_at_span_within_line() {
+        let line = "Card 1: 41 x8 | 83";
+        let span = &line[11..13];
+        let diagnostic = Diagnostic::at(3, line, span, "not a number");
+        assert_eq!(diagnostic.to_string(), "3:12: error: not a number");
+
+        let elsewhere = Diagnostic::at(3, line, "x8", "not a number");
+        assert_eq!(elsewhere.column, 1);
+    }
+
+    #[test]
+    fn should_downgrade_to_a_warning() {
+        let warning = Diagnostic::new(2, 1, "cards out of order").warning();
+        assert!(!warning.is_error());
+        assert_eq!(warning.to_string(), "2:1: warning: cards out of order");
+        assert!(Severity::Warning < Severity::Error);
+    }
+}
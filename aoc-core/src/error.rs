@@ -0,0 +1,172 @@
+//! The error the day crates return, saying where in the puzzle input a
+//! solver gave up so the cli can point the user at it.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::num::ParseIntError;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Why solving failed and where. The day crates fill in the line of the
+/// input they were on, and [`DynSolver`](crate::solver::DynSolver) fills in
+/// the day and part.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[error("{}{message}", location(*.day, *.part, *.line))]
+pub struct AocError {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub day: Option<usize>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub part: Option<u8>,
+    /// counted from 1
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub line: Option<usize>,
+    pub message: String,
+    /// set when the solve gave up because it needed more memory than its
+    /// budget, see [`reserve`](crate::context::reserve)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub memory: Option<MemoryExceeded>,
+}
+
+/// How far over its memory budget a solve would have gone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryExceeded {
+    /// bytes the solve's reservations added up to, counting the one that
+    /// went over
+    pub needed: usize,
+    /// bytes the budget allowed
+    pub budget: usize,
+}
+
+/// Shorthand for results from the day crates
+pub type Result<T, E = AocError> = core::result::Result<T, E>;
+
+/// `day 4 part 1, line 3: `, leaving out whatever isn't known
+fn location(day: Option<usize>, part: Option<u8>, line: Option<usize>) -> String {
+    let puzzle: Vec<String> = [
+        day.map(|day| format!("day {day}")),
+        part.map(|part| format!("part {part}")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let puzzle = puzzle.join(" ");
+    match (puzzle.is_empty(), line) {
+        (true, None) => String::new(),
+        (true, Some(line)) => format!("line {line}: "),
+        (false, None) => format!("{puzzle}: "),
+        (false, Some(line)) => format!("{puzzle}, line {line}: "),
+    }
+}
+
+impl AocError {
+    pub fn new(message: impl Into<String>) -> AocError {
+        AocError {
+            day: None,
+            part: None,
+            line: None,
+            message: message.into(),
+            memory: None,
+        }
+    }
+
+    /// reserving room for `what` brought the solve to `needed` bytes, more
+    /// than the `budget` it was given
+    pub fn memory_budget_exceeded(what: &str, needed: usize, budget: usize) -> AocError {
+        AocError {
+            memory: Some(MemoryExceeded { needed, budget }),
+            ..AocError::new(format!(
+                "memory budget exceeded: {what} brings the solve to {needed} bytes, over its budget of {budget}"
+            ))
+        }
+    }
+
+    /// note the line the error was found on, unless a more specific line is
+    /// already known
+    pub fn at_line(mut self, line: usize) -> AocError {
+        self.line = self.line.or(Some(line));
+        self
+    }
+
+    pub fn with_day(mut self, day: usize) -> AocError {
+        self.day = self.day.or(Some(day));
+        self
+    }
+
+    pub fn with_part(mut self, part: u8) -> AocError {
+        self.part = self.part.or(Some(part));
+        self
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    fn from(error: ParseIntError) -> AocError {
+        AocError::new(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_describe_where_the_error_is() {
+        let error = AocError::new("malformatted card id");
+        assert_eq!(error.to_string(), "malformatted card id");
+
+        let error = error.at_line(3);
+        assert_eq!(error.to_string(), "line 3: malformatted card id");
+
+        let error = error.at_line(1).with_day(4).with_part(2);
+        assert_eq!(
+            error.to_string(),
+            "day 4 part 2, line 3: malformatted card id"
+        );
+
+        let error = AocError::from("x".parse::<u64>().unwrap_err()).with_day(1);
+        assert_eq!(error.to_string(), "day 1: invalid digit found in string");
+
+        let error = AocError::memory_budget_exceeded("schematic", 2048, 1024).with_day(3);
+        assert_eq!(
+            error.to_string(),
+            "day 3: memory budget exceeded: schematic brings the solve to 2048 bytes, over its budget of 1024"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_serialize_where_the_error_is() -> serde_json::Result<()> {
+        let error = AocError::new("malformatted card id")
+            .at_line(3)
+            .with_day(4)
+            .with_part(2);
+        assert_eq!(
+            serde_json::to_string(&error)?,
+            r#"{"day":4,"part":2,"line":3,"message":"malformatted card id"}"#
+        );
+
+        let error = AocError::memory_budget_exceeded("schematic", 2048, 1024);
+        assert_eq!(
+            serde_json::to_string(&error.memory)?,
+            r#"{"needed":2048,"budget":1024}"#
+        );
+        Ok(())
+    }
+}
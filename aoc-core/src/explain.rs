@@ -0,0 +1,111 @@
+//! Explaining how a solve got its answer.
+//!
+//! Solvers call [`step`] with a closure describing each decision that goes
+//! into the answer, like which numbers were counted and why, for the cli's
+//! `--explain` to print for teaching or for tracking down a wrong answer.
+//! Like progress reporting, the closure is only run when the caller has
+//! given the solve an [`Explain`] sink, with [`scoped`] or
+//! [`SolveContext::with_explain`], so explanations cost nothing in normal
+//! runs, and never without the `std` feature. Steps explained on rayon
+//! workers arrive in whatever order they happen.
+//!
+//! [`SolveContext::with_explain`]: crate::context::SolveContext::with_explain
+
+use alloc::{format, string::String};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+/// Receives the steps a solver explains
+pub trait Explain: Send + Sync {
+    fn step(&self, step: String);
+}
+
+/// whether anyone is listening, for solvers that would have to do extra work
+/// to explain themselves at all
+#[cfg(not(feature = "std"))]
+pub fn is_active() -> bool {
+    false
+}
+
+/// whether anyone is listening, for solvers that would have to do extra work
+/// to explain themselves at all
+#[cfg(feature = "std")]
+pub fn is_active() -> bool {
+    crate::context::current(|context| context.explain.is_some()).unwrap_or_default()
+}
+
+/// explain a step, only formatting it when someone is listening
+#[cfg(not(feature = "std"))]
+pub fn step(_step: impl FnOnce() -> String) {}
+
+/// explain a step, only formatting it when someone is listening
+#[cfg(feature = "std")]
+pub fn step(step: impl FnOnce() -> String) {
+    let sink = crate::context::current(|context| context.explain.clone()).flatten();
+    if let Some(sink) = sink {
+        sink.step(step());
+    }
+}
+
+/// `count` followed by `one` or `many`, whichever reads right, for steps
+/// like "1 match" and "4 matches"
+pub fn plural(count: u128, one: &str, many: &str) -> String {
+    match count {
+        1 => format!("{count} {one}"),
+        _ => format!("{count} {many}"),
+    }
+}
+
+/// run `f` with `sink` receiving any steps explained while it runs,
+/// including on the threads its
+/// [`ExecutionStrategy`](crate::strategy::ExecutionStrategy) hands work to,
+/// and put back the sink that was receiving them before
+#[cfg(feature = "std")]
+pub fn scoped<T>(sink: Arc<dyn Explain>, f: impl FnOnce() -> T) -> T {
+    crate::context::amended(|context| context.explain = Some(sink), f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec::Vec};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Steps(Mutex<Vec<String>>);
+
+    impl Explain for Steps {
+        fn step(&self, step: String) {
+            self.0.lock().unwrap().push(step);
+        }
+    }
+
+    #[test]
+    fn should_explain_only_while_scoped() {
+        let steps = Arc::new(Steps::default());
+        scoped(steps.clone(), || {
+            step(|| "617 counted because '*' at (4,3) is adjacent".to_string())
+        });
+        // nobody is listening any more, so this isn't even formatted
+        step(|| unreachable!("steps aren't formatted without a sink"));
+
+        assert_eq!(
+            *steps.0.lock().unwrap(),
+            vec!["617 counted because '*' at (4,3) is adjacent"]
+        );
+    }
+
+    #[test]
+    fn should_put_back_the_outer_sink() {
+        let outer = Arc::new(Steps::default());
+        let inner = Arc::new(Steps::default());
+        scoped(outer.clone(), || {
+            step(|| "outer".to_string());
+            scoped(inner.clone(), || step(|| "inner".to_string()));
+            step(|| "outer again".to_string());
+        });
+
+        assert_eq!(*outer.0.lock().unwrap(), vec!["outer", "outer again"]);
+        assert_eq!(*inner.0.lock().unwrap(), vec!["inner"]);
+    }
+}
@@ -0,0 +1,49 @@
+//! Describing what an input parsed into.
+//!
+//! `aoc parse` prints a day's parsed input instead of solving it, to check a
+//! parser against an odd input or to analyse inputs somewhere else. What a
+//! day parses into is shaped for solving rather than reading, so a day picks
+//! what to show with [`Model`], which needs the `serde` feature. Every
+//! `Model` is an [`Export`], which encodes it as JSON and needs `std` too.
+
+#[cfg(all(feature = "std", feature = "serde"))]
+use alloc::format;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[cfg(feature = "serde")]
+use crate::solver::Solver;
+#[cfg(all(feature = "std", feature = "serde"))]
+use crate::{error::AocError, solver::parse};
+#[cfg(feature = "std")]
+use crate::{error::Result, strategy::ExecutionStrategy};
+
+/// A day that can show what an input parsed into
+#[cfg(feature = "serde")]
+pub trait Model: Solver {
+    /// what `parsed` holds, in a form that reads well as JSON
+    fn model<'a, 'b: 'a>(parsed: &'a Self::Parsed<'b>) -> impl Serialize + 'a;
+}
+
+/// An object safe view of a [`Model`], so the cli can describe any day's
+/// parsed input
+#[cfg(feature = "std")]
+pub trait Export: Send + Sync {
+    /// parse `text` and describe what it was parsed into. Errors say which
+    /// day failed.
+    fn export(&self, text: &str, strategy: ExecutionStrategy) -> Result<serde_json::Value>;
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+impl<S: Model + Send + Sync> Export for S {
+    fn export(&self, text: &str, strategy: ExecutionStrategy) -> Result<serde_json::Value> {
+        strategy
+            .install(|| {
+                let parsed = parse::<S>(text, strategy)?;
+                serde_json::to_value(S::model(&parsed))
+                    .map_err(|e| AocError::new(format!("unable to describe the parsed input: {e}")))
+            })
+            .map_err(|e| e.with_day(S::DAY))
+    }
+}
@@ -0,0 +1,65 @@
+//! Keeping answers up to date as an input is edited.
+//!
+//! Most puzzle inputs are a line per record, so an edit to a few lines only
+//! changes what those lines, and sometimes their neighbors, contribute to
+//! the answers. A day that implements [`Incremental`] keeps what it worked
+//! out for each line and only redoes the lines an edit touches, which lets
+//! the cli's watch mode answer in about the time it takes to notice the
+//! change. [`diff`] finds which lines those are.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{answer::Answer, error::Result};
+
+/// A solve that keeps its work between edits to the input. It starts out
+/// with no lines, so the first [`splice`](Incremental::splice) is the whole
+/// input.
+pub trait Incremental: Send {
+    /// replace `changed`, a range of the input's lines counted from 0, with
+    /// `lines`
+    fn splice(&mut self, changed: Range<usize>, lines: &[&str]);
+
+    /// part one's answer for the input as it stands
+    fn part1(&self) -> Result<Answer>;
+
+    /// part two's answer for the input as it stands
+    fn part2(&self) -> Result<Answer>;
+}
+
+/// the lines that changed between `old` and `new`: which of `old`'s lines
+/// were replaced, and the lines of `new` that replaced them. Lines the two
+/// have in common at either end are left out, so an edit in one place is
+/// only as big as the lines it touched.
+pub fn diff<'a>(old: &str, new: &'a str) -> (Range<usize>, Vec<&'a str>) {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    (
+        prefix..old.len() - suffix,
+        new[prefix..new.len() - suffix].to_vec(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn should_find_only_the_lines_that_changed() {
+        assert_eq!(diff("", "a\nb"), (0..0, vec!["a", "b"]));
+        assert_eq!(diff("a\nb\nc", "a\nx\nc"), (1..2, vec!["x"]));
+        assert_eq!(diff("a\nb\nc", "a\nc"), (1..2, vec![]));
+        assert_eq!(diff("a\nc", "a\nb\nc"), (1..1, vec!["b"]));
+        assert_eq!(diff("a\nb", "a\nb\n"), (2..2, vec![]));
+        // a repeated line is only counted once, at the start
+        assert_eq!(diff("a\na", "a"), (1..2, vec![]));
+    }
+}
@@ -0,0 +1,30 @@
+//! Pieces shared by the day crates and the cli
+//!
+//! Everything a solver needs builds with just `alloc`, so the day crates can
+//! run on embedded and WASM targets without an operating system. Registering
+//! days, reporting progress, rayon and timing need the default `std` feature.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod answer;
+pub mod collections;
+pub mod context;
+pub mod diagnostic;
+pub mod error;
+pub mod explain;
+#[cfg(any(feature = "std", feature = "serde"))]
+pub mod export;
+pub mod incremental;
+pub mod metadata;
+pub mod overflow;
+pub mod persist;
+pub mod progress;
+pub mod random;
+pub mod search;
+pub mod solver;
+pub mod strategy;
+pub mod telemetry;
+#[cfg(feature = "std")]
+pub mod timing;
+pub mod visualize;
@@ -0,0 +1,47 @@
+//! What the cli can say about a solver without running it. Each [`Solver`]
+//! declares its puzzle's title, some tags for the techniques it uses and how
+//! long it expects to take, and [`DynSolver::metadata`] collects them.
+//!
+//! [`Solver`]: crate::solver::Solver
+//! [`DynSolver::metadata`]: crate::solver::DynSolver::metadata
+
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Roughly how long a solver takes on a real puzzle input
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Runtime {
+    /// well under a millisecond
+    #[default]
+    Instant,
+    /// up to about a second
+    Fast,
+    /// seconds or more, worth a progress bar
+    Slow,
+}
+
+impl fmt::Display for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Runtime::Instant => write!(f, "instant"),
+            Runtime::Fast => write!(f, "fast"),
+            Runtime::Slow => write!(f, "slow"),
+        }
+    }
+}
+
+/// A description of a day's solver
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Metadata {
+    pub day: usize,
+    /// the puzzle's title on adventofcode.com
+    pub title: &'static str,
+    /// the techniques the solver uses, like `"grid"` or `"graph"`
+    pub tags: &'static [&'static str],
+    pub runtime: Runtime,
+}
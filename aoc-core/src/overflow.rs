@@ -0,0 +1,155 @@
+//! What solvers do when their arithmetic overflows.
+//!
+//! Sums and products over a whole input can outgrow a `u64` on pathological
+//! inputs, and release builds would wrap around silently. Solvers do that
+//! arithmetic through [`OverflowPolicy`] instead, in `u128`, with the policy
+//! [`policy`] returns: the one of the [`SolveContext`] installed on the
+//! calling thread, which the caller picks with
+//! [`SolveContext::with_overflow`] or [`scoped`]. That's
+//! [`OverflowPolicy::Checked`] when there's no context, and always without
+//! the `std` feature.
+//!
+//! [`SolveContext`]: crate::context::SolveContext
+//! [`SolveContext::with_overflow`]: crate::context::SolveContext::with_overflow
+
+use alloc::format;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AocError, Result};
+
+/// How arithmetic that outgrows a `u64` is handled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum OverflowPolicy {
+    /// fail the solve with an error saying what overflowed
+    #[default]
+    Checked,
+    /// clamp at `u64::MAX`
+    Saturating,
+    /// carry on in `u128`, failing only if that overflows too
+    Widen,
+}
+
+/// the policy solvers should follow
+#[cfg(not(feature = "std"))]
+pub fn policy() -> OverflowPolicy {
+    OverflowPolicy::Checked
+}
+
+/// the policy solvers should follow, that of the context installed on this
+/// thread
+#[cfg(feature = "std")]
+pub fn policy() -> OverflowPolicy {
+    crate::context::current(|context| context.overflow).unwrap_or_default()
+}
+
+/// run `f` with solvers following `policy`, including on the threads its
+/// [`ExecutionStrategy`](crate::strategy::ExecutionStrategy) hands work to,
+/// and put back the policy they followed before
+#[cfg(feature = "std")]
+pub fn scoped<T>(policy: OverflowPolicy, f: impl FnOnce() -> T) -> T {
+    crate::context::amended(|context| context.overflow = policy, f)
+}
+
+impl OverflowPolicy {
+    /// the largest value allowed through before the policy steps in
+    fn limit(self) -> u128 {
+        match self {
+            OverflowPolicy::Widen => u128::MAX,
+            _ => u64::MAX.into(),
+        }
+    }
+
+    /// apply the policy to the result of an operation, `None` if it
+    /// overflowed even a `u128`
+    fn settle(self, value: Option<u128>, operation: &str) -> Result<u128> {
+        match value.filter(|value| *value <= self.limit()) {
+            Some(value) => Ok(value),
+            None if self == OverflowPolicy::Saturating => Ok(self.limit()),
+            None => Err(AocError::new(format!(
+                "{operation} overflowed, the largest value allowed is {}",
+                self.limit()
+            ))),
+        }
+    }
+
+    pub fn add(self, a: u128, b: u128) -> Result<u128> {
+        self.settle(a.checked_add(b), "addition")
+    }
+
+    pub fn mul(self, a: u128, b: u128) -> Result<u128> {
+        self.settle(a.checked_mul(b), "multiplication")
+    }
+
+    /// two to the power of `exponent`
+    pub fn pow2(self, exponent: u32) -> Result<u128> {
+        self.settle(2u128.checked_pow(exponent), "power of two")
+    }
+
+    pub fn sum<T: Into<u128>>(self, values: impl IntoIterator<Item = T>) -> Result<u128> {
+        values
+            .into_iter()
+            .try_fold(0, |total, value| self.add(total, value.into()))
+    }
+
+    pub fn product<T: Into<u128>>(self, values: impl IntoIterator<Item = T>) -> Result<u128> {
+        values
+            .into_iter()
+            .try_fold(1, |total, value| self.mul(total, value.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::ExecutionStrategy;
+    use alloc::vec::Vec;
+    use std::thread;
+
+    const BIG: u64 = u64::MAX / 2 + 1;
+
+    #[test]
+    fn should_follow_each_policy() {
+        let big = [BIG, BIG, 1];
+        assert_eq!(
+            OverflowPolicy::Checked.sum(big).unwrap_err().to_string(),
+            "addition overflowed, the largest value allowed is 18446744073709551615"
+        );
+        assert_eq!(OverflowPolicy::Saturating.sum(big), Ok(u64::MAX.into()));
+        assert_eq!(OverflowPolicy::Widen.sum(big), Ok(u128::from(BIG) * 2 + 1));
+
+        assert_eq!(OverflowPolicy::Checked.product([2u64, 3, 7]), Ok(42));
+        assert!(OverflowPolicy::Checked.pow2(64).is_err());
+        assert_eq!(OverflowPolicy::Saturating.pow2(64), Ok(u64::MAX.into()));
+        assert_eq!(OverflowPolicy::Widen.pow2(64), Ok(1 << 64));
+        assert!(OverflowPolicy::Widen.pow2(128).is_err());
+    }
+
+    #[test]
+    fn should_restore_the_policy_after_a_scope() {
+        assert_eq!(policy(), OverflowPolicy::Checked);
+        let inner = scoped(OverflowPolicy::Widen, policy);
+        assert_eq!(inner, OverflowPolicy::Widen);
+        assert_eq!(policy(), OverflowPolicy::Checked);
+    }
+
+    #[test]
+    fn should_keep_side_by_side_policies_apart() {
+        let strategy = ExecutionStrategy::Rayon { threads: Some(2) };
+        let items: Vec<u64> = (0..100).collect();
+        let followed = |overflow| {
+            scoped(overflow, || {
+                strategy.install(|| strategy.try_map(&items, |_, _| Ok(policy())))
+            })
+            .is_ok_and(|policies| policies.iter().all(|policy| *policy == overflow))
+        };
+        thread::scope(|scope| {
+            let other = scope.spawn(|| followed(OverflowPolicy::Saturating));
+            assert!(followed(OverflowPolicy::Widen));
+            assert_eq!(other.join().ok(), Some(true));
+        });
+    }
+}
@@ -0,0 +1,64 @@
+//! Keeping a parsed input between runs.
+//!
+//! Benchmark loops and solving one part at a time parse the same input over
+//! and over. A day whose [`Solver::Parsed`] can be serialized is a [`Persist`]
+//! too: it can encode what an input parsed into, and later solve straight
+//! from those bytes without looking at the text again. The cli keeps the
+//! bytes on disk, keyed by a hash of the input. Encoding needs the `std`
+//! and `serde` features.
+
+#[cfg(all(feature = "std", feature = "serde"))]
+use alloc::format;
+use alloc::vec::Vec;
+
+#[cfg(all(feature = "std", feature = "serde"))]
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "std", feature = "serde"))]
+use crate::{
+    context,
+    error::AocError,
+    solver::{parse, solve_part, Solver},
+    telemetry::{span, Phase},
+};
+use crate::{error::Result, solver::Answer, strategy::ExecutionStrategy};
+
+/// A day that can solve from a parsed input it saved earlier. Every
+/// [`Solver`] whose parsed input is serializable is one.
+pub trait Persist: Send + Sync {
+    /// parse `text` and encode what it was parsed into. Errors say which day
+    /// failed.
+    fn save(&self, text: &str, strategy: ExecutionStrategy) -> Result<Vec<u8>>;
+
+    /// solve `part` from bytes [`Persist::save`] made. Bytes that don't
+    /// decode, such as ones saved by an older build, are an error rather than
+    /// a wrong answer.
+    fn solve_saved(&self, saved: &[u8], part: u8, strategy: ExecutionStrategy) -> Result<Answer>;
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+impl<S> Persist for S
+where
+    S: Solver + Send + Sync,
+    for<'a> S::Parsed<'a>: Serialize + Deserialize<'a>,
+{
+    fn save(&self, text: &str, strategy: ExecutionStrategy) -> Result<Vec<u8>> {
+        context::fresh(|| {
+            strategy.install(|| {
+                let parsed = parse::<S>(text, strategy)?;
+                bincode::serialize(&parsed)
+                    .map_err(|e| AocError::new(format!("unable to save the parsed input: {e}")))
+            })
+        })
+        .map_err(|e| e.with_day(S::DAY))
+    }
+
+    fn solve_saved(&self, saved: &[u8], part: u8, strategy: ExecutionStrategy) -> Result<Answer> {
+        solve_part::<S>(part, strategy, || {
+            span(S::DAY, Phase::Parse).in_scope(|| {
+                bincode::deserialize(saved)
+                    .map_err(|e| AocError::new(format!("saved parsed input is unreadable: {e}")))
+            })
+        })
+    }
+}
@@ -0,0 +1,114 @@
+//! Progress reporting for long running solvers.
+//!
+//! Solvers call the free functions in this module ([`start`], [`advance`],
+//! [`finish`]) as they work through their input. Those calls do nothing
+//! unless the caller has given the solve a [`Progress`] reporter, with
+//! [`scoped`] or [`SolveContext::with_progress`], so solvers can report
+//! unconditionally without slowing down normal runs. The reporter belongs to
+//! the solve's context, so solves running side by side each report to their
+//! own. Reporters need the `std` feature; without it the calls always do
+//! nothing.
+//!
+//! [`SolveContext::with_progress`]: crate::context::SolveContext::with_progress
+
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+/// Receives progress updates from a running solver
+pub trait Progress: Send + Sync {
+    /// the solver is starting a phase with `total` units of work
+    /// (lines to parse, states to explore, ...)
+    fn start(&self, phase: &str, total: u64);
+
+    /// `delta` more units of work have been completed
+    fn advance(&self, delta: u64);
+
+    /// the current phase has completed
+    fn finish(&self) {}
+}
+
+#[cfg(not(feature = "std"))]
+fn with_reporter(_f: impl FnOnce(&dyn Progress)) {}
+
+/// the reporter of the solve context installed on this thread
+#[cfg(feature = "std")]
+fn with_reporter(f: impl FnOnce(&dyn Progress)) {
+    if let Some(reporter) = crate::context::progress() {
+        f(&*reporter);
+    }
+}
+
+/// report that a phase with `total` units of work is starting
+pub fn start(phase: &str, total: u64) {
+    with_reporter(|reporter| reporter.start(phase, total));
+}
+
+/// report that `delta` units of work have been completed
+pub fn advance(delta: u64) {
+    with_reporter(|reporter| reporter.advance(delta));
+}
+
+/// report that the current phase has completed
+pub fn finish() {
+    with_reporter(|reporter| reporter.finish());
+}
+
+/// run `f` with `reporter` receiving any progress reported while it runs,
+/// including from the threads its
+/// [`ExecutionStrategy`](crate::strategy::ExecutionStrategy) hands work to,
+/// and put back the reporter that was receiving it before
+#[cfg(feature = "std")]
+pub fn scoped<T>(reporter: Arc<dyn Progress>, f: impl FnOnce() -> T) -> T {
+    crate::context::amended(|context| context.progress = Some(reporter), f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[derive(Default)]
+    struct Counter {
+        total: AtomicU64,
+        done: AtomicU64,
+    }
+
+    impl Progress for Counter {
+        fn start(&self, _phase: &str, total: u64) {
+            self.total.store(total, Ordering::Relaxed);
+        }
+
+        fn advance(&self, delta: u64) {
+            self.done.fetch_add(delta, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn should_report_only_while_scoped() {
+        let counter = Arc::new(Counter::default());
+        scoped(counter.clone(), || {
+            start("parse", 10);
+            advance(3);
+            advance(4);
+        });
+        // nobody is listening any more, so this is dropped
+        advance(100);
+
+        assert_eq!(counter.total.load(Ordering::Relaxed), 10);
+        assert_eq!(counter.done.load(Ordering::Relaxed), 7);
+    }
+
+    #[test]
+    fn should_put_back_the_outer_reporter() {
+        let outer = Arc::new(Counter::default());
+        let inner = Arc::new(Counter::default());
+        scoped(outer.clone(), || {
+            advance(1);
+            scoped(inner.clone(), || advance(2));
+            advance(4);
+        });
+
+        assert_eq!(outer.done.load(Ordering::Relaxed), 5);
+        assert_eq!(inner.done.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,126 @@
+//! Randomness solvers can reproduce.
+//!
+//! Some puzzles are best answered by randomized algorithms, like Karger's
+//! minimum cut, whose answers and runtimes depend on the numbers drawn.
+//! Solvers draw them from [`rng`], or from [`stream`] when the work is
+//! spread over rayon. Those are seeded from the seed of the [`SolveContext`]
+//! installed on the calling thread, which the caller picks with
+//! [`SolveContext::with_seed`] or [`scoped`], or else [`DEFAULT_SEED`], so
+//! the same seed always makes the same draws and a run can be repeated
+//! exactly. Without the `std` feature it's always [`DEFAULT_SEED`].
+//!
+//! [`SolveContext`]: crate::context::SolveContext
+//! [`SolveContext::with_seed`]: crate::context::SolveContext::with_seed
+
+/// the seed solvers draw from unless the caller picks another
+pub const DEFAULT_SEED: u64 = 2023;
+
+/// the seed solvers should draw from
+#[cfg(not(feature = "std"))]
+pub fn seed() -> u64 {
+    DEFAULT_SEED
+}
+
+/// the seed solvers should draw from, that of the context installed on this
+/// thread
+#[cfg(feature = "std")]
+pub fn seed() -> u64 {
+    crate::context::current(|context| context.seed).unwrap_or(DEFAULT_SEED)
+}
+
+/// run `f` with solvers drawing from `seed`, including on the threads its
+/// [`ExecutionStrategy`](crate::strategy::ExecutionStrategy) hands work to,
+/// and put back the seed they drew from before
+#[cfg(feature = "std")]
+pub fn scoped<T>(seed: u64, f: impl FnOnce() -> T) -> T {
+    crate::context::amended(|context| context.seed = seed, f)
+}
+
+/// a generator seeded from the current seed
+pub fn rng() -> Rng {
+    Rng::new(seed())
+}
+
+/// the `index`th of a family of generators seeded from the current seed, for
+/// work spread over threads: giving each unit of work its own stream means it
+/// draws the same numbers whichever thread picks it up, in whatever order
+pub fn stream(index: u64) -> Rng {
+    Rng::new(seed() ^ Rng::new(index).next_u64())
+}
+
+/// splitmix64: small, fast and plenty random for puzzles, though no good for
+/// anything that has to be unpredictable
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// a number from `start` to `end` inclusive, panicking if `start` is past
+    /// `end`
+    pub fn between(&mut self, start: u64, end: u64) -> u64 {
+        assert!(
+            start <= end,
+            "can't draw a number between {start} and {end}, the start is past the end"
+        );
+        match (end - start).checked_add(1) {
+            Some(span) => start + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    /// one of `items` at random, panicking if there are none
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        assert!(!items.is_empty(), "can't pick one of no items");
+        items[self.between(0, items.len() as u64 - 1) as usize]
+    }
+
+    /// put `items` in a random order
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.between(0, i as u64) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn draws(mut rng: Rng) -> Vec<u64> {
+        (0..4).map(|_| rng.between(1, 6)).collect()
+    }
+
+    #[test]
+    fn should_draw_the_same_numbers_from_the_same_seed() {
+        let first = scoped(7, || draws(rng()));
+        assert_eq!(scoped(7, || draws(rng())), first);
+        assert_ne!(scoped(8, || draws(rng())), first);
+        assert!(first.iter().all(|n| (1..=6).contains(n)));
+
+        let streams = scoped(7, || [draws(stream(0)), draws(stream(1))]);
+        assert_ne!(streams[0], streams[1]);
+        assert_eq!(scoped(7, || draws(stream(1))), streams[1]);
+    }
+
+    #[test]
+    fn should_shuffle_every_item_somewhere() {
+        let mut items: Vec<u64> = (0..50).collect();
+        Rng::new(DEFAULT_SEED).shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+        assert!(Rng::new(1).between(0, u64::MAX) > 0);
+    }
+}
@@ -0,0 +1,180 @@
+//! Brute-force searches over large spaces.
+//!
+//! Some puzzles are easiest answered by trying every candidate, a range of
+//! numbers or a list of guesses, until one works. These split the space into
+//! chunks of [`CHUNK`] candidates that rayon's workers steal from each other,
+//! and stop handing out chunks once an answer has been found. Between chunks
+//! they call [`checkpoint`], so a long search can still be cancelled or run
+//! out of time. Run sequentially, or without the `std` feature, they simply
+//! try the candidates in order.
+
+use core::ops::Range;
+
+#[cfg(feature = "std")]
+use rayon::prelude::*;
+
+#[cfg(feature = "std")]
+use crate::context;
+use crate::{context::checkpoint, error::Result, strategy::ExecutionStrategy};
+
+/// how many candidates a worker tries before checking whether it should stop
+/// or find more work
+pub const CHUNK: u64 = 4096;
+
+/// the candidates in the `index`th chunk of `range`
+fn chunk(range: &Range<u64>, index: u64) -> Range<u64> {
+    let start = range.start + index * CHUNK;
+    start..start.saturating_add(CHUNK).min(range.end)
+}
+
+fn chunks(range: &Range<u64>) -> u64 {
+    (range.end.saturating_sub(range.start)).div_ceil(CHUNK)
+}
+
+/// try the candidates in one chunk in order, stopping at the first answer
+fn search_chunk<R>(
+    range: &Range<u64>,
+    index: u64,
+    f: &impl Fn(u64) -> Option<R>,
+) -> Option<Result<(u64, R)>> {
+    if let Err(e) = checkpoint() {
+        return Some(Err(e));
+    }
+    chunk(range, index).find_map(|n| f(n).map(|answer| Ok((n, answer))))
+}
+
+/// the smallest number in `range` that `f` gives an answer for, with that
+/// answer. On rayon, chunks after one with an answer are abandoned, but
+/// chunks before it are still finished so the answer is the first.
+pub fn first<R: Send>(
+    strategy: ExecutionStrategy,
+    range: Range<u64>,
+    f: impl Fn(u64) -> Option<R> + Sync + Send,
+) -> Result<Option<(u64, R)>> {
+    let found = match strategy {
+        #[cfg(feature = "std")]
+        ExecutionStrategy::Rayon { .. } => (0..chunks(&range))
+            .into_par_iter()
+            .find_map_first(context::carry(|index| search_chunk(&range, index, &f))),
+        _ => (0..chunks(&range)).find_map(|index| search_chunk(&range, index, &f)),
+    };
+    found.transpose()
+}
+
+/// any number in `range` that `f` gives an answer for, with that answer,
+/// stopping every worker as soon as one finds it. Quicker than [`first`]
+/// when any answer will do, but which one is found can differ between runs.
+pub fn any<R: Send>(
+    strategy: ExecutionStrategy,
+    range: Range<u64>,
+    f: impl Fn(u64) -> Option<R> + Sync + Send,
+) -> Result<Option<(u64, R)>> {
+    let found = match strategy {
+        #[cfg(feature = "std")]
+        ExecutionStrategy::Rayon { .. } => (0..chunks(&range))
+            .into_par_iter()
+            .find_map_any(context::carry(|index| search_chunk(&range, index, &f))),
+        _ => (0..chunks(&range)).find_map(|index| search_chunk(&range, index, &f)),
+    };
+    found.transpose()
+}
+
+/// the first of `candidates` that `f` gives an answer for, with its index
+/// and that answer
+pub fn first_of<T: Sync, R: Send>(
+    strategy: ExecutionStrategy,
+    candidates: &[T],
+    f: impl Fn(&T) -> Option<R> + Sync + Send,
+) -> Result<Option<(usize, R)>> {
+    let found = first(strategy, 0..candidates.len() as u64, |i| {
+        f(&candidates[i as usize])
+    })?;
+    Ok(found.map(|(i, answer)| (i as usize, answer)))
+}
+
+/// how many numbers in `range` `f` holds for, for puzzles that ask how many
+/// ways there are rather than for one of them
+pub fn count(
+    strategy: ExecutionStrategy,
+    range: Range<u64>,
+    f: impl Fn(u64) -> bool + Sync + Send,
+) -> Result<u64> {
+    let count_chunk = |index| {
+        checkpoint()?;
+        Ok(chunk(&range, index).filter(|n| f(*n)).count() as u64)
+    };
+    match strategy {
+        #[cfg(feature = "std")]
+        ExecutionStrategy::Rayon { .. } => (0..chunks(&range))
+            .into_par_iter()
+            .map(context::carry(count_chunk))
+            .try_reduce(|| 0, |a, b| Ok(a + b)),
+        _ => (0..chunks(&range)).map(count_chunk).sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    const STRATEGIES: [ExecutionStrategy; 3] = [
+        ExecutionStrategy::Sequential,
+        ExecutionStrategy::Rayon { threads: None },
+        ExecutionStrategy::Rayon { threads: Some(2) },
+    ];
+
+    #[test]
+    fn should_find_the_first_answer_and_stop() -> Result<()> {
+        for strategy in STRATEGIES {
+            let tried = AtomicU64::new(0);
+            // every multiple of 7919 past 100,000 works, in a space far too
+            // big to search to the end
+            let found = strategy.install(|| {
+                first(strategy, 1..u64::MAX, |n| {
+                    tried.fetch_add(1, Ordering::Relaxed);
+                    (n > 100_000 && n % 7919 == 0).then_some(n / 7919)
+                })
+            })?;
+            assert_eq!(found, Some((102_947, 13)));
+            assert!(tried.load(Ordering::Relaxed) < 100_000_000);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_find_any_answer() -> Result<()> {
+        for strategy in STRATEGIES {
+            let found = strategy
+                .install(|| any(strategy, 0..1_000_000, |n| (n % 1000 == 999).then_some(())))?;
+            assert!(found.is_some_and(|(n, _)| n % 1000 == 999));
+            assert_eq!(
+                strategy.install(|| any(strategy, 0..1000, |_| None::<()>))?,
+                None
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_search_candidates_in_order() -> Result<()> {
+        let candidates = ["tried", "almost", "right", "also right"];
+        for strategy in STRATEGIES {
+            let found = first_of(strategy, &candidates, |c| {
+                c.ends_with("right").then_some(c.len())
+            })?;
+            assert_eq!(found, Some((2, 5)));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_count_across_chunks() -> Result<()> {
+        for strategy in STRATEGIES {
+            let counted = strategy.install(|| count(strategy, 3..100_003, |n| n % 3 == 0))?;
+            assert_eq!(counted, 33_334);
+            assert_eq!(count(strategy, 5..5, |_| true)?, 0);
+        }
+        Ok(())
+    }
+}
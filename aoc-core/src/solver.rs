@@ -0,0 +1,627 @@
+//! The interface every day crate implements, so the cli can run any day
+//! without knowing the types it parses its input into.
+//!
+//! Day crates announce themselves with [`register!`], and the cli finds them
+//! with [`find`], so adding a day doesn't mean editing the cli. Both need the
+//! `std` feature, as does timing a solve with [`DynSolver::solve`] and
+//! running one under a [`SolveContext`] with [`DynSolver::solve_with`].
+
+use alloc::{boxed::Box, vec::Vec};
+
+pub use crate::answer::{Answer, Answers, Timings};
+#[cfg(feature = "std")]
+use crate::export::Export;
+#[cfg(feature = "std")]
+use crate::{context::SolveContext, timing::timed};
+use crate::{
+    context::{self, checkpoint},
+    diagnostic::Diagnostic,
+    error::{AocError, Result},
+    incremental::Incremental,
+    metadata::{Metadata, Runtime},
+    persist::Persist,
+    strategy::ExecutionStrategy,
+    telemetry::{span, Phase},
+};
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub use inventory;
+
+/// A day's puzzle. The input is parsed once into [`Solver::Parsed`], which
+/// both parts then work from. Each step is given the [`ExecutionStrategy`]
+/// to spread its work with.
+pub trait Solver {
+    /// the day of December the puzzle unlocks on
+    const DAY: usize;
+
+    /// the puzzle's title on adventofcode.com
+    const TITLE: &'static str;
+
+    /// the techniques the solver uses, like `"grid"` or `"graph"`
+    const TAGS: &'static [&'static str] = &[];
+
+    /// how long the solver expects to take on a real input
+    const RUNTIME: Runtime = Runtime::Instant;
+
+    /// the input in the form both parts work from. It may borrow from the
+    /// text it was parsed from.
+    type Parsed<'a>;
+
+    fn parse(text: &str, strategy: ExecutionStrategy) -> Result<Self::Parsed<'_>>;
+
+    /// parse the raw bytes of an input, such as a memory mapped file. By
+    /// default they're checked to be UTF-8 and handed to [`Solver::parse`];
+    /// days that work on bytes anyway override it to skip the check.
+    fn parse_bytes(bytes: &[u8], strategy: ExecutionStrategy) -> Result<Self::Parsed<'_>> {
+        Self::parse(utf8(bytes)?, strategy)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>, strategy: ExecutionStrategy) -> Result<Answer>;
+
+    fn part2(parsed: &Self::Parsed<'_>, strategy: ExecutionStrategy) -> Result<Answer>;
+}
+
+/// An object safe view of a [`Solver`], so solvers for different days can be
+/// kept side by side and picked at runtime. Every `Solver` is one.
+pub trait DynSolver: Send + Sync {
+    fn day(&self) -> usize;
+
+    fn metadata(&self) -> Metadata;
+
+    /// parse `text` and solve part one. Errors say which day and part failed.
+    fn solve_part_one(&self, text: &str, strategy: ExecutionStrategy) -> Result<Answer>;
+
+    /// parse `text` and solve part two. Errors say which day and part failed.
+    fn solve_part_two(&self, text: &str, strategy: ExecutionStrategy) -> Result<Answer>;
+
+    /// [`DynSolver::solve_part_one`] from the raw bytes of an input
+    fn solve_part_one_bytes(&self, bytes: &[u8], strategy: ExecutionStrategy) -> Result<Answer>;
+
+    /// [`DynSolver::solve_part_two`] from the raw bytes of an input
+    fn solve_part_two_bytes(&self, bytes: &[u8], strategy: ExecutionStrategy) -> Result<Answer>;
+
+    /// parse `text` once and solve both parts from it, timing each step
+    #[cfg(feature = "std")]
+    fn solve(&self, text: &str, strategy: ExecutionStrategy) -> Result<Answers>;
+
+    /// [`DynSolver::solve`] from the raw bytes of an input
+    #[cfg(feature = "std")]
+    fn solve_bytes(&self, bytes: &[u8], strategy: ExecutionStrategy) -> Result<Answers>;
+
+    /// [`DynSolver::solve`] with the context's strategy, giving up between
+    /// steps, or at the solver's own checkpoints, once the context is
+    /// cancelled or past its deadline
+    #[cfg(feature = "std")]
+    fn solve_with(&self, text: &str, context: &SolveContext) -> Result<Answers>;
+}
+
+impl<S: Solver + Send + Sync> DynSolver for S {
+    fn day(&self) -> usize {
+        S::DAY
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            day: S::DAY,
+            title: S::TITLE,
+            tags: S::TAGS,
+            runtime: S::RUNTIME,
+        }
+    }
+
+    fn solve_part_one(&self, text: &str, strategy: ExecutionStrategy) -> Result<Answer> {
+        solve_part::<S>(1, strategy, || parse::<S>(text, strategy))
+    }
+
+    fn solve_part_two(&self, text: &str, strategy: ExecutionStrategy) -> Result<Answer> {
+        solve_part::<S>(2, strategy, || parse::<S>(text, strategy))
+    }
+
+    fn solve_part_one_bytes(&self, bytes: &[u8], strategy: ExecutionStrategy) -> Result<Answer> {
+        solve_part::<S>(1, strategy, || parse_bytes::<S>(bytes, strategy))
+    }
+
+    fn solve_part_two_bytes(&self, bytes: &[u8], strategy: ExecutionStrategy) -> Result<Answer> {
+        solve_part::<S>(2, strategy, || parse_bytes::<S>(bytes, strategy))
+    }
+
+    #[cfg(feature = "std")]
+    fn solve(&self, text: &str, strategy: ExecutionStrategy) -> Result<Answers> {
+        let parse = || parse::<S>(text, strategy);
+        context::fresh(|| strategy.install(|| solve_steps::<S>(parse, strategy, || Ok(()))))
+            .map_err(|e| e.with_day(S::DAY))
+    }
+
+    #[cfg(feature = "std")]
+    fn solve_bytes(&self, bytes: &[u8], strategy: ExecutionStrategy) -> Result<Answers> {
+        let parse = || parse_bytes::<S>(bytes, strategy);
+        context::fresh(|| strategy.install(|| solve_steps::<S>(parse, strategy, || Ok(()))))
+            .map_err(|e| e.with_day(S::DAY))
+    }
+
+    #[cfg(feature = "std")]
+    fn solve_with(&self, text: &str, context: &SolveContext) -> Result<Answers> {
+        let strategy = context.strategy;
+        // there's no point installing a context that's already done with
+        context
+            .check()
+            .and_then(|()| {
+                context::scoped(context, || {
+                    let parse = || parse::<S>(text, strategy);
+                    strategy.install(|| solve_steps::<S>(parse, strategy, || context.check()))
+                })
+            })
+            .map_err(|e| e.with_day(S::DAY))
+    }
+}
+
+/// parse the input once and solve both parts from it, timing each step and
+/// calling `check` before each so the caller can stop the solve between them
+#[cfg(feature = "std")]
+fn solve_steps<'a, S: Solver>(
+    parse: impl FnOnce() -> Result<S::Parsed<'a>>,
+    strategy: ExecutionStrategy,
+    check: impl Fn() -> Result<()>,
+) -> Result<Answers> {
+    check()?;
+    let (parsed, parse) = timed(parse);
+    let parsed = parsed?;
+    check().map_err(|e| e.with_part(1))?;
+    let (part1, part1_time) =
+        timed(|| span(S::DAY, Phase::Part1).in_scope(|| S::part1(&parsed, strategy)));
+    let part1 = part1.map_err(|e| e.with_part(1))?;
+    check().map_err(|e| e.with_part(2))?;
+    let (part2, part2_time) =
+        timed(|| span(S::DAY, Phase::Part2).in_scope(|| S::part2(&parsed, strategy)));
+    let part2 = part2.map_err(|e| e.with_part(2))?;
+    Ok(Answers {
+        part1,
+        part2,
+        timings: Timings {
+            parse,
+            part1: part1_time,
+            part2: part2_time,
+        },
+    })
+}
+
+/// solve one part from whatever `parse` parses, with a memory budget of its
+/// own. Errors say which day and part failed.
+pub(crate) fn solve_part<'a, S: Solver>(
+    part: u8,
+    strategy: ExecutionStrategy,
+    parse: impl FnOnce() -> Result<S::Parsed<'a>> + Send,
+) -> Result<Answer> {
+    context::fresh(|| {
+        strategy.install(|| {
+            checkpoint()?;
+            let parsed = parse()?;
+            checkpoint()?;
+            match part {
+                1 => span(S::DAY, Phase::Part1).in_scope(|| S::part1(&parsed, strategy)),
+                _ => span(S::DAY, Phase::Part2).in_scope(|| S::part2(&parsed, strategy)),
+            }
+        })
+    })
+    .map_err(|e| e.with_day(S::DAY).with_part(part))
+}
+
+/// parse `text` inside its own span, so it's timed apart from the parts
+pub(crate) fn parse<S: Solver>(text: &str, strategy: ExecutionStrategy) -> Result<S::Parsed<'_>> {
+    span(S::DAY, Phase::Parse).in_scope(|| S::parse(text, strategy))
+}
+
+/// [`parse`] from the raw bytes of the input
+fn parse_bytes<S: Solver>(bytes: &[u8], strategy: ExecutionStrategy) -> Result<S::Parsed<'_>> {
+    span(S::DAY, Phase::Parse).in_scope(|| S::parse_bytes(bytes, strategy))
+}
+
+/// `bytes` as text, or an error pointing at the line that isn't UTF-8
+fn utf8(bytes: &[u8]) -> Result<&str> {
+    core::str::from_utf8(bytes).map_err(|e| {
+        let line = bytes[..e.valid_up_to()]
+            .iter()
+            .filter(|byte| **byte == b'\n')
+            .count();
+        AocError::new("input is not valid UTF-8").at_line(line + 1)
+    })
+}
+
+/// A day's solver and the extras the cli offers alongside it
+pub struct Registration {
+    pub solver: &'static dyn DynSolver,
+    /// the example inputs from the puzzle description for part one and part two
+    pub examples: [&'static str; 2],
+    /// the answers the puzzle description gives for each part's example, or
+    /// `None` for a part that isn't solved yet
+    pub expected: fn() -> [Option<Answer>; 2],
+    /// checks an input without solving it
+    pub validate: fn(&str) -> Vec<Diagnostic>,
+    /// starts a solve that keeps its answers up to date as the input is
+    /// edited, for days that can
+    pub incremental: Option<fn() -> Box<dyn Incremental>>,
+    /// saves a parsed input so later runs can skip parsing it, for days whose
+    /// parsed input can be serialized
+    pub persist: Option<&'static dyn Persist>,
+    /// describes what an input parsed into, for days with a [`Model`]
+    ///
+    /// [`Model`]: crate::export::Model
+    #[cfg(feature = "std")]
+    pub export: Option<&'static dyn Export>,
+    /// the implementations the day ships, its [`Solver`] first, or none for
+    /// a day with only the one
+    pub algorithms: &'static [Algorithm],
+}
+
+/// A part solver that parses the input itself, as a day's other
+/// [`Algorithm`]s do
+pub type PartFn = fn(&str) -> Result<Answer>;
+
+/// One of the implementations a day ships, which callers pick between by
+/// name, like the cli's `--algo`
+#[derive(Clone, Copy, Debug)]
+pub struct Algorithm {
+    pub name: &'static str,
+    /// how the algorithm solves each part, `None` for a part it solves the
+    /// way the day's [`Solver`] does
+    pub parts: [Option<PartFn>; 2],
+}
+
+impl Algorithm {
+    /// the day's [`Solver`] itself, under `name`
+    pub const fn solver(name: &'static str) -> Algorithm {
+        Algorithm {
+            name,
+            parts: [None, None],
+        }
+    }
+
+    /// both parts solved by functions of their own
+    pub const fn new(name: &'static str, part_one: PartFn, part_two: PartFn) -> Algorithm {
+        Algorithm {
+            name,
+            parts: [Some(part_one), Some(part_two)],
+        }
+    }
+
+    /// the function solving `part` (1 or 2), if the algorithm has its own
+    pub fn part(&self, part: u8) -> Option<PartFn> {
+        match part {
+            1 => self.parts[0],
+            _ => self.parts[1],
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Registration {
+    /// the algorithm named `name`, if the day ships one by that name
+    pub fn algorithm(&self, name: &str) -> Option<&'static Algorithm> {
+        self.algorithms
+            .iter()
+            .find(|algorithm| algorithm.name == name)
+    }
+
+    /// parse `text` and solve one part (1 or 2) with `algorithm`, or with the
+    /// day's [`Solver`] when that's `None`. Errors say which day and part
+    /// failed.
+    pub fn solve_part(
+        &self,
+        algorithm: Option<&Algorithm>,
+        part: u8,
+        text: &str,
+        strategy: ExecutionStrategy,
+    ) -> Result<Answer> {
+        match (algorithm.and_then(|algorithm| algorithm.part(part)), part) {
+            (Some(solve), _) => context::fresh(|| solve(text))
+                .map_err(|e| e.with_day(self.solver.day()).with_part(part)),
+            (None, 1) => self.solver.solve_part_one(text, strategy),
+            (None, _) => self.solver.solve_part_two(text, strategy),
+        }
+    }
+
+    /// [`DynSolver::solve_with`] with `algorithm`, or with the day's
+    /// [`Solver`] when that's `None`. An algorithm's parts parse the input
+    /// themselves, so parsing is timed as part of each.
+    pub fn solve_with(
+        &self,
+        algorithm: Option<&Algorithm>,
+        text: &str,
+        context: &SolveContext,
+    ) -> Result<Answers> {
+        let Some(algorithm) =
+            algorithm.filter(|algorithm| algorithm.parts.iter().any(Option::is_some))
+        else {
+            return self.solver.solve_with(text, context);
+        };
+        let solve = |part| -> Result<_> {
+            context.check().map_err(|e| e.with_part(part))?;
+            let (answer, time) =
+                timed(|| self.solve_part(Some(algorithm), part, text, context.strategy));
+            Ok((answer?, time))
+        };
+        context::scoped(context, || {
+            let (part1, part1_time) = solve(1)?;
+            let (part2, part2_time) = solve(2)?;
+            Ok(Answers {
+                part1,
+                part2,
+                timings: Timings {
+                    part1: part1_time,
+                    part2: part2_time,
+                    ..Timings::default()
+                },
+            })
+        })
+        .map_err(|e: AocError| e.with_day(self.solver.day()))
+    }
+}
+
+#[cfg(feature = "std")]
+inventory::collect!(Registration);
+
+/// Register a day with the cli. Registrations are collected when the
+/// binary is linked, so a day crate only has to be a dependency of the cli
+/// to be found.
+///
+/// The expected answers are checked against the examples by the cli's
+/// tests, so a day doesn't need its own example tests.
+///
+/// ```ignore
+/// aoc_core::register! {
+///     solver: Day1,
+///     examples: [PART_ONE_EXAMPLE, PART_TWO_EXAMPLE],
+///     expected: [Some(Answer::U64(142)), Some(Answer::U64(281))],
+///     validate: validate,
+///     algorithms: &[
+///         Algorithm::solver("scan"),
+///         Algorithm::new("regex", regex::solve_part_one, regex::solve_part_two),
+///     ],
+///     incremental: || alloc::boxed::Box::new(Calibrations::default()),
+/// }
+/// ```
+///
+/// `incremental` can be left out by days that can't update their answers
+/// a few lines at a time. Days whose parsed input can be serialized add
+/// `persist: DayN` so the cli can save it between runs, and days with a
+/// [`Model`](crate::export::Model) add `export: DayN` for `aoc parse`. Both
+/// are only registered with the day crate's own `serde` feature on, which is
+/// what makes its parsed input serializable.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! register {
+    (
+        solver: $solver:expr,
+        examples: $examples:expr,
+        expected: [$part_one:expr, $part_two:expr],
+        validate: $validate:expr
+        $(, algorithms: $algorithms:expr)?
+        $(, incremental: $incremental:expr)?
+        $(, persist: $persist:expr)?
+        $(, export: $export:expr)? $(,)?
+    ) => {
+        $crate::solver::inventory::submit! {
+            $crate::solver::Registration {
+                solver: &$solver,
+                examples: $examples,
+                expected: || [$part_one, $part_two],
+                validate: $validate,
+                incremental: $crate::register!(@optional $($incremental)?),
+                persist: $crate::register!(@serde $crate::persist::Persist $(, &$persist)?),
+                export: $crate::register!(@serde $crate::export::Export $(, &$export)?),
+                algorithms: $crate::register!(@algorithms $($algorithms)?),
+            }
+        }
+    };
+    (@optional) => {
+        None
+    };
+    (@optional $value:expr) => {
+        Some($value)
+    };
+    (@algorithms) => {
+        &[]
+    };
+    (@algorithms $algorithms:expr) => {
+        $algorithms
+    };
+    (@serde $trait:path) => {
+        None
+    };
+    // the `cfg` is expanded in the day crate, so it's that crate's feature
+    (@serde $trait:path, $value:expr) => {{
+        #[allow(unused_mut, unused_assignments)]
+        let mut value: Option<&'static dyn $trait> = None;
+        #[cfg(feature = "serde")]
+        {
+            value = Some($value);
+        }
+        value
+    }};
+}
+
+/// Without `std` there's nowhere to register days, so registering does nothing
+#[cfg(not(feature = "std"))]
+#[macro_export]
+macro_rules! register {
+    ($($registration:tt)*) => {};
+}
+
+/// every registered day, in order
+#[cfg(feature = "std")]
+pub fn registered() -> Vec<&'static Registration> {
+    let mut days: Vec<&Registration> = inventory::iter::<Registration>.into_iter().collect();
+    days.sort_by_key(|registration| registration.solver.day());
+    days
+}
+
+/// the registration for `day`, if a linked crate registered it
+#[cfg(feature = "std")]
+pub fn find(day: usize) -> Option<&'static Registration> {
+    inventory::iter::<Registration>
+        .into_iter()
+        .find(|registration| registration.solver.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// sums the numbers on each line, or multiplies them for part two
+    struct Sums;
+
+    impl Solver for Sums {
+        const DAY: usize = 0;
+        const TITLE: &'static str = "Sums";
+        const TAGS: &'static [&'static str] = &["arithmetic"];
+        type Parsed<'a> = Vec<&'a str>;
+
+        fn parse(text: &str, _strategy: ExecutionStrategy) -> Result<Vec<&str>> {
+            Ok(text.lines().collect())
+        }
+
+        fn part1(lines: &Vec<&str>, strategy: ExecutionStrategy) -> Result<Answer> {
+            let numbers = strategy.try_map(lines, |_, line| Ok(line.parse::<u64>()?))?;
+            Ok(numbers.iter().sum::<u64>().into())
+        }
+
+        fn part2(lines: &Vec<&str>, strategy: ExecutionStrategy) -> Result<Answer> {
+            let numbers = strategy.try_map(lines, |_, line| Ok(line.parse::<u64>()?))?;
+            Ok(numbers.iter().product::<u64>().into())
+        }
+    }
+
+    /// adds the numbers as they're parsed, without keeping them
+    fn streamed_sum(text: &str) -> Result<Answer> {
+        let mut sum = 0;
+        for line in text.lines() {
+            sum += line.parse::<u64>()?;
+        }
+        Ok(sum.into())
+    }
+
+    crate::register! {
+        solver: Sums,
+        examples: ["1\n2", "3\n4"],
+        expected: [Some(Answer::U64(3)), Some(Answer::U64(12))],
+        validate: |_| vec![],
+        algorithms: &[
+            Algorithm::solver("parsed"),
+            Algorithm {
+                name: "streamed",
+                parts: [Some(streamed_sum), None],
+            },
+        ],
+    }
+
+    #[test]
+    fn should_find_registered_solvers() -> Result<()> {
+        let registration = find(0).ok_or(AocError::new("day 0 missing"))?;
+        assert_eq!(
+            registration
+                .solver
+                .solve_part_one(registration.examples[0], ExecutionStrategy::Sequential)?,
+            3
+        );
+        assert_eq!(
+            (registration.expected)(),
+            [Some(Answer::U64(3)), Some(Answer::U64(12))]
+        );
+        assert!(find(1).is_none());
+        assert_eq!(registered().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn should_solve_with_a_registered_algorithm() -> Result<()> {
+        let registration = find(0).ok_or(AocError::new("day 0 missing"))?;
+        let streamed = registration.algorithm("streamed");
+        assert!(streamed.is_some());
+        assert!(registration.algorithm("bitset").is_none());
+
+        let context = SolveContext::new(ExecutionStrategy::Sequential);
+        let answers = registration.solve_with(streamed, "2\n3\n4", &context)?;
+        assert_eq!(
+            [answers.part1, answers.part2],
+            [Answer::U64(9), Answer::U64(24)]
+        );
+        let error = registration
+            .solve_part(streamed, 1, "2\nx", ExecutionStrategy::Sequential)
+            .unwrap_err();
+        assert_eq!((error.day, error.part), (Some(0), Some(1)));
+        Ok(())
+    }
+
+    #[test]
+    fn should_solve_through_trait_objects() -> Result<()> {
+        let solvers: [&dyn DynSolver; 1] = [&Sums];
+        assert_eq!(solvers[0].day(), 0);
+        assert_eq!(
+            solvers[0].metadata(),
+            Metadata {
+                day: 0,
+                title: "Sums",
+                tags: &["arithmetic"],
+                runtime: Runtime::Instant,
+            }
+        );
+        for strategy in [
+            ExecutionStrategy::Sequential,
+            ExecutionStrategy::Rayon { threads: Some(2) },
+        ] {
+            assert_eq!(solvers[0].solve_part_one("2\n3\n4", strategy)?, 9);
+            assert_eq!(solvers[0].solve_part_two("2\n3\n4", strategy)?, 24);
+            assert_eq!(solvers[0].solve_part_one_bytes(b"2\n3\n4", strategy)?, 9);
+            let error = solvers[0].solve_part_one("2\nx", strategy).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "day 0 part 1: invalid digit found in string"
+            );
+            let error = solvers[0]
+                .solve_part_two_bytes(b"2\n\xff", strategy)
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "day 0 part 2, line 2: input is not valid UTF-8"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_solve_both_parts_from_one_parse() -> Result<()> {
+        let answers = Sums.solve("2\n3\n4", ExecutionStrategy::Sequential)?;
+        assert_eq!(
+            (answers.part1, answers.part2),
+            (Answer::U64(9), Answer::U64(24))
+        );
+        let error = Sums
+            .solve("2\nx", ExecutionStrategy::Sequential)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 0 part 1: invalid digit found in string"
+        );
+        let answers = Sums.solve_bytes(b"2\n3\n4", ExecutionStrategy::Sequential)?;
+        assert_eq!(answers.part2, Answer::U64(24));
+        let error = Sums
+            .solve_bytes(b"2\n\xff", ExecutionStrategy::Sequential)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "day 0, line 2: input is not valid UTF-8");
+        Ok(())
+    }
+
+    #[test]
+    fn should_stop_a_cancelled_solve() -> Result<()> {
+        let cancellation = crate::context::CancellationToken::new();
+        let context = SolveContext::new(ExecutionStrategy::Sequential)
+            .with_cancellation(cancellation.clone());
+        assert_eq!(Sums.solve_with("2\n3\n4", &context)?.part2, Answer::U64(24));
+
+        cancellation.cancel();
+        let error = Sums.solve_with("2\n3\n4", &context).unwrap_err();
+        assert_eq!(error.to_string(), "day 0: cancelled");
+        Ok(())
+    }
+}
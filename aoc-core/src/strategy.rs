@@ -0,0 +1,125 @@
+//! How a solver spreads its work over threads.
+//!
+//! Solvers are handed an [`ExecutionStrategy`] and do their per-line or
+//! per-item work through [`ExecutionStrategy::map`] and
+//! [`ExecutionStrategy::try_map`], so each day has a single code path that
+//! runs either sequentially or on rayon, picked at runtime. Work handed to
+//! rayon carries the calling thread's [`SolveContext`] with it. Rayon needs
+//! the `std` feature; without it every strategy runs sequentially.
+//!
+//! [`SolveContext`]: crate::context::SolveContext
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+#[cfg(feature = "std")]
+use crate::{context, error::AocError};
+
+/// Whether a solve runs on the calling thread or on rayon
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ExecutionStrategy {
+    #[default]
+    Sequential,
+    /// run on a rayon pool of `threads` threads, or on the current pool
+    /// (the global one unless the caller installed another) when `None`
+    Rayon { threads: Option<usize> },
+}
+
+impl ExecutionStrategy {
+    /// run `f`, inside a pool of the requested size if there is one
+    pub fn install<R: Send>(self, f: impl FnOnce() -> Result<R> + Send) -> Result<R> {
+        match self {
+            #[cfg(feature = "std")]
+            ExecutionStrategy::Rayon {
+                threads: Some(threads),
+            } => rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .map_err(|e| AocError::new(format!("failed to start {threads} threads: {e}")))?
+                .install(context::carry_once(f)),
+            _ => f(),
+        }
+    }
+
+    /// apply `f` to every item, keeping their order
+    pub fn map<T: Sync, R: Send>(self, items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+        match self {
+            #[cfg(feature = "std")]
+            ExecutionStrategy::Rayon { .. } => items.par_iter().map(context::carry(f)).collect(),
+            _ => items.iter().map(f).collect(),
+        }
+    }
+
+    /// apply `f` to every item along with its index, keeping their order.
+    /// When several items fail the error is the first one's, whichever
+    /// thread got to it first.
+    pub fn try_map<T: Sync, R: Send>(
+        self,
+        items: &[T],
+        f: impl Fn(usize, &T) -> Result<R> + Sync + Send,
+    ) -> Result<Vec<R>> {
+        match self {
+            #[cfg(feature = "std")]
+            ExecutionStrategy::Rayon { .. } => items
+                .par_iter()
+                .enumerate()
+                .map(context::carry(|(i, item)| f(i, item)))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .collect(),
+            _ => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| f(i, item))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STRATEGIES: [ExecutionStrategy; 3] = [
+        ExecutionStrategy::Sequential,
+        ExecutionStrategy::Rayon { threads: None },
+        ExecutionStrategy::Rayon { threads: Some(2) },
+    ];
+
+    #[test]
+    fn should_map_in_order_whatever_the_strategy() -> Result<()> {
+        let items: Vec<u64> = (0..1000).collect();
+        for strategy in STRATEGIES {
+            let doubled = strategy.install(|| Ok(strategy.map(&items, |n| n * 2)))?;
+            assert_eq!(doubled, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_report_the_first_failure() {
+        let items: Vec<u64> = (0..1000).collect();
+        for strategy in STRATEGIES {
+            let result = strategy.install(|| {
+                strategy.try_map(&items, |i, n| match n % 300 {
+                    299 => Err(AocError::new("too big").at_line(i + 1)),
+                    _ => Ok(*n),
+                })
+            });
+            assert_eq!(result, Err(AocError::new("too big").at_line(300)));
+        }
+    }
+
+    #[test]
+    fn should_size_the_pool_it_was_asked_for() -> Result<()> {
+        let strategy = ExecutionStrategy::Rayon { threads: Some(3) };
+        assert_eq!(strategy.install(|| Ok(rayon::current_num_threads()))?, 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+//! Structured tracing for the phases of a solve.
+//!
+//! Every [`Solver`](crate::solver::Solver) run through
+//! [`DynSolver`](crate::solver::DynSolver) gets a `parse`, `part1` or `part2`
+//! span at debug level, tagged with the day. Solvers fill in the span's
+//! counters with [`lines`] and [`entries`] as they work, so every day reports
+//! the same fields whichever subscriber is listening. Outside of a phase
+//! span, or with no subscriber, the counters go nowhere.
+
+use tracing::{debug_span, field::Empty, Span};
+
+/// One of the steps a solve is split into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+/// a span for `phase` of `day`, with its counters left to be recorded
+pub fn span(day: usize, phase: Phase) -> Span {
+    // span names have to be known at compile time, hence one macro per phase
+    match phase {
+        Phase::Parse => debug_span!("parse", day, lines = Empty, entries = Empty),
+        Phase::Part1 => debug_span!("part1", day, lines = Empty, entries = Empty),
+        Phase::Part2 => debug_span!("part2", day, lines = Empty, entries = Empty),
+    }
+}
+
+/// record that the current phase read `count` lines of input
+pub fn lines(count: usize) {
+    Span::current().record("lines", count);
+}
+
+/// record that the current phase inserted `count` entries into whatever
+/// collections it builds (numbers found, table rows, map keys, ...)
+pub fn entries(count: usize) {
+    Span::current().record("entries", count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Subscriber,
+    };
+    use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+    /// a span's name and the fields recorded on it
+    type Recorded = (String, Vec<(String, u64)>);
+
+    /// keeps every span opened while it's listening
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<Recorded>>>);
+
+    struct Fields<'a>(&'a mut Vec<(String, u64)>);
+
+    impl Visit for Fields<'_> {
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            self.0.push((field.name().to_string(), value));
+        }
+
+        fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+    }
+
+    impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Recorder {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            let mut fields = vec![];
+            attrs.record(&mut Fields(&mut fields));
+            let name = attrs.metadata().name().to_string();
+            self.0.lock().unwrap().push((name, fields));
+        }
+
+        fn on_record(&self, _id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+            let mut spans = self.0.lock().unwrap();
+            if let Some((_, fields)) = spans.last_mut() {
+                values.record(&mut Fields(fields));
+            }
+        }
+    }
+
+    #[test]
+    fn should_record_counters_on_the_phase_span() {
+        let recorder = Recorder::default();
+        let subscriber = tracing_subscriber::registry().with(recorder.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = span(4, Phase::Parse).entered();
+            lines(6);
+            entries(48);
+        });
+
+        let spans = recorder.0.lock().unwrap();
+        let fields = vec![
+            ("day".to_string(), 4),
+            ("lines".to_string(), 6),
+            ("entries".to_string(), 48),
+        ];
+        assert_eq!(*spans, vec![("parse".to_string(), fields)]);
+    }
+
+    #[test]
+    fn should_ignore_counters_outside_a_phase() {
+        lines(1);
+        entries(1);
+    }
+}
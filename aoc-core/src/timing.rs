@@ -0,0 +1,11 @@
+//! Wall-clock timing, shared by [`DynSolver::solve`](crate::solver::DynSolver::solve)
+//! and the cli so a phase is timed the same way wherever it's reported
+
+use std::time::{Duration, Instant};
+
+/// run a closure, returning its result along with the wall-clock time it took
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
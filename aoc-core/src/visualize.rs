@@ -0,0 +1,175 @@
+//! Frames of a solve, for animating it.
+//!
+//! Solvers that have something worth watching call [`emit`] as they work,
+//! with a closure building a [`Frame`]: the grid as it stands, some cells
+//! highlighted and a few lines of annotation. Like progress reporting, the
+//! closure is only run when the caller has given the solve a [`Visualize`]
+//! sink, with [`scoped`] or [`SolveContext::with_visualize`], so frames cost
+//! nothing in normal runs, and never without the `std` feature. Frames
+//! emitted from rayon workers arrive in whatever order they're built, so
+//! visualizations are best watched sequentially.
+//!
+//! [`SolveContext::with_visualize`]: crate::context::SolveContext::with_visualize
+
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// What a highlighted cell means, which the player picks a color for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Mark {
+    /// what the solver is looking at now
+    Current,
+    /// counted towards the answer
+    Accepted,
+    /// looked at and left out of the answer
+    Rejected,
+}
+
+/// A cell of the frame's grid to draw attention to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Highlight {
+    pub row: usize,
+    pub column: usize,
+    pub mark: Mark,
+}
+
+/// One picture of a solve in progress, built up from [`Frame::new`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Frame {
+    /// the grid, a row of text per line
+    pub rows: Vec<String>,
+    pub highlights: Vec<Highlight>,
+    /// lines shown under the grid, like a running total
+    pub annotations: Vec<String>,
+}
+
+impl Frame {
+    pub fn new(rows: Vec<String>) -> Frame {
+        Frame {
+            rows,
+            ..Frame::default()
+        }
+    }
+
+    pub fn highlight(mut self, row: usize, column: usize, mark: Mark) -> Frame {
+        self.highlights.push(Highlight { row, column, mark });
+        self
+    }
+
+    pub fn annotate(mut self, annotation: impl Into<String>) -> Frame {
+        self.annotations.push(annotation.into());
+        self
+    }
+
+    /// the mark on the cell at `row` and `column`, the last one given when
+    /// it was highlighted more than once
+    pub fn mark(&self, row: usize, column: usize) -> Option<Mark> {
+        self.highlights
+            .iter()
+            .rev()
+            .find(|highlight| highlight.row == row && highlight.column == column)
+            .map(|highlight| highlight.mark)
+    }
+}
+
+/// Receives the frames a solver emits
+pub trait Visualize: Send + Sync {
+    fn frame(&self, frame: Frame);
+}
+
+/// whether anyone is watching, for solvers that would have to do extra work
+/// to emit frames at all
+#[cfg(not(feature = "std"))]
+pub fn is_active() -> bool {
+    false
+}
+
+/// whether anyone is watching, for solvers that would have to do extra work
+/// to emit frames at all
+#[cfg(feature = "std")]
+pub fn is_active() -> bool {
+    crate::context::current(|context| context.visualize.is_some()).unwrap_or_default()
+}
+
+/// emit the frame `frame` builds, only building it when someone is watching
+#[cfg(not(feature = "std"))]
+pub fn emit(_frame: impl FnOnce() -> Frame) {}
+
+/// emit the frame `frame` builds, only building it when someone is watching
+#[cfg(feature = "std")]
+pub fn emit(frame: impl FnOnce() -> Frame) {
+    let sink = crate::context::current(|context| context.visualize.clone()).flatten();
+    if let Some(sink) = sink {
+        sink.frame(frame());
+    }
+}
+
+/// run `f` with `sink` receiving any frames emitted while it runs, including
+/// from the threads its
+/// [`ExecutionStrategy`](crate::strategy::ExecutionStrategy) hands work to,
+/// and put back the sink that was receiving them before
+#[cfg(feature = "std")]
+pub fn scoped<T>(sink: Arc<dyn Visualize>, f: impl FnOnce() -> T) -> T {
+    crate::context::amended(|context| context.visualize = Some(sink), f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<Frame>>);
+
+    impl Visualize for Recorder {
+        fn frame(&self, frame: Frame) {
+            self.0.lock().unwrap().push(frame);
+        }
+    }
+
+    #[test]
+    fn should_emit_only_while_scoped() {
+        let recorder = Arc::new(Recorder::default());
+        let frame = || {
+            Frame::new(vec!["12.".to_string(), "..*".to_string()])
+                .highlight(0, 1, Mark::Current)
+                .highlight(0, 1, Mark::Accepted)
+                .annotate("12 is next to *")
+        };
+        scoped(recorder.clone(), || emit(frame));
+        // nobody is watching any more, so this isn't even built
+        emit(|| unreachable!("frames aren't built without a sink"));
+
+        let frames = recorder.0.lock().unwrap();
+        assert_eq!(*frames, vec![frame()]);
+        assert_eq!(frames[0].mark(0, 1), Some(Mark::Accepted));
+        assert_eq!(frames[0].mark(1, 2), None);
+    }
+
+    #[test]
+    fn should_put_back_the_outer_sink() {
+        let outer = Arc::new(Recorder::default());
+        let inner = Arc::new(Recorder::default());
+        let frame = |row: &str| Frame::new(vec![row.to_string()]);
+        scoped(outer.clone(), || {
+            emit(|| frame("outer"));
+            scoped(inner.clone(), || emit(|| frame("inner")));
+            emit(|| frame("outer again"));
+        });
+
+        assert_eq!(
+            *outer.0.lock().unwrap(),
+            vec![frame("outer"), frame("outer again")]
+        );
+        assert_eq!(*inner.0.lock().unwrap(), vec![frame("inner")]);
+    }
+}
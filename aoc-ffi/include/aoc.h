@@ -0,0 +1,41 @@
+/* C interface to the aoc2023 solvers, implemented by the aoc-ffi crate */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stdint.h>
+
+#define AOC_OK 0
+#define AOC_ERR_NULL_POINTER -1
+#define AOC_ERR_INVALID_UTF8 -2
+#define AOC_ERR_UNKNOWN_DAY -3
+#define AOC_ERR_INVALID_PART -4
+#define AOC_ERR_SOLVE -5
+#define AOC_ERR_PANIC -6
+#define AOC_ERR_ANSWER_TYPE -7
+#define AOC_ERR_UNKNOWN_ALGO -8
+
+/*
+ * Solve one part (1 or 2) of a day's puzzle for the NUL terminated, UTF-8
+ * puzzle input. On success the answer is written to *answer and AOC_OK is
+ * returned, otherwise one of the AOC_ERR_* codes is returned and *answer is
+ * left untouched. Answers that are not unsigned integers fail with
+ * AOC_ERR_ANSWER_TYPE.
+ */
+int32_t aoc_solve(uint32_t day, uint32_t part, const char *input, uint64_t *answer);
+
+/*
+ * aoc_solve with the algorithm the day registers as algo, a NUL terminated
+ * name such as "arena", or the day's usual solver when algo is NULL. Names
+ * the day does not register fail with AOC_ERR_UNKNOWN_ALGO.
+ */
+int32_t aoc_solve_algo(uint32_t day, uint32_t part, const char *algo, const char *input,
+                       uint64_t *answer);
+
+/*
+ * A description of the last error returned on the calling thread, or NULL if
+ * there has not been one. The string is owned by the library and is valid
+ * until the next call to aoc_solve on the same thread.
+ */
+const char *aoc_last_error(void);
+
+#endif
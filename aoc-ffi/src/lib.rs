@@ -0,0 +1,201 @@
+//! C ABI for the solvers, so they can be embedded in programs that aren't
+//! written in Rust. The matching declarations are in `include/aoc.h`.
+
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+use aoc_core::answer::Answer;
+use aoc_core::solver::{self, Registration};
+use aoc_core::strategy::ExecutionStrategy;
+// days register their solvers with `aoc_core::register!`, which is lost for
+// a crate that's never named, so name each one to have it linked in
+use day1 as _;
+use day2 as _;
+use day3 as _;
+use day4 as _;
+
+pub const AOC_OK: i32 = 0;
+pub const AOC_ERR_NULL_POINTER: i32 = -1;
+pub const AOC_ERR_INVALID_UTF8: i32 = -2;
+pub const AOC_ERR_UNKNOWN_DAY: i32 = -3;
+pub const AOC_ERR_INVALID_PART: i32 = -4;
+pub const AOC_ERR_SOLVE: i32 = -5;
+pub const AOC_ERR_PANIC: i32 = -6;
+pub const AOC_ERR_ANSWER_TYPE: i32 = -7;
+pub const AOC_ERR_UNKNOWN_ALGO: i32 = -8;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// remember `message` as the calling thread's last error and return `code`
+fn fail(code: i32, message: String) -> i32 {
+    // interior NULs would truncate the message in C anyway
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+    code
+}
+
+/// the registration for `day`, if it has been implemented
+fn lookup(day: u32) -> Option<&'static Registration> {
+    solver::find(usize::try_from(day).ok()?)
+}
+
+/// Solve one part (1 or 2) of a day's puzzle for the NUL terminated, UTF-8
+/// puzzle `input`. On success the answer is written to `answer` and
+/// [`AOC_OK`] is returned, otherwise one of the `AOC_ERR_*` codes is returned,
+/// `answer` is left untouched and [`aoc_last_error`] describes what went wrong.
+/// Answers that aren't unsigned integers fail with [`AOC_ERR_ANSWER_TYPE`].
+///
+/// # Safety
+///
+/// `input` must be null or point to a NUL terminated string, and `answer`
+/// must be null or valid for writing a `u64`.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input: *const c_char,
+    answer: *mut u64,
+) -> i32 {
+    aoc_solve_algo(day, part, ptr::null(), input, answer)
+}
+
+/// [`aoc_solve`] with the algorithm the day registers as `algo`, a NUL
+/// terminated name such as `"arena"`, or the day's usual solver when `algo`
+/// is null. Names the day doesn't register fail with
+/// [`AOC_ERR_UNKNOWN_ALGO`].
+///
+/// # Safety
+///
+/// `algo` must be null or point to a NUL terminated string, and `input` and
+/// `answer` must be as [`aoc_solve`] needs them.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve_algo(
+    day: u32,
+    part: u32,
+    algo: *const c_char,
+    input: *const c_char,
+    answer: *mut u64,
+) -> i32 {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+    if input.is_null() || answer.is_null() {
+        return fail(
+            AOC_ERR_NULL_POINTER,
+            "input and answer must not be null".into(),
+        );
+    }
+    let text = match CStr::from_ptr(input).to_str() {
+        Ok(text) => text,
+        Err(e) => return fail(AOC_ERR_INVALID_UTF8, format!("input is not utf-8: {e}")),
+    };
+    if !(1..=2).contains(&part) {
+        return fail(
+            AOC_ERR_INVALID_PART,
+            format!("part must be 1 or 2, not {part}"),
+        );
+    }
+    let Some(registration) = lookup(day) else {
+        return fail(AOC_ERR_UNKNOWN_DAY, format!("no solver for day {day}"));
+    };
+    let algorithm = match algo.is_null() {
+        true => None,
+        false => {
+            let name = CStr::from_ptr(algo).to_string_lossy();
+            match registration.algorithm(&name) {
+                Some(algorithm) => Some(algorithm),
+                None => {
+                    return fail(
+                        AOC_ERR_UNKNOWN_ALGO,
+                        format!("day {day} has no algorithm named {name:?}"),
+                    )
+                }
+            }
+        }
+    };
+    let solve =
+        || registration.solve_part(algorithm, part as u8, text, ExecutionStrategy::Sequential);
+
+    // unwinding across the C boundary is undefined behaviour
+    match panic::catch_unwind(AssertUnwindSafe(solve)) {
+        Ok(Ok(Answer::U64(result))) => {
+            *answer = result;
+            AOC_OK
+        }
+        Ok(Ok(result)) => fail(
+            AOC_ERR_ANSWER_TYPE,
+            format!("answer {result} is not an unsigned integer"),
+        ),
+        Ok(Err(e)) => fail(AOC_ERR_SOLVE, e.to_string()),
+        Err(_) => fail(AOC_ERR_PANIC, format!("solver for day {day} panicked")),
+    }
+}
+
+/// A description of the last error returned on the calling thread, or null
+/// if there hasn't been one. The string is owned by the library and is valid
+/// until the next call to [`aoc_solve`] on the same thread.
+#[no_mangle]
+pub extern "C" fn aoc_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| match &*last.borrow() {
+        Some(message) => message.as_ptr(),
+        None => ptr::null(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(day: u32, part: u32, input: &str) -> (i32, u64) {
+        let input = CString::new(input).unwrap();
+        let mut answer = 0;
+        let code = unsafe { aoc_solve(day, part, input.as_ptr(), &mut answer) };
+        (code, answer)
+    }
+
+    fn solve_algo(day: u32, part: u32, algo: &str, input: &str) -> (i32, u64) {
+        let algo = CString::new(algo).unwrap();
+        let input = CString::new(input).unwrap();
+        let mut answer = 0;
+        let code = unsafe { aoc_solve_algo(day, part, algo.as_ptr(), input.as_ptr(), &mut answer) };
+        (code, answer)
+    }
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(aoc_last_error()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn should_solve_through_the_c_abi() {
+        let example = include_str!("../../day4/src/part1_example.txt");
+        assert_eq!(solve(4, 1, example), (AOC_OK, 13));
+        assert_eq!(solve(4, 2, example), (AOC_OK, 30));
+        assert!(aoc_last_error().is_null());
+    }
+
+    #[test]
+    fn should_solve_with_a_registered_algorithm() {
+        let example = include_str!("../../day3/src/part1_example.txt");
+        assert_eq!(solve_algo(3, 1, "arena", example), (AOC_OK, 4361));
+        assert_eq!(solve_algo(3, 2, "vec", example), (AOC_OK, 467835));
+        assert_eq!(solve_algo(3, 1, "bitset", example).0, AOC_ERR_UNKNOWN_ALGO);
+        assert!(last_error().contains("bitset"));
+    }
+
+    #[test]
+    fn should_return_error_codes() {
+        assert_eq!(solve(99, 1, "").0, AOC_ERR_UNKNOWN_DAY);
+        assert_eq!(solve(4, 3, "").0, AOC_ERR_INVALID_PART);
+        assert_eq!(solve(4, 1, "not a card").0, AOC_ERR_SOLVE);
+        assert!(last_error().contains("malformatted line"));
+
+        let code = unsafe { aoc_solve(4, 1, ptr::null(), ptr::null_mut()) };
+        assert_eq!(code, AOC_ERR_NULL_POINTER);
+    }
+}
@@ -0,0 +1,94 @@
+//! Calibration documents: lines of digits, spelled out digits and filler
+//! letters
+
+use aoc_core::solver::Answer;
+use proptest::prelude::*;
+
+use crate::Case;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// letters that appear in none of the digit words, so filler can't spell one
+const FILLER: &str = "[abcdjklmpqy]{1,4}";
+
+#[derive(Debug, Clone)]
+pub enum Token {
+    Digit(u64),
+    Word(u64),
+    Filler(String),
+}
+
+impl Token {
+    fn render(&self) -> String {
+        match self {
+            Token::Digit(digit) => digit.to_string(),
+            Token::Word(digit) => WORDS[*digit as usize - 1].to_string(),
+            Token::Filler(letters) => letters.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub lines: Vec<Vec<Token>>,
+}
+
+fn token() -> impl Strategy<Value = Token> {
+    prop_oneof![
+        (0..=9u64).prop_map(Token::Digit),
+        (1..=9u64).prop_map(Token::Word),
+        FILLER.prop_map(Token::Filler),
+    ]
+}
+
+/// a line with at least one digit, which part one needs
+fn line() -> impl Strategy<Value = Vec<Token>> {
+    (
+        prop::collection::vec(token(), 0..12),
+        0..=9u64,
+        any::<prop::sample::Index>(),
+    )
+        .prop_map(|(mut tokens, digit, at)| {
+            let at = at.index(tokens.len() + 1);
+            tokens.insert(at, Token::Digit(digit));
+            tokens
+        })
+}
+
+pub fn document() -> impl Strategy<Value = Document> {
+    prop::collection::vec(line(), 1..50).prop_map(|lines| Document { lines })
+}
+
+/// the first and last digit of a line as a two digit number
+fn calibration(digits: impl Iterator<Item = u64> + Clone) -> u64 {
+    let first = digits.clone().next().unwrap_or(0);
+    let last = digits.last().unwrap_or(0);
+    first * 10 + last
+}
+
+impl Case for Document {
+    fn input(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| line.iter().map(Token::render).collect::<String>() + "\n")
+            .collect()
+    }
+
+    fn expected(&self) -> [Answer; 2] {
+        let part_one = self.lines.iter().map(|line| {
+            calibration(line.iter().filter_map(|token| match token {
+                Token::Digit(digit) => Some(*digit),
+                _ => None,
+            }))
+        });
+        let part_two = self.lines.iter().map(|line| {
+            calibration(line.iter().filter_map(|token| match token {
+                Token::Digit(digit) | Token::Word(digit) => Some(*digit),
+                Token::Filler(_) => None,
+            }))
+        });
+        [Answer::U64(part_one.sum()), Answer::U64(part_two.sum())]
+    }
+}
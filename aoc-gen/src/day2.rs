@@ -0,0 +1,103 @@
+//! Game records: handfuls of red, green and blue cubes
+
+use aoc_core::solver::Answer;
+use proptest::prelude::*;
+
+use crate::Case;
+
+const COLORS: [&str; 3] = ["red", "green", "blue"];
+
+/// the bag part one asks about, in `COLORS` order
+const BAG: [u64; 3] = [12, 13, 14];
+
+/// how many of each color were shown, in `COLORS` order, and the order they
+/// were listed in
+#[derive(Debug, Clone)]
+pub struct Handful {
+    pub counts: [Option<u64>; 3],
+    pub order: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub games: Vec<Vec<Handful>>,
+}
+
+fn handful() -> impl Strategy<Value = Handful> {
+    (
+        prop::collection::vec(1..=20u64, 3),
+        Just(vec![0, 1, 2]).prop_shuffle(),
+        1..=3usize,
+    )
+        .prop_map(|(counts, mut order, shown)| {
+            order.truncate(shown);
+            let mut shown_counts = [None; 3];
+            for &color in &order {
+                shown_counts[color] = Some(counts[color]);
+            }
+            Handful {
+                counts: shown_counts,
+                order,
+            }
+        })
+}
+
+/// A game where every color turns up at least once, as in real inputs. A
+/// color that's never shown would make the fewest cubes needed ambiguous.
+fn game() -> impl Strategy<Value = Vec<Handful>> {
+    prop::collection::vec(handful(), 1..6).prop_filter("every color is shown", |handfuls| {
+        (0..3).all(|color| handfuls.iter().any(|h| h.counts[color].is_some()))
+    })
+}
+
+pub fn record() -> impl Strategy<Value = Record> {
+    prop::collection::vec(game(), 1..30).prop_map(|games| Record { games })
+}
+
+/// the most of each color shown in a game
+fn fewest(game: &[Handful]) -> [u64; 3] {
+    let mut most = [0; 3];
+    for handful in game {
+        for (color, count) in handful.counts.iter().enumerate() {
+            most[color] = most[color].max(count.unwrap_or(0));
+        }
+    }
+    most
+}
+
+impl Case for Record {
+    fn input(&self) -> String {
+        let mut text = String::new();
+        for (i, game) in self.games.iter().enumerate() {
+            let handfuls: Vec<String> = game
+                .iter()
+                .map(|handful| {
+                    let cubes: Vec<String> = handful
+                        .order
+                        .iter()
+                        .map(|&color| {
+                            let count = handful.counts[color].unwrap_or(0);
+                            format!("{count} {}", COLORS[color])
+                        })
+                        .collect();
+                    cubes.join(", ")
+                })
+                .collect();
+            text.push_str(&format!("Game {}: {}\n", i + 1, handfuls.join("; ")));
+        }
+        text
+    }
+
+    fn expected(&self) -> [Answer; 2] {
+        let mut possible = 0;
+        let mut power = 0;
+        for (i, game) in self.games.iter().enumerate() {
+            let fewest = fewest(game);
+            if fewest.iter().zip(BAG).all(|(&needed, held)| needed <= held) {
+                possible += i as u64 + 1;
+            }
+            power += fewest.iter().product::<u64>();
+        }
+        [Answer::U64(possible), Answer::U64(power)]
+    }
+}
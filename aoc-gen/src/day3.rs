@@ -0,0 +1,108 @@
+//! Engine schematics: numbers and symbols scattered over a grid of dots
+
+use std::collections::HashMap;
+
+use aoc_core::solver::Answer;
+use proptest::prelude::*;
+
+use crate::Case;
+
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    pub rows: Vec<Vec<char>>,
+}
+
+fn cell() -> impl Strategy<Value = char> {
+    prop_oneof![
+        6 => Just('.'),
+        3 => prop::char::range('0', '9'),
+        1 => prop::sample::select(vec!['*', '#', '+', '$', '/', '@', '=', '%', '&', '-']),
+    ]
+}
+
+pub fn schematic() -> impl Strategy<Value = Schematic> {
+    (1..20usize, 1..20usize).prop_flat_map(|(width, height)| {
+        prop::collection::vec(prop::collection::vec(cell(), width), height)
+            .prop_map(|rows| Schematic { rows })
+    })
+}
+
+/// a number on the schematic: its value, row and the columns it spans
+struct Number {
+    value: u64,
+    row: usize,
+    columns: std::ops::Range<usize>,
+}
+
+impl Schematic {
+    fn numbers(&self) -> Vec<Number> {
+        let mut numbers = vec![];
+        for (row, cells) in self.rows.iter().enumerate() {
+            let mut column = 0;
+            while column < cells.len() {
+                let start = column;
+                let mut value = 0u64;
+                while column < cells.len() && cells[column].is_ascii_digit() {
+                    value = value * 10 + u64::from(cells[column] as u8 - b'0');
+                    column += 1;
+                }
+                if column > start {
+                    numbers.push(Number {
+                        value,
+                        row,
+                        columns: start..column,
+                    });
+                }
+                column += 1;
+            }
+        }
+        numbers
+    }
+
+    /// every symbol touching `number`, diagonals included
+    fn symbols_around(&self, number: &Number) -> Vec<(usize, usize, char)> {
+        let mut symbols = vec![];
+        let rows = number.row.saturating_sub(1)..=number.row + 1;
+        for row in rows.filter(|&row| row < self.rows.len()) {
+            let columns = number.columns.start.saturating_sub(1)..=number.columns.end;
+            for column in columns.filter(|&column| column < self.rows[row].len()) {
+                let cell = self.rows[row][column];
+                if cell != '.' && !cell.is_ascii_digit() {
+                    symbols.push((row, column, cell));
+                }
+            }
+        }
+        symbols
+    }
+}
+
+impl Case for Schematic {
+    fn input(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
+    fn expected(&self) -> [Answer; 2] {
+        let mut part_numbers = 0;
+        let mut gears: HashMap<(usize, usize), Vec<u64>> = HashMap::new();
+        for number in self.numbers() {
+            let symbols = self.symbols_around(&number);
+            if !symbols.is_empty() {
+                part_numbers += number.value;
+            }
+            for (row, column, symbol) in symbols {
+                if symbol == '*' {
+                    gears.entry((row, column)).or_default().push(number.value);
+                }
+            }
+        }
+        let ratios = gears
+            .values()
+            .filter(|numbers| numbers.len() == 2)
+            .map(|numbers| numbers[0] * numbers[1])
+            .sum();
+        [Answer::U64(part_numbers), Answer::U64(ratios)]
+    }
+}
@@ -0,0 +1,83 @@
+//! Scratchcards: winning numbers and the numbers we have
+
+use aoc_core::solver::Answer;
+use proptest::{prelude::*, sample::subsequence};
+
+use crate::Case;
+
+#[derive(Debug, Clone)]
+pub struct Card {
+    pub winning: Vec<u64>,
+    pub ours: Vec<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cards {
+    pub cards: Vec<Card>,
+}
+
+/// numbers from 1 to 99 with no repeats, in any order
+fn numbers(count: std::ops::RangeInclusive<usize>) -> impl Strategy<Value = Vec<u64>> {
+    subsequence((1..100).collect::<Vec<u64>>(), count).prop_shuffle()
+}
+
+fn card() -> impl Strategy<Value = Card> {
+    (numbers(1..=10), numbers(1..=25)).prop_map(|(winning, ours)| Card { winning, ours })
+}
+
+pub fn cards() -> impl Strategy<Value = Cards> {
+    prop::collection::vec(card(), 1..40).prop_map(|cards| Cards { cards })
+}
+
+impl Card {
+    fn matches(&self) -> usize {
+        self.ours
+            .iter()
+            .filter(|number| self.winning.contains(number))
+            .count()
+    }
+}
+
+fn columns(numbers: &[u64]) -> String {
+    numbers
+        .iter()
+        .map(|number| format!("{number:>2}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl Case for Cards {
+    fn input(&self) -> String {
+        let mut text = String::new();
+        for (i, card) in self.cards.iter().enumerate() {
+            text.push_str(&format!(
+                "Card {:>3}: {} | {}\n",
+                i + 1,
+                columns(&card.winning),
+                columns(&card.ours)
+            ));
+        }
+        text
+    }
+
+    fn expected(&self) -> [Answer; 2] {
+        let points = self
+            .cards
+            .iter()
+            .map(|card| match card.matches() {
+                0 => 0,
+                matches => 1 << (matches - 1),
+            })
+            .sum();
+
+        // every copy of a card wins one more of each of the next cards
+        let mut copies = vec![1u64; self.cards.len()];
+        let last = self.cards.len() - 1;
+        for (i, card) in self.cards.iter().enumerate() {
+            for won in i + 1..=(i + card.matches()).min(last) {
+                copies[won] += copies[i];
+            }
+        }
+        [Answer::U64(points), Answer::U64(copies.iter().sum())]
+    }
+}
@@ -0,0 +1,368 @@
+//! Proptest strategies for random puzzle inputs, each paired with the
+//! answers worked out from the puzzle it was rendered from rather than by
+//! parsing the text, so the day crates can be checked against thousands of
+//! inputs instead of just the examples.
+//!
+//! A day gets a generator by adding a module with a strategy for its
+//! [`Case`] type. Its tests then only need [`check`]:
+//!
+//! ```ignore
+//! proptest! {
+//!     #[test]
+//!     fn should_match_the_model(case in day4::cards()) {
+//!         check(&day4::Day4, &case)?;
+//!     }
+//! }
+//! ```
+//!
+//! [`agree`] checks the other way round: it needs no answers, only that a
+//! solver's sequential and rayon paths give the same ones, so it can be run
+//! on inputs nobody has worked out, like the examples and the real-size
+//! inputs the benchmarks generate. [`reference`] checks a solver against
+//! its day's `reference` module, which every day keeps behind a feature of
+//! the same name, and [`incremental`] checks a day's incremental solve
+//! keeps up with an edit to its input.
+
+use std::{fmt::Debug, sync::OnceLock};
+
+use aoc_core::{
+    incremental::{self, Incremental},
+    solver::{Answer, DynSolver},
+    strategy::ExecutionStrategy,
+};
+use proptest::test_runner::TestCaseError;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+
+/// A generated puzzle that knows its own answers
+pub trait Case: Debug {
+    /// the puzzle as it would appear in an input file
+    fn input(&self) -> String;
+
+    /// the answers to part one and part two
+    fn expected(&self) -> [Answer; 2];
+}
+
+/// fail the property if `solver` gets either part of `case` wrong, run
+/// sequentially or on rayon
+pub fn check(solver: &dyn DynSolver, case: &impl Case) -> Result<(), TestCaseError> {
+    let input = case.input();
+    let expected = case.expected();
+    for strategy in [
+        ExecutionStrategy::Sequential,
+        ExecutionStrategy::Rayon { threads: None },
+    ] {
+        let solved = [
+            solver.solve_part_one(&input, strategy),
+            solver.solve_part_two(&input, strategy),
+        ];
+        for (part, (expected, solved)) in expected.iter().zip(solved).enumerate() {
+            let answer = solved.map_err(|e| TestCaseError::fail(format!("{e}\n{input}")))?;
+            if answer != *expected {
+                return Err(TestCaseError::fail(format!(
+                    "part {} ({strategy:?}): expected {expected}, got {answer}\n{input}",
+                    part + 1
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// the pools [`agree`] runs rayon solves on besides the global one: a single
+/// thread and more threads than the examples have lines. They're built once,
+/// as building a pool per solve would take longer than the solves.
+fn pools() -> &'static [ThreadPool] {
+    static POOLS: OnceLock<Vec<ThreadPool>> = OnceLock::new();
+    POOLS.get_or_init(|| {
+        [1, 8]
+            .into_iter()
+            .map(|threads| {
+                ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .expect("a rayon pool")
+            })
+            .collect()
+    })
+}
+
+/// fail the property if `solver` solves `input` differently on rayon than it
+/// does sequentially, from text or from bytes. Errors have to agree too, so
+/// a bad input fails with the same message however it's solved.
+pub fn agree(solver: &dyn DynSolver, input: &str) -> Result<(), TestCaseError> {
+    let solve = |strategy| {
+        [
+            solver.solve_part_one(input, strategy),
+            solver.solve_part_two(input, strategy),
+            solver.solve_part_one_bytes(input.as_bytes(), strategy),
+            solver.solve_part_two_bytes(input.as_bytes(), strategy),
+        ]
+    };
+    let sequential = solve(ExecutionStrategy::Sequential);
+    let rayon = ExecutionStrategy::Rayon { threads: None };
+    let global = (None, solve(rayon));
+    let pooled = pools().iter().map(|pool| {
+        (
+            Some(pool.current_num_threads()),
+            pool.install(|| solve(rayon)),
+        )
+    });
+    for (threads, parallel) in std::iter::once(global).chain(pooled) {
+        for (i, (sequential, parallel)) in sequential.iter().zip(parallel).enumerate() {
+            if *sequential != parallel {
+                let from = if i < 2 { "text" } else { "bytes" };
+                let threads = threads.map_or("the global pool".to_string(), |threads| {
+                    format!("{threads} threads")
+                });
+                return Err(TestCaseError::fail(format!(
+                    "day {} part {} from {from}: sequential gave {sequential:?}, \
+                     {threads} gave {parallel:?}\n{input}",
+                    solver.day(),
+                    i % 2 + 1
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A day's slow but obviously correct implementation of each part
+pub type Reference = [fn(&str) -> aoc_core::error::Result<u128>; 2];
+
+/// fail the property if `solver` answers either part of `input` differently
+/// from the day's reference implementation, or only one of them fails
+pub fn reference(
+    solver: &dyn DynSolver,
+    input: &str,
+    reference: Reference,
+) -> Result<(), TestCaseError> {
+    let strategy = ExecutionStrategy::Sequential;
+    let solved = [
+        solver.solve_part_one(input, strategy),
+        solver.solve_part_two(input, strategy),
+    ];
+    for (part, (solved, reference)) in solved.into_iter().zip(reference).enumerate() {
+        match (solved, reference(input).map(Answer::from)) {
+            (Ok(answer), Ok(expected)) if answer == expected => {}
+            (Err(_), Err(_)) => {}
+            (solved, expected) => {
+                return Err(TestCaseError::fail(format!(
+                "day {} part {}: the reference gave {expected:?}, the solver {solved:?}\n{input}",
+                solver.day(),
+                part + 1
+            )))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// fail the property if `solver`'s incremental solve, started by `start`,
+/// answers `before` differently from solving it in one go, or answers
+/// differently again once `before` is edited into `after`. Either way only
+/// one of them failing fails the property.
+pub fn incremental(
+    solver: &dyn DynSolver,
+    start: fn() -> Box<dyn Incremental>,
+    before: &str,
+    after: &str,
+) -> Result<(), TestCaseError> {
+    let mut solving = start();
+    solving.splice(0..0, &before.lines().collect::<Vec<_>>());
+    for (input, edit) in [(before, "before"), (after, "after")] {
+        if edit == "after" {
+            let (changed, lines) = incremental::diff(before, after);
+            solving.splice(changed, &lines);
+        }
+        let strategy = ExecutionStrategy::Sequential;
+        let solved = [
+            solver.solve_part_one(input, strategy),
+            solver.solve_part_two(input, strategy),
+        ];
+        let updated = [solving.part1(), solving.part2()];
+        for (part, (solved, updated)) in solved.into_iter().zip(updated).enumerate() {
+            match (solved, updated) {
+                (Ok(answer), Ok(expected)) if answer == expected => {}
+                (Err(_), Err(_)) => {}
+                (solved, updated) => {
+                    return Err(TestCaseError::fail(format!(
+                        "day {} part {} {edit} the edit: solving in one go gave {solved:?}, incrementally {updated:?}\n{before}\n---\n{after}",
+                        solver.day(),
+                        part + 1
+                    )))
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::solver::registered;
+    use proptest::prelude::*;
+
+    /// lines of letters that often spell out digits, some of them without
+    /// any digits at all
+    fn scrambled_lines() -> impl Strategy<Value = String> {
+        prop::collection::vec("[a-z]{0,3}[eghinorstuvwxz1-9]{0,12}", 1..30)
+            .prop_map(|lines| lines.join("\n"))
+    }
+
+    /// a generated input, and the same input with a run of its lines
+    /// swapped for a run of another's, which may be empty on either side so
+    /// lines are inserted and removed too
+    fn edited<C: Case, S: Strategy<Value = C>>(
+        inputs: fn() -> S,
+    ) -> impl Strategy<Value = (String, String)> {
+        let indices = any::<[prop::sample::Index; 4]>();
+        (inputs(), inputs(), indices).prop_map(|(before, other, [a, b, c, d])| {
+            let (before, other) = (before.input(), other.input());
+            let lines: Vec<&str> = before.lines().collect();
+            let others: Vec<&str> = other.lines().collect();
+            let [start, end] = ordered(a.index(lines.len() + 1), b.index(lines.len() + 1));
+            let [from, to] = ordered(c.index(others.len() + 1), d.index(others.len() + 1));
+            let after = [&lines[..start], &others[from..to], &lines[end..]].concat();
+            (before.clone(), after.join("\n"))
+        })
+    }
+
+    fn ordered(a: usize, b: usize) -> [usize; 2] {
+        [a.min(b), a.max(b)]
+    }
+
+    #[test]
+    fn should_update_incrementally_like_a_full_solve() -> Result<(), TestCaseError> {
+        for registration in registered() {
+            let Some(start) = registration.incremental else {
+                continue;
+            };
+            let [part_one, part_two] = registration.examples;
+            incremental(registration.solver, start, part_one, part_two)?;
+            incremental(registration.solver, start, part_two, part_one)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_agree_on_the_examples() -> Result<(), TestCaseError> {
+        // only linked crates register their solvers
+        use {::day1 as _, ::day2 as _, ::day3 as _, ::day4 as _};
+        for registration in registered() {
+            for example in registration.examples {
+                agree(registration.solver, example)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_agree_on_real_size_inputs() -> Result<(), TestCaseError> {
+        for registration in registered() {
+            let day = registration.solver.day();
+            for seed in [2023, 2024] {
+                if let Some(input) = benches::generate(day, seed) {
+                    agree(registration.solver, &input)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn should_solve_day1(case in day1::document()) {
+            check(&::day1::Day1, &case)?;
+        }
+
+        #[test]
+        fn should_agree_on_day1(case in day1::document()) {
+            agree(&::day1::Day1, &case.input())?;
+        }
+
+        #[test]
+        fn should_match_the_day1_reference(case in day1::document()) {
+            reference(&::day1::Day1, &case.input(), [::day1::reference::part_one, ::day1::reference::part_two])?;
+        }
+
+        #[test]
+        fn should_update_day1_incrementally((before, after) in edited(day1::document)) {
+            let start = aoc_core::solver::find(1).and_then(|day| day.incremental).expect("day 1 updates incrementally");
+            incremental(&::day1::Day1, start, &before, &after)?;
+        }
+
+        #[test]
+        fn should_match_the_day1_reference_on_scrambled_lines(text in scrambled_lines()) {
+            reference(&::day1::Day1, &text, [::day1::reference::part_one, ::day1::reference::part_two])?;
+        }
+
+        #[test]
+        fn should_solve_day2(case in day2::record()) {
+            check(&::day2::Day2, &case)?;
+        }
+
+        #[test]
+        fn should_agree_on_day2(case in day2::record()) {
+            agree(&::day2::Day2, &case.input())?;
+        }
+
+        #[test]
+        fn should_match_the_day2_reference(case in day2::record()) {
+            reference(&::day2::Day2, &case.input(), [::day2::reference::part_one, ::day2::reference::part_two])?;
+        }
+
+        #[test]
+        fn should_update_day2_incrementally((before, after) in edited(day2::record)) {
+            let start = aoc_core::solver::find(2).and_then(|day| day.incremental).expect("day 2 updates incrementally");
+            incremental(&::day2::Day2, start, &before, &after)?;
+        }
+
+        #[test]
+        fn should_solve_day3(case in day3::schematic()) {
+            check(&::day3::Day3, &case)?;
+        }
+
+        #[test]
+        fn should_agree_on_day3(case in day3::schematic()) {
+            agree(&::day3::Day3, &case.input())?;
+        }
+
+        #[test]
+        fn should_match_the_day3_reference(case in day3::schematic()) {
+            reference(&::day3::Day3, &case.input(), [::day3::reference::part_one, ::day3::reference::part_two])?;
+        }
+
+        #[test]
+        fn should_update_day3_incrementally((before, after) in edited(day3::schematic)) {
+            let start = aoc_core::solver::find(3).and_then(|day| day.incremental).expect("day 3 updates incrementally");
+            incremental(&::day3::Day3, start, &before, &after)?;
+        }
+
+        #[test]
+        fn should_solve_day4(case in day4::cards()) {
+            check(&::day4::Day4, &case)?;
+        }
+
+        #[test]
+        fn should_agree_on_day4(case in day4::cards()) {
+            agree(&::day4::Day4, &case.input())?;
+        }
+
+        #[test]
+        fn should_match_the_day4_reference(case in day4::cards()) {
+            reference(&::day4::Day4, &case.input(), [::day4::reference::part_one, ::day4::reference::part_two])?;
+        }
+
+        #[test]
+        fn should_update_day4_incrementally((before, after) in edited(day4::cards)) {
+            let start = aoc_core::solver::find(4).and_then(|day| day.incremental).expect("day 4 updates incrementally");
+            incremental(&::day4::Day4, start, &before, &after)?;
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    sync::mpsc::{Receiver, TryRecvError},
+    time::Duration,
+};
+
+use aoc_core::solver::{self, Algorithm, Registration};
+use eframe::egui::{self, Context, ScrollArea, Slider, TextEdit, Ui};
+
+use crate::{
+    grid,
+    solve::{self, Solved},
+};
+
+/// Steps through a solve's frames, on its own or a frame at a time
+pub struct Playback {
+    pub frame: usize,
+    pub playing: bool,
+    pub fps: u32,
+    /// when the frame shown was shown, in seconds since the window opened
+    shown_at: f64,
+}
+
+impl Default for Playback {
+    fn default() -> Playback {
+        Playback {
+            frame: 0,
+            playing: false,
+            fps: 10,
+            shown_at: 0.0,
+        }
+    }
+}
+
+impl Playback {
+    /// move on as many frames as `fps` says should have been shown by `now`,
+    /// stopping on the last of `frames`. Returns how long until the next
+    /// frame is due while playing.
+    pub fn tick(&mut self, now: f64, frames: usize) -> Option<Duration> {
+        if !self.playing {
+            self.shown_at = now;
+            return None;
+        }
+        let interval = 1.0 / f64::from(self.fps.max(1));
+        let due = ((now - self.shown_at) / interval) as usize;
+        if due > 0 {
+            self.frame = (self.frame + due).min(frames.saturating_sub(1));
+            self.shown_at += due as f64 * interval;
+        }
+        if self.frame + 1 >= frames {
+            self.playing = false;
+            return None;
+        }
+        Some(Duration::from_secs_f64(self.shown_at + interval - now))
+    }
+}
+
+/// The viewer's window: pick a day, load an input, solve it and step through
+/// what the solver drew along the way
+pub struct Viewer {
+    days: Vec<&'static Registration>,
+    day: usize,
+    /// the day's algorithm to solve with, its usual solver when `None`
+    algorithm: Option<&'static Algorithm>,
+    path: String,
+    input: String,
+    /// why the last load failed
+    status: Option<String>,
+    solving: Option<Receiver<Solved>>,
+    solved: Option<Solved>,
+    playback: Playback,
+    zoom: f32,
+}
+
+impl Viewer {
+    pub fn new() -> Viewer {
+        let mut viewer = Viewer {
+            days: solver::registered(),
+            day: 0,
+            algorithm: None,
+            path: String::new(),
+            input: String::new(),
+            status: None,
+            solving: None,
+            solved: None,
+            playback: Playback::default(),
+            zoom: 1.0,
+        };
+        viewer.select(0);
+        viewer
+    }
+
+    fn registration(&self) -> Option<&'static Registration> {
+        self.days.get(self.day).copied()
+    }
+
+    /// switch to the `index`th day, loading its input from the inputs
+    /// directory when there is one and its first example otherwise
+    fn select(&mut self, index: usize) {
+        self.day = index;
+        self.algorithm = None;
+        self.solved = None;
+        self.playback = Playback::default();
+        let Some(registration) = self.registration() else {
+            return;
+        };
+        let day = registration.solver.day();
+        let dir = env::var_os("AOC_INPUTS_DIR").map_or_else(|| "inputs".into(), PathBuf::from);
+        self.path = dir.join(format!("day{day}.txt")).display().to_string();
+        if !self.load() {
+            self.input = registration.examples[0].to_string();
+            self.status = Some(format!("no input at {}, showing the example", self.path));
+        }
+    }
+
+    /// read the input at `path`, returning whether it could be
+    fn load(&mut self) -> bool {
+        match fs::read_to_string(&self.path) {
+            Ok(input) => {
+                self.input = input;
+                self.status = None;
+                true
+            }
+            Err(e) => {
+                self.status = Some(format!("unable to read {}: {e}", self.path));
+                false
+            }
+        }
+    }
+
+    fn solve(&mut self, ctx: &Context) {
+        let Some(registration) = self.registration() else {
+            return;
+        };
+        let ctx = ctx.clone();
+        self.solving = Some(solve::spawn(
+            registration,
+            self.algorithm,
+            self.input.clone(),
+            move || ctx.request_repaint(),
+        ));
+    }
+
+    /// pick up the answers once the solve running in the background is done
+    fn receive(&mut self) {
+        let Some(solving) = &self.solving else {
+            return;
+        };
+        match solving.try_recv() {
+            Ok(solved) => {
+                self.solved = Some(solved);
+                self.playback = Playback::default();
+                self.solving = None;
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => {
+                self.status = Some("the solver panicked".to_string());
+                self.solving = None;
+            }
+        }
+    }
+
+    fn days_panel(&mut self, ui: &mut Ui) {
+        let mut selected = None;
+        for (index, registration) in self.days.iter().enumerate() {
+            let metadata = registration.solver.metadata();
+            let label = format!("{:>2}  {}", metadata.day, metadata.title);
+            if ui.selectable_label(index == self.day, label).clicked() {
+                selected = Some(index);
+            }
+        }
+        if let Some(index) = selected.filter(|index| *index != self.day) {
+            self.select(index);
+        }
+    }
+
+    fn input_bar(&mut self, ui: &mut Ui, ctx: &Context) {
+        ui.horizontal(|ui| {
+            ui.label("input");
+            ui.add(TextEdit::singleline(&mut self.path).desired_width(320.0));
+            if ui.button("Load").clicked() {
+                self.load();
+            }
+            if let Some(registration) = self.registration() {
+                for (part, example) in registration.examples.iter().enumerate() {
+                    if ui.button(format!("Example {}", part + 1)).clicked() {
+                        self.input = example.to_string();
+                        self.status = None;
+                    }
+                }
+            }
+            ui.separator();
+            self.algorithm_picker(ui);
+            let solve = ui.add_enabled(self.solving.is_none(), egui::Button::new("Solve"));
+            if solve.clicked() {
+                self.solve(ctx);
+            }
+            if self.solving.is_some() {
+                ui.spinner();
+            }
+        });
+        if let Some(status) = &self.status {
+            ui.colored_label(ui.visuals().warn_fg_color, status);
+        }
+    }
+
+    /// pick between the algorithms the day registers, if it has more than one
+    fn algorithm_picker(&mut self, ui: &mut Ui) {
+        let Some(registration) = self.registration() else {
+            return;
+        };
+        let [first, ..] = registration.algorithms else {
+            return;
+        };
+        let selected = self.algorithm.unwrap_or(first);
+        egui::ComboBox::from_label("algorithm")
+            .selected_text(selected.name)
+            .show_ui(ui, |ui| {
+                for algorithm in registration.algorithms {
+                    let chosen = algorithm.name == selected.name;
+                    if ui.selectable_label(chosen, algorithm.name).clicked() {
+                        self.algorithm = Some(algorithm);
+                    }
+                }
+            });
+    }
+
+    fn answers(&self, ui: &mut Ui) {
+        let Some(solved) = &self.solved else {
+            ui.weak("solve the input to see its answers");
+            return;
+        };
+        match &solved.answers {
+            Ok(answers) => {
+                egui::Grid::new("answers").striped(true).show(ui, |ui| {
+                    ui.strong("part");
+                    ui.strong("answer");
+                    ui.strong("time");
+                    ui.end_row();
+                    ui.label("parse");
+                    ui.label("");
+                    ui.label(format!("{:?}", answers.timings.parse));
+                    ui.end_row();
+                    for (part, answer, time) in [
+                        (1, &answers.part1, answers.timings.part1),
+                        (2, &answers.part2, answers.timings.part2),
+                    ] {
+                        ui.label(part.to_string());
+                        ui.monospace(answer.to_string());
+                        ui.label(format!("{time:?}"));
+                        ui.end_row();
+                    }
+                });
+            }
+            Err(e) => {
+                ui.colored_label(ui.visuals().error_fg_color, e.to_string());
+            }
+        }
+    }
+
+    fn frames(&mut self, ui: &mut Ui) {
+        let Some(solved) = &self.solved else {
+            return;
+        };
+        if solved.frames.is_empty() {
+            ui.weak("this day doesn't draw anything while it solves");
+            return;
+        }
+        let last = solved.frames.len() - 1;
+        ui.horizontal(|ui| {
+            let label = if self.playback.playing {
+                "Pause"
+            } else {
+                "Play"
+            };
+            if ui.button(label).clicked() {
+                if self.playback.frame == last {
+                    self.playback.frame = 0;
+                }
+                self.playback.playing = !self.playback.playing;
+            }
+            ui.add(Slider::new(&mut self.playback.frame, 0..=last).text("frame"));
+            ui.add(Slider::new(&mut self.playback.fps, 1..=60).text("fps"));
+            ui.add(Slider::new(&mut self.zoom, 0.25..=4.0).text("zoom"));
+        });
+        ScrollArea::both().show(ui, |ui| {
+            grid::draw(ui, &solved.frames[self.playback.frame], self.zoom);
+        });
+    }
+}
+
+impl eframe::App for Viewer {
+    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        self.receive();
+        let frames = self.solved.as_ref().map_or(0, |solved| solved.frames.len());
+        if let Some(next) = self.playback.tick(ctx.input(|input| input.time), frames) {
+            ctx.request_repaint_after(next);
+        }
+
+        egui::SidePanel::left("days").show(ctx, |ui| {
+            ui.heading("Days");
+            ScrollArea::vertical().show(ui, |ui| self.days_panel(ui));
+        });
+        egui::TopBottomPanel::top("input").show(ctx, |ui| self.input_bar(ui, ctx));
+        egui::CentralPanel::default().show(ctx, |ui| {
+            egui::CollapsingHeader::new("Input text").show(ui, |ui| {
+                ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    ui.add(TextEdit::multiline(&mut self.input).code_editor());
+                });
+            });
+            self.answers(ui);
+            ui.separator();
+            self.frames(ui);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_play_frames_at_the_chosen_rate() {
+        let mut playback = Playback::default();
+        assert_eq!(playback.tick(1.0, 5), None);
+
+        playback.playing = true;
+        let next = playback.tick(1.05, 5).expect("still playing");
+        assert_eq!(playback.frame, 0);
+        assert!((next.as_secs_f64() - 0.05).abs() < 1e-9);
+
+        playback.tick(1.25, 5);
+        assert_eq!(playback.frame, 2);
+
+        // stops on the last frame however late it's drawn
+        assert_eq!(playback.tick(9.0, 5), None);
+        assert_eq!(playback.frame, 4);
+        assert!(!playback.playing);
+    }
+}
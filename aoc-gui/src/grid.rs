@@ -0,0 +1,69 @@
+use aoc_core::visualize::{Frame, Mark};
+use eframe::egui::{self, Align2, Color32, FontId, Rect, Sense, Ui, Vec2};
+
+/// the side of a cell, in points, at a zoom of 1
+const CELL: f32 = 14.0;
+
+/// the color a cell is filled with, matching the terminal animation's yellow,
+/// green and red
+pub fn fill(mark: Mark) -> Color32 {
+    match mark {
+        Mark::Current => Color32::from_rgb(0xd7, 0xaf, 0x00),
+        Mark::Accepted => Color32::from_rgb(0x2e, 0x9d, 0x4f),
+        Mark::Rejected => Color32::from_rgb(0xb3, 0x3a, 0x3a),
+    }
+}
+
+/// draw `frame`'s grid, a cell per character with highlighted cells filled
+/// in, followed by its annotations. Hovering a cell shows where it is.
+pub fn draw(ui: &mut Ui, frame: &Frame, zoom: f32) {
+    let side = CELL * zoom;
+    let columns = frame
+        .rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or_default();
+    let size = Vec2::new(columns as f32, frame.rows.len() as f32) * side;
+    let (response, painter) = ui.allocate_painter(size, Sense::hover());
+    let origin = response.rect.min;
+    let font = FontId::monospace(side * 0.8);
+    let text = ui.visuals().text_color();
+
+    for (row, cells) in frame.rows.iter().enumerate() {
+        for (column, cell) in cells.chars().enumerate() {
+            let rect = Rect::from_min_size(
+                origin + Vec2::new(column as f32, row as f32) * side,
+                Vec2::splat(side),
+            );
+            if !painter.clip_rect().intersects(rect) {
+                continue;
+            }
+            let color = match frame.mark(row, column) {
+                Some(mark) => {
+                    painter.rect_filled(rect, 0.0, fill(mark));
+                    Color32::WHITE
+                }
+                None => text,
+            };
+            painter.text(
+                rect.center(),
+                Align2::CENTER_CENTER,
+                cell,
+                font.clone(),
+                color,
+            );
+        }
+    }
+
+    if let Some(pointer) = response.hover_pos() {
+        let cell = (pointer - origin) / side;
+        response.on_hover_text(format!(
+            "row {}, column {}",
+            cell.y as usize, cell.x as usize
+        ));
+    }
+    for annotation in &frame.annotations {
+        ui.label(egui::RichText::new(annotation).weak());
+    }
+}
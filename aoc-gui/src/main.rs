@@ -0,0 +1,28 @@
+//! A desktop viewer for the solvers: pick a day, load an input, solve it and
+//! step through the frames the solver drew along the way, such as day 3's
+//! schematic with each part number lit up as it's found.
+
+// days register themselves when they're linked, so they're linked even
+// though nothing here names them
+extern crate day1;
+extern crate day2;
+extern crate day3;
+extern crate day4;
+
+mod app;
+mod grid;
+mod solve;
+
+fn main() -> eframe::Result {
+    let options = eframe::NativeOptions {
+        viewport: eframe::egui::ViewportBuilder::default()
+            .with_title("Advent of Code 2023")
+            .with_inner_size([1000.0, 720.0]),
+        ..Default::default()
+    };
+    eframe::run_native(
+        "aoc-gui",
+        options,
+        Box::new(|_| Ok(Box::new(app::Viewer::new()))),
+    )
+}
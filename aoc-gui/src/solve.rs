@@ -0,0 +1,99 @@
+use std::{
+    sync::{
+        mpsc::{self, Receiver},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use aoc_core::{
+    context::SolveContext,
+    error::Result,
+    solver::{Algorithm, Answers, Registration},
+    strategy::ExecutionStrategy,
+    visualize::{Frame, Visualize},
+};
+
+/// Keeps every frame a solve emits, to step through once it's done
+#[derive(Default)]
+struct Recorder(Mutex<Vec<Frame>>);
+
+impl Visualize for Recorder {
+    fn frame(&self, frame: Frame) {
+        if let Ok(mut frames) = self.0.lock() {
+            frames.push(frame);
+        }
+    }
+}
+
+/// What solving a day's input gave
+pub struct Solved {
+    pub answers: Result<Answers>,
+    /// every frame the solver emitted, in order
+    pub frames: Vec<Frame>,
+}
+
+/// solve both parts of `text` with `registration`'s `algorithm`, or its
+/// solver without one, keeping the frames it emits. Solves sequentially so
+/// the frames come in the order the solver got to them.
+pub fn solve(registration: &Registration, algorithm: Option<&Algorithm>, text: &str) -> Solved {
+    let recorder = Arc::new(Recorder::default());
+    let context = SolveContext::new(ExecutionStrategy::Sequential).with_visualize(recorder.clone());
+    let answers = registration.solve_with(algorithm, text, &context);
+    let frames = recorder
+        .0
+        .lock()
+        .map(|mut frames| std::mem::take(&mut *frames))
+        .unwrap_or_default();
+    Solved { answers, frames }
+}
+
+/// [`solve`] on another thread so the window keeps drawing, calling `done`
+/// once it has finished so the window can be redrawn with the answers
+pub fn spawn(
+    registration: &'static Registration,
+    algorithm: Option<&'static Algorithm>,
+    text: String,
+    done: impl FnOnce() + Send + 'static,
+) -> Receiver<Solved> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // the window has gone if this fails, and there's nobody to tell
+        let _ = sender.send(solve(registration, algorithm, &text));
+        done();
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::{answer::Answer, solver};
+
+    #[test]
+    fn should_solve_and_keep_the_frames() {
+        let registration = solver::find(3).expect("day 3 is registered");
+        let solved = solve(registration, None, day3::PART_ONE_EXAMPLE);
+        let answers = solved.answers.expect("the example solves");
+        assert_eq!(answers.part1, Answer::U64(4361));
+        assert_eq!(answers.part2, Answer::U64(467835));
+        assert!(!solved.frames.is_empty());
+
+        let day4 = solver::find(4).expect("day 4 is registered");
+        let receiver = spawn(day4, None, "not a card\n".to_string(), || {});
+        let failed = receiver.recv().expect("the solve finishes");
+        assert!(failed.answers.is_err());
+    }
+
+    #[test]
+    fn should_solve_with_a_registered_algorithm() {
+        let registration = solver::find(3).expect("day 3 is registered");
+        let arena = registration.algorithm("arena");
+        assert!(arena.is_some());
+        let answers = solve(registration, arena, day3::PART_ONE_EXAMPLE)
+            .answers
+            .expect("the example solves");
+        assert_eq!(answers.part1, Answer::U64(4361));
+        assert_eq!(answers.part2, Answer::U64(467835));
+    }
+}
@@ -0,0 +1,256 @@
+//! Node.js bindings for the solvers, so JavaScript tooling and web backends
+//! can reuse them. Each function has a sync variant, which blocks the event
+//! loop while it solves, and an async one, which solves on libuv's thread
+//! pool and returns a promise.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc_core::{
+    answer::Answer,
+    context::SolveContext,
+    error::Result as AocResult,
+    solver::{self, Algorithm, Registration},
+    strategy::ExecutionStrategy,
+};
+// days register their solvers with `aoc_core::register!`, which is lost for
+// a crate that's never named, so name each one to have it linked in
+use day1 as _;
+use day2 as _;
+use day3 as _;
+use day4 as _;
+use napi::{bindgen_prelude::AsyncTask, Env, Error, Result, Status, Task};
+use napi_derive::napi;
+
+type PartSolver = Box<dyn Fn(&str) -> AocResult<Answer>>;
+
+/// Both parts' answers to an input. Answers are strings, since some are too
+/// big for JavaScript's numbers and some are text.
+#[napi(object)]
+#[derive(Debug, PartialEq)]
+pub struct Solved {
+    pub day: u32,
+    pub part1: String,
+    pub part2: String,
+    pub timings: Timings,
+}
+
+/// How long each step of a solve took, in nanoseconds
+#[napi(object)]
+#[derive(Debug, PartialEq)]
+pub struct Timings {
+    pub parse_ns: f64,
+    pub part1_ns: f64,
+    pub part2_ns: f64,
+}
+
+/// the registration for `day`
+fn lookup(day: u32) -> Result<&'static Registration> {
+    usize::try_from(day)
+        .ok()
+        .and_then(solver::find)
+        .ok_or_else(|| Error::new(Status::InvalidArg, format!("no solver for day {day}")))
+}
+
+/// the algorithm `day` registers as `algo`, or its usual solver without one
+fn lookup_algorithm(
+    day: u32,
+    algo: Option<&str>,
+) -> Result<(&'static Registration, Option<&'static Algorithm>)> {
+    let registration = lookup(day)?;
+    match algo {
+        None => Ok((registration, None)),
+        Some(name) => match registration.algorithm(name) {
+            Some(algorithm) => Ok((registration, Some(algorithm))),
+            None => Err(Error::new(
+                Status::InvalidArg,
+                format!("day {day} has no algorithm named {name:?}"),
+            )),
+        },
+    }
+}
+
+/// one part (1 or 2) of `day`, solved with the algorithm named `algo`
+fn lookup_part(day: u32, part: u32, algo: Option<&str>) -> Result<PartSolver> {
+    let (registration, algorithm) = lookup_algorithm(day, algo)?;
+    let strategy = ExecutionStrategy::Sequential;
+    match part {
+        1 | 2 => Ok(Box::new(move |input| {
+            registration.solve_part(algorithm, part as u8, input, strategy)
+        })),
+        _ => Err(Error::new(
+            Status::InvalidArg,
+            format!("part must be 1 or 2, not {part}"),
+        )),
+    }
+}
+
+/// run `solve`, turning its errors and panics into JavaScript errors, since
+/// unwinding into node would abort the process
+fn guarded<T>(day: u32, solve: impl FnOnce() -> AocResult<T>) -> Result<T> {
+    match panic::catch_unwind(AssertUnwindSafe(solve)) {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(e)) => Err(Error::from_reason(e.to_string())),
+        Err(_) => Err(Error::from_reason(format!("solver for day {day} panicked"))),
+    }
+}
+
+fn solve_input(day: u32, input: &str, algo: Option<&str>) -> Result<Solved> {
+    let (registration, algorithm) = lookup_algorithm(day, algo)?;
+    let context = SolveContext::new(ExecutionStrategy::Sequential);
+    let answers = guarded(day, || registration.solve_with(algorithm, input, &context))?;
+    Ok(Solved {
+        day,
+        part1: answers.part1.to_string(),
+        part2: answers.part2.to_string(),
+        timings: Timings {
+            parse_ns: answers.timings.parse.as_nanos() as f64,
+            part1_ns: answers.timings.part1.as_nanos() as f64,
+            part2_ns: answers.timings.part2.as_nanos() as f64,
+        },
+    })
+}
+
+fn solve_input_part(day: u32, part: u32, input: &str, algo: Option<&str>) -> Result<String> {
+    let solve = lookup_part(day, part, algo)?;
+    Ok(guarded(day, || solve(input))?.to_string())
+}
+
+/// the algorithms `day` registers, which the other functions take as `algo`,
+/// or none for a day with only the one
+#[napi]
+pub fn algorithms(day: u32) -> Result<Vec<String>> {
+    Ok(lookup(day)?
+        .algorithms
+        .iter()
+        .map(|algorithm| algorithm.name.to_string())
+        .collect())
+}
+
+/// solve both parts of `day` for `input`, parsing it once, with the
+/// algorithm named `algo` or the day's usual solver
+#[napi]
+pub fn solve(day: u32, input: String, algo: Option<String>) -> Result<Solved> {
+    solve_input(day, &input, algo.as_deref())
+}
+
+/// solve one part (1 or 2) of `day` for `input`, with the algorithm named
+/// `algo` or the day's usual solver
+#[napi]
+pub fn solve_part(day: u32, part: u32, input: String, algo: Option<String>) -> Result<String> {
+    solve_input_part(day, part, &input, algo.as_deref())
+}
+
+/// [`solve`] off the event loop
+#[napi(ts_return_type = "Promise<Solved>")]
+pub fn solve_async(day: u32, input: String, algo: Option<String>) -> AsyncTask<SolveTask> {
+    AsyncTask::new(SolveTask { day, input, algo })
+}
+
+/// [`solve_part`] off the event loop
+#[napi(ts_return_type = "Promise<string>")]
+pub fn solve_part_async(
+    day: u32,
+    part: u32,
+    input: String,
+    algo: Option<String>,
+) -> AsyncTask<SolvePartTask> {
+    AsyncTask::new(SolvePartTask {
+        day,
+        part,
+        input,
+        algo,
+    })
+}
+
+pub struct SolveTask {
+    day: u32,
+    input: String,
+    algo: Option<String>,
+}
+
+impl Task for SolveTask {
+    type Output = Solved;
+    type JsValue = Solved;
+
+    fn compute(&mut self) -> Result<Solved> {
+        solve_input(self.day, &self.input, self.algo.as_deref())
+    }
+
+    fn resolve(&mut self, _env: Env, output: Solved) -> Result<Solved> {
+        Ok(output)
+    }
+}
+
+pub struct SolvePartTask {
+    day: u32,
+    part: u32,
+    input: String,
+    algo: Option<String>,
+}
+
+impl Task for SolvePartTask {
+    type Output = String;
+    type JsValue = String;
+
+    fn compute(&mut self) -> Result<String> {
+        solve_input_part(self.day, self.part, &self.input, self.algo.as_deref())
+    }
+
+    fn resolve(&mut self, _env: Env, output: String) -> Result<String> {
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../day4/src/part1_example.txt");
+
+    #[test]
+    fn should_solve_both_parts() -> Result<()> {
+        let solved = solve_input(4, EXAMPLE, None)?;
+        assert_eq!((solved.part1.as_str(), solved.part2.as_str()), ("13", "30"));
+        assert_eq!(solve_input_part(4, 2, EXAMPLE, None)?, "30");
+
+        let mut task = SolveTask {
+            day: 4,
+            input: EXAMPLE.to_string(),
+            algo: None,
+        };
+        assert_eq!(task.compute()?.part1, "13");
+        Ok(())
+    }
+
+    #[test]
+    fn should_solve_with_a_registered_algorithm() -> Result<()> {
+        assert_eq!(algorithms(3)?, ["vec", "arena"]);
+        let example = include_str!("../../day3/src/part1_example.txt");
+        let solved = solve_input(3, example, Some("arena"))?;
+        assert_eq!(
+            (solved.part1.as_str(), solved.part2.as_str()),
+            ("4361", "467835")
+        );
+        assert_eq!(solve_input_part(4, 2, EXAMPLE, Some("vec"))?, "30");
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_bad_calls() {
+        let status = |result: Result<String>| result.map_err(|e| e.status).err();
+        assert_eq!(
+            status(solve_input_part(99, 1, "", None)),
+            Some(Status::InvalidArg)
+        );
+        assert_eq!(
+            status(solve_input_part(4, 3, "", None)),
+            Some(Status::InvalidArg)
+        );
+        assert_eq!(
+            status(solve_input_part(4, 1, "", Some("bitset"))),
+            Some(Status::InvalidArg)
+        );
+        let failed = solve_input(4, "not a card", None).map_err(|e| e.reason);
+        assert!(failed.is_err_and(|reason| reason.contains("malformatted line")));
+    }
+}
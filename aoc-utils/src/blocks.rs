@@ -0,0 +1,141 @@
+//! Splitting puzzle input into blocks separated by blank lines, such as the
+//! seeds and each map of an almanac, keeping track of the line each block
+//! starts on so errors still point at the right line of the input.
+
+use alloc::vec::Vec;
+
+use aoc_core::error::Result;
+
+/// A run of non-blank lines from the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// the lines of the block, without the newline after the last one
+    pub text: &'a str,
+    /// the line of the input the block starts on, counted from 1
+    pub line: usize,
+}
+
+impl<'a> Block<'a> {
+    pub fn lines(&self) -> core::str::Lines<'a> {
+        self.text.lines()
+    }
+}
+
+/// Iterator over the [`Block`]s of some text, from [`blocks`]
+#[derive(Debug, Clone)]
+pub struct Blocks<'a> {
+    text: &'a str,
+    /// byte offset of the first line not yet looked at
+    offset: usize,
+    /// number of that line, counted from 1
+    line: usize,
+}
+
+/// the blocks of `text`, with any number of blank or whitespace only lines
+/// between them, and `\r\n` line endings taken care of
+pub fn blocks(text: &str) -> Blocks<'_> {
+    Blocks {
+        text,
+        offset: 0,
+        line: 1,
+    }
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block<'a>;
+
+    fn next(&mut self) -> Option<Block<'a>> {
+        let mut start = None;
+        let mut end = self.offset;
+        for line in self.text[self.offset..].split_inclusive('\n') {
+            let blank = line.trim().is_empty();
+            if blank && start.is_some() {
+                break;
+            }
+            if !blank && start.is_none() {
+                start = Some((self.offset, self.line));
+            }
+            if !blank {
+                end = self.offset + line.trim_end_matches(['\n', '\r']).len();
+            }
+            self.offset += line.len();
+            self.line += 1;
+        }
+        let (start, line) = start?;
+        Some(Block {
+            text: &self.text[start..end],
+            line,
+        })
+    }
+}
+
+/// Parses every block of `text` with `parse`. A line on an error from
+/// `parse` is taken to be within the block and moved to the line of the
+/// whole input, and errors without one get the block's first line.
+pub fn parse_blocks<T>(text: &str, mut parse: impl FnMut(&str) -> Result<T>) -> Result<Vec<T>> {
+    blocks(text)
+        .map(|block| {
+            parse(block.text).map_err(|mut e| {
+                e.line = Some(block.line + e.line.map_or(0, |line| line - 1));
+                e
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::error::AocError;
+
+    #[test]
+    fn should_split_on_blank_lines() {
+        let text = "\nseeds: 79 14\n\nseed-to-soil map:\r\n50 98 2\r\n  \r\n\n#.#\n..#\n";
+        let found: Vec<Block> = blocks(text).collect();
+        assert_eq!(
+            found,
+            vec![
+                Block {
+                    text: "seeds: 79 14",
+                    line: 2
+                },
+                Block {
+                    text: "seed-to-soil map:\r\n50 98 2",
+                    line: 4
+                },
+                Block {
+                    text: "#.#\n..#",
+                    line: 8
+                },
+            ]
+        );
+        assert_eq!(
+            found[1].lines().collect::<Vec<_>>(),
+            ["seed-to-soil map:", "50 98 2"]
+        );
+        assert_eq!(blocks("\n \n").count(), 0);
+    }
+
+    #[test]
+    fn should_parse_blocks_with_input_lines() {
+        let text = "1\n2\n\n3\nx\n\ny";
+        let sum = |block: &str| -> Result<u64> {
+            block
+                .lines()
+                .enumerate()
+                .map(|(i, line)| {
+                    line.parse::<u64>()
+                        .map_err(|e| AocError::from(e).at_line(i + 1))
+                })
+                .sum()
+        };
+        assert_eq!(parse_blocks("1\n2\n\n3", sum), Ok(vec![3, 3]));
+        assert_eq!(parse_blocks(text, sum).map_err(|e| e.line), Err(Some(5)));
+
+        let first = |block: &str| -> Result<u64> { Ok(block.parse()?) };
+        assert_eq!(
+            parse_blocks("1\n\n\nno", first).map_err(|e| e.line),
+            Err(Some(4))
+        );
+    }
+}
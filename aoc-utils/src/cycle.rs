@@ -0,0 +1,156 @@
+//! Finding where a simulation starts repeating itself, so asking for the
+//! state after a billion steps only takes as many steps as it takes to loop.
+//! The state must eventually repeat, or these never return.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Where the states start to repeat: the states from index `start` on go
+/// round every `length` steps, with the initial state at index 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// the earliest index whose state is the same as the one at `index`
+    pub fn equivalent(&self, index: usize) -> usize {
+        if index < self.start {
+            index
+        } else {
+            self.start + (index - self.start) % self.length
+        }
+    }
+}
+
+/// Brent's algorithm, which only ever keeps two states around and takes
+/// fewer steps than Floyd's
+pub fn brent<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // find the length by moving the hare in growing powers of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // then walk two states `length` apart until they meet at the start
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Floyd's tortoise and hare, kept for checking [`brent`] against
+pub fn floyd<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    let mut start = 0;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// The state after `n` steps from `initial`. Every state is remembered until
+/// one repeats, then the rest of the steps are skipped, so each step runs at
+/// most once.
+pub fn fast_forward<S: Clone + Eq + Hash>(
+    initial: S,
+    n: usize,
+    mut step: impl FnMut(&S) -> S,
+) -> S {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut history = vec![initial];
+    for index in 1..=n {
+        let next = step(&history[index - 1]);
+        if let Some(&start) = seen.get(&next) {
+            let cycle = Cycle {
+                start,
+                length: index - start,
+            };
+            return history.swap_remove(cycle.equivalent(n));
+        }
+        seen.insert(next.clone(), index);
+        history.push(next);
+    }
+    history.swap_remove(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, then 3..8 round and round
+    fn step(&x: &u32) -> u32 {
+        if x == 7 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn should_find_the_cycle() {
+        let cycle = Cycle {
+            start: 3,
+            length: 5,
+        };
+        assert_eq!(brent(0, step), cycle);
+        assert_eq!(floyd(0, step), cycle);
+        assert_eq!(
+            brent(5, step),
+            Cycle {
+                start: 0,
+                length: 5
+            }
+        );
+        assert_eq!(cycle.equivalent(2), 2);
+        assert_eq!(cycle.equivalent(9), 4);
+    }
+
+    #[test]
+    fn should_fast_forward() {
+        assert_eq!(fast_forward(0, 2, step), 2);
+        assert_eq!(fast_forward(0, 8, step), 3);
+        assert_eq!(fast_forward(0, 1_000_000_000, step), 5);
+
+        let mut steps = 0;
+        fast_forward(0, usize::MAX, |x| {
+            steps += 1;
+            step(x)
+        });
+        assert_eq!(steps, 8);
+    }
+}
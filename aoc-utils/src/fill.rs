@@ -0,0 +1,141 @@
+//! Flood fills and connected regions over a [`Grid`], moving up, down, left
+//! and right, with the caller deciding which cells can be crossed.
+
+use alloc::{collections::VecDeque, vec::Vec};
+
+use crate::grid::{Grid, Position};
+
+/// The cells reachable from any of `starts` through cells that `passable`
+/// allows. Starts that aren't passable are left out.
+pub fn flood_fill<T>(
+    grid: &Grid<T>,
+    starts: impl IntoIterator<Item = Position>,
+    mut passable: impl FnMut(Position, &T) -> bool,
+) -> Grid<bool> {
+    let mut filled = Grid::new(grid.width(), grid.height(), false);
+    let mut queue: VecDeque<Position> = VecDeque::new();
+    for start in starts {
+        if grid.get(start).is_some_and(|cell| passable(start, cell)) && !filled[start] {
+            filled[start] = true;
+            queue.push_back(start);
+        }
+    }
+    while let Some(position) = queue.pop_front() {
+        for next in grid.neighbors4(position) {
+            if !filled[next] && passable(next, &grid[next]) {
+                filled[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    filled
+}
+
+/// Cells split into regions, numbered from 0 in the order their first cell
+/// comes reading the grid row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    /// which region each cell is in, `None` for cells that aren't passable
+    pub labels: Grid<Option<usize>>,
+    /// how many cells are in each region
+    pub sizes: Vec<usize>,
+}
+
+impl Components {
+    pub fn len(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sizes.is_empty()
+    }
+
+    /// the cells in region `label`
+    pub fn cells(&self, label: usize) -> impl Iterator<Item = Position> + '_ {
+        self.labels
+            .cells()
+            .filter(move |&(_, &l)| l == Some(label))
+            .map(|(position, _)| position)
+    }
+}
+
+/// the regions of neighboring cells that `passable` allows
+pub fn components<T>(grid: &Grid<T>, passable: impl FnMut(&T) -> bool) -> Components {
+    label(grid, passable, |_, _| true)
+}
+
+/// the regions of neighboring cells that hold the same value, such as the
+/// plots of each crop in a garden
+pub fn regions<T: PartialEq>(grid: &Grid<T>) -> Components {
+    label(grid, |_| true, |a, b| a == b)
+}
+
+fn label<T>(
+    grid: &Grid<T>,
+    mut passable: impl FnMut(&T) -> bool,
+    mut joined: impl FnMut(&T, &T) -> bool,
+) -> Components {
+    let mut labels = Grid::new(grid.width(), grid.height(), None);
+    let mut sizes = Vec::new();
+    let mut queue = VecDeque::new();
+    for (start, cell) in grid.cells() {
+        if labels[start].is_some() || !passable(cell) {
+            continue;
+        }
+        let label = sizes.len();
+        let mut size = 0;
+        labels[start] = Some(label);
+        queue.push_back(start);
+        while let Some(position) = queue.pop_front() {
+            size += 1;
+            for next in grid.neighbors4(position) {
+                if labels[next].is_none()
+                    && passable(&grid[next])
+                    && joined(&grid[position], &grid[next])
+                {
+                    labels[next] = Some(label);
+                    queue.push_back(next);
+                }
+            }
+        }
+        sizes.push(size);
+    }
+    Components { labels, sizes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::error::Result;
+
+    #[test]
+    fn should_find_what_a_wall_encloses() -> Result<()> {
+        let grid = Grid::parse(".....\n.###.\n.#.#.\n.###.\n....#", |c| c)?;
+        let (width, height) = (grid.width(), grid.height());
+        let border = (0..height)
+            .flat_map(|row| [(row, 0), (row, width - 1)])
+            .chain((0..width).flat_map(|column| [(0, column), (height - 1, column)]));
+        let outside = flood_fill(&grid, border, |_, &c| c == '.');
+        let enclosed: Vec<Position> = grid
+            .cells()
+            .filter(|&(position, &c)| c == '.' && !outside[position])
+            .map(|(position, _)| position)
+            .collect();
+        assert_eq!(enclosed, vec![(2, 2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_label_components() -> Result<()> {
+        let grid = Grid::parse("AAB\nCAC\nCCA", |c| c)?;
+        let open = components(&grid, |&c| c != 'C');
+        assert_eq!(open.sizes, vec![4, 1]);
+        assert_eq!(open.labels[(1, 0)], None);
+        assert_eq!(open.cells(1).collect::<Vec<_>>(), vec![(2, 2)]);
+
+        let plots = regions(&grid);
+        assert_eq!(plots.sizes, vec![3, 1, 3, 1, 1]);
+        assert_eq!(plots.labels[(2, 1)], Some(2));
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+//! Searches over graphs that are never built up front. Each function takes
+//! the node to start from and a closure giving a node's neighbors, so a node
+//! can be whatever state the puzzle needs, such as a position together with
+//! the direction it was entered from.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use aoc_core::error::{AocError, Result};
+
+/// how many steps it takes to reach every node reachable from `start`
+pub fn bfs<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in neighbors(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// every node reachable from `start`, in the order a depth first search
+/// visits them
+pub fn dfs<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        let mut next: Vec<N> = neighbors(&node)
+            .into_iter()
+            .filter(|next| !seen.contains(next))
+            .collect();
+        // pushed backwards so the first neighbor is visited first
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+    order
+}
+
+/// the cheapest node satisfying `is_goal` and what it costs to reach it,
+/// where `neighbors` gives each next node with the cost of the step to it
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(N, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut goal = None;
+    search(start, neighbors, |node, cost| {
+        let found = is_goal(node);
+        if found {
+            goal = Some((node.clone(), cost));
+        }
+        found
+    });
+    goal
+}
+
+/// the cost of the cheapest way to reach every node reachable from `start`
+pub fn dijkstra_all<N, C, I>(start: N, neighbors: impl FnMut(&N) -> I) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    search(start, neighbors, |_, _| false)
+}
+
+/// Settles nodes cheapest first until `stop` says so, and gives the costs
+/// settled so far. Nodes live in a `Vec` and the heap holds their indices,
+/// so `N` doesn't need to be `Ord`.
+fn search<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut stop: impl FnMut(&N, C) -> bool,
+) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut nodes = vec![start.clone()];
+    let mut best = HashMap::from([(start, C::default())]);
+    let mut settled = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((C::default(), 0))]);
+    while let Some(Reverse((cost, index))) = heap.pop() {
+        let node = nodes[index].clone();
+        if settled.contains_key(&node) {
+            continue;
+        }
+        settled.insert(node.clone(), cost);
+        if stop(&node, cost) {
+            break;
+        }
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            if settled.contains_key(&next) || best.get(&next).is_some_and(|&b| b <= next_cost) {
+                continue;
+            }
+            best.insert(next.clone(), next_cost);
+            nodes.push(next);
+            heap.push(Reverse((next_cost, nodes.len() - 1)));
+        }
+    }
+    settled
+}
+
+/// Orders `nodes` so every node comes before the nodes `neighbors` says
+/// depend on it. Errors if the dependencies go round in a cycle.
+pub fn topological_sort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> Result<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let nodes: Vec<N> = nodes.into_iter().collect();
+    let mut edges: HashMap<N, Vec<N>> = HashMap::new();
+    let mut incoming: HashMap<N, usize> = nodes.iter().map(|n| (n.clone(), 0)).collect();
+    for node in &nodes {
+        let next: Vec<N> = neighbors(node).into_iter().collect();
+        for n in &next {
+            *incoming.entry(n.clone()).or_default() += 1;
+        }
+        edges.insert(node.clone(), next);
+    }
+
+    let mut ready: VecDeque<N> = nodes
+        .iter()
+        .filter(|n| incoming[*n] == 0)
+        .cloned()
+        .collect();
+    let mut order = Vec::with_capacity(incoming.len());
+    while let Some(node) = ready.pop_front() {
+        for next in edges.get(&node).into_iter().flatten() {
+            let count = incoming.get_mut(next).expect("every neighbor is counted");
+            *count -= 1;
+            if *count == 0 {
+                ready.push_back(next.clone());
+            }
+        }
+        order.push(node);
+    }
+
+    if order.len() < incoming.len() {
+        return Err(AocError::new(format!(
+            "graph has a cycle through {} nodes",
+            incoming.len() - order.len()
+        )));
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    #[test]
+    fn should_search_a_maze() -> Result<()> {
+        let maze = Grid::parse("..#\n#..\n...", |c| c)?;
+        let open = |&p: &(usize, usize)| {
+            maze.neighbors4(p)
+                .filter(|&n| maze[n] == '.')
+                .collect::<Vec<_>>()
+        };
+        let distances = bfs((0, 0), open);
+        assert_eq!(distances[&(2, 0)], 4);
+        assert!(!distances.contains_key(&(0, 2)));
+
+        let order = dfs((0, 0), open);
+        assert_eq!(order.len(), distances.len());
+        assert_eq!(order[0], (0, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn should_find_the_cheapest_path() {
+        // state is (position, steps taken in a row), and a step costs the
+        // digit moved onto, like a crucible that can't go straight too long
+        let costs = [1, 9, 1, 1, 1];
+        let neighbors = |&(p, run): &(usize, u8)| {
+            [p.checked_sub(1), Some(p + 1).filter(|&n| n < costs.len())]
+                .into_iter()
+                .flatten()
+                .filter(move |_| run < 3)
+                .map(move |n| ((n, run + 1), costs[n]))
+        };
+        assert_eq!(dijkstra((0, 0), neighbors, |&(p, _)| p == 4), None);
+        let reachable: HashMap<(usize, u8), u32> = dijkstra_all((0, 0), neighbors);
+        assert_eq!(reachable[&(3, 3)], 11);
+
+        let cheap = |&p: &usize| (p < 4).then(|| (p + 1, costs[p + 1])).into_iter();
+        assert_eq!(dijkstra(0, cheap, |&p| p == 4), Some((4, 12)));
+    }
+
+    #[test]
+    fn should_order_dependencies() -> Result<()> {
+        let depends = |n: &char| match n {
+            'a' => vec!['b', 'c'],
+            'b' => vec!['d'],
+            'c' => vec!['d'],
+            _ => vec![],
+        };
+        assert_eq!(
+            topological_sort("dcba".chars(), depends)?,
+            ['a', 'b', 'c', 'd']
+        );
+
+        let cycle = |n: &u8| vec![(n + 1) % 3];
+        assert!(topological_sort(0..3, cycle).is_err());
+        Ok(())
+    }
+}
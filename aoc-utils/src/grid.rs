@@ -0,0 +1,538 @@
+//! A rectangle of cells, such as a map or a schematic, addressed by
+//! `(row, column)` from the top left.
+
+use alloc::{format, vec, vec::Vec};
+use core::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use aoc_core::error::{AocError, Result};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::point::{Direction, Point2};
+
+/// A position in a grid, as `(row, column)`
+pub type Position = (usize, usize);
+
+/// A rectangle of cells, stored a row at a time
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// a `width` by `height` grid with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// a grid of the given rows, which must all be the same width
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Grid<T>> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for row in rows {
+            match width {
+                None => width = Some(row.len()),
+                Some(width) if width != row.len() => {
+                    return Err(AocError::new(format!(
+                        "row is {} cells wide, expected {width}",
+                        row.len()
+                    ))
+                    .at_line(height + 1))
+                }
+                Some(_) => {}
+            }
+            cells.extend(row);
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// a grid with a cell for each character of each line of `text`
+    pub fn parse(text: &str, mut cell: impl FnMut(char) -> T) -> Result<Grid<T>> {
+        Grid::from_rows(
+            text.lines()
+                .map(|line| line.chars().map(&mut cell).collect()),
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0 * self.width + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        match self.contains(position) {
+            true => Some(&mut self.cells[position.0 * self.width + position.1]),
+            false => None,
+        }
+    }
+
+    /// every row, from the top
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, and a grid with no columns has no cells anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        self.rows().map(move |row| &row[column])
+    }
+
+    /// every cell with its position, a row at a time
+    pub fn cells(&self) -> impl Iterator<Item = (Position, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+
+    /// the position one step from `position` in `direction`, if it's in the grid
+    pub fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        let position = Point2::from(position).step(direction).position()?;
+        self.contains(position).then_some(position)
+    }
+
+    /// the positions up, down, left and right of `position` that are in the grid
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// the positions around `position`, diagonals included, that are in the grid
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// a grid of the same shape with `f` applied to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// build a `width` by `height` grid where each cell is copied from the
+    /// position `source` picks in this grid
+    fn rearrange(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(Position) -> Position,
+    ) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |column| (row, column)))
+            .map(|position| self[source(position)].clone())
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// rows become columns, mirroring the grid along its main diagonal
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rearrange(self.height, self.width, |(row, column)| (column, row))
+    }
+
+    /// turned a quarter turn clockwise, so the left column becomes the top row
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearrange(self.height, self.width, |(row, column)| {
+            (height - 1 - column, row)
+        })
+    }
+
+    /// turned a quarter turn counterclockwise, so the top row becomes the
+    /// left column
+    pub fn rotate_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearrange(self.height, self.width, |(row, column)| {
+            (column, width - 1 - row)
+        })
+    }
+
+    /// mirrored left to right
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.rearrange(self.width, self.height, |(row, column)| {
+            (row, width - 1 - column)
+        })
+    }
+
+    /// mirrored top to bottom
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.rearrange(self.width, self.height, |(row, column)| {
+            (height - 1 - row, column)
+        })
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "{position:?} is outside a {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position:?} is outside a {width}x{height} grid"))
+    }
+}
+
+/// the grid a row per line, as it would appear in a puzzle input
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A grid of at most `W` columns by `H` rows, fixed when the day is
+/// compiled, kept in one array instead of on the heap. With the row length a
+/// constant, finding a cell is a multiply the compiler can fold away, which
+/// pays off in the loops over every cell that grid puzzles spend their time
+/// in. A smaller input fills the top left of the array, so one type fits
+/// both the example and the real input. The whole array is stored inline, so
+/// big grids are best kept in a `Box`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FixedGrid<T, const W: usize, const H: usize> {
+    width: usize,
+    height: usize,
+    cells: [[T; W]; H],
+}
+
+impl<T: Copy, const W: usize, const H: usize> FixedGrid<T, W, H> {
+    /// a `W` by `H` grid with every cell set to `fill`
+    pub fn new(fill: T) -> FixedGrid<T, W, H> {
+        FixedGrid {
+            width: W,
+            height: H,
+            cells: [[fill; W]; H],
+        }
+    }
+
+    /// a grid with a cell for each character of each line of `text`, which
+    /// must be rows of the same width that fit in `W` by `H`. `fill` is what
+    /// the cells past the input hold.
+    pub fn parse(
+        text: &str,
+        fill: T,
+        mut cell: impl FnMut(char) -> T,
+    ) -> Result<FixedGrid<T, W, H>> {
+        let mut grid = FixedGrid {
+            width: 0,
+            height: 0,
+            cells: [[fill; W]; H],
+        };
+        for (row, line) in text.lines().enumerate() {
+            if row == H {
+                return Err(AocError::new(format!("more than {H} rows")).at_line(row + 1));
+            }
+            let mut width = 0;
+            for c in line.chars() {
+                if width == W {
+                    return Err(
+                        AocError::new(format!("row is more than {W} cells wide")).at_line(row + 1)
+                    );
+                }
+                grid.cells[row][width] = cell(c);
+                width += 1;
+            }
+            if row > 0 && width != grid.width {
+                return Err(AocError::new(format!(
+                    "row is {width} cells wide, expected {}",
+                    grid.width
+                ))
+                .at_line(row + 1));
+            }
+            grid.width = width;
+            grid.height = row + 1;
+        }
+        Ok(grid)
+    }
+
+    /// `grid` copied into a fixed grid, if it fits
+    pub fn from_grid(grid: &Grid<T>, fill: T) -> Result<FixedGrid<T, W, H>> {
+        if grid.width() > W || grid.height() > H {
+            return Err(AocError::new(format!(
+                "a {}x{} grid doesn't fit in {W}x{H}",
+                grid.width(),
+                grid.height()
+            )));
+        }
+        let mut fixed = FixedGrid {
+            width: grid.width(),
+            height: grid.height(),
+            cells: [[fill; W]; H],
+        };
+        for (row, cells) in grid.rows().enumerate().take(grid.height()) {
+            fixed.cells[row][..cells.len()].copy_from_slice(cells);
+        }
+        Ok(fixed)
+    }
+
+    /// the same cells in a [`Grid`], sized to the input rather than the bound
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.rows().flatten().copied().collect(),
+        }
+    }
+}
+
+impl<T, const W: usize, const H: usize> FixedGrid<T, W, H> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0][position.1])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        match self.contains(position) {
+            true => Some(&mut self.cells[position.0][position.1]),
+            false => None,
+        }
+    }
+
+    /// every row, from the top
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        let width = self.width;
+        self.cells[..self.height]
+            .iter()
+            .map(move |row| &row[..width])
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row][..self.width]
+    }
+
+    /// every cell with its position, a row at a time
+    pub fn cells(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.rows().enumerate().flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(move |(column, cell)| ((row, column), cell))
+        })
+    }
+
+    /// the position one step from `position` in `direction`, if it's in the grid
+    pub fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        let position = Point2::from(position).step(direction).position()?;
+        self.contains(position).then_some(position)
+    }
+
+    /// the positions up, down, left and right of `position` that are in the grid
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// the positions around `position`, diagonals included, that are in the grid
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+}
+
+impl<T, const W: usize, const H: usize> Index<Position> for FixedGrid<T, W, H> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "{position:?} is outside a {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T, const W: usize, const H: usize> IndexMut<Position> for FixedGrid<T, W, H> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position:?} is outside a {width}x{height} grid"))
+    }
+}
+
+/// the grid a row per line, as it would appear in a puzzle input
+impl<T: fmt::Display, const W: usize, const H: usize> fmt::Display for FixedGrid<T, W, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef", |c| c).unwrap()
+    }
+
+    #[test]
+    fn should_index_by_row_and_column() -> Result<()> {
+        let mut grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.get((2, 0)), None);
+        grid[(0, 2)] = 'x';
+        assert_eq!(grid.row(0), &['a', 'b', 'x']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.cells().nth(4), Some(((1, 1), &'e')));
+
+        let error = Grid::parse("abc\nde", |c| c).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: row is 2 cells wide, expected 3");
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_give_neighbors_in_the_grid() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbors8((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 1), (0, 2), (1, 2), (1, 0), (0, 0)]
+        );
+        assert_eq!(grid.step((1, 2), Direction::Right), None);
+    }
+
+    #[test]
+    fn should_rearrange() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+
+    #[test]
+    fn should_fit_a_fixed_grid_inside_its_bound() -> Result<()> {
+        let mut fixed: FixedGrid<char, 4, 3> = FixedGrid::parse("abc\ndef", '.', |c| c)?;
+        assert_eq!((fixed.width(), fixed.height()), (3, 2));
+        assert_eq!(fixed[(1, 0)], 'd');
+        assert_eq!(fixed.get((0, 3)), None);
+        fixed[(0, 2)] = 'x';
+        assert_eq!(fixed.row(0), &['a', 'b', 'x']);
+        assert_eq!(fixed.cells().nth(4), Some(((1, 1), &'e')));
+        assert_eq!(
+            fixed.neighbors8((1, 1)).collect::<Vec<_>>(),
+            grid().neighbors8((1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(fixed.to_string(), "abx\ndef");
+        assert_eq!(
+            FixedGrid::<char, 4, 3>::from_grid(&fixed.to_grid(), '.')?,
+            fixed
+        );
+
+        let error = FixedGrid::<char, 2, 3>::parse("abc", '.', |c| c).unwrap_err();
+        assert_eq!(error.to_string(), "line 1: row is more than 2 cells wide");
+        let error = FixedGrid::<char, 4, 2>::parse("abc\ndef\nghi", '.', |c| c).unwrap_err();
+        assert_eq!(error.to_string(), "line 3: more than 2 rows");
+        assert!(FixedGrid::<char, 2, 2>::from_grid(&grid(), '.').is_err());
+        Ok(())
+    }
+}
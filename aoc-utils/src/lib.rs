@@ -0,0 +1,24 @@
+//! Building blocks for the puzzles that keep coming back, such as grids, so
+//! each day crate doesn't write its own
+//!
+//! Like `aoc-core`, everything but the helpers built on `HashMap` needs only
+//! `alloc`. `cycle`, `graph` and `memo` come with the default `std` feature.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod blocks;
+#[cfg(feature = "std")]
+pub mod cycle;
+pub mod fill;
+#[cfg(feature = "std")]
+pub mod graph;
+pub mod grid;
+pub mod math;
+#[cfg(feature = "std")]
+pub mod memo;
+pub mod parse;
+pub mod point;
+pub mod polygon;
+pub mod ranges;
+pub mod space;
@@ -0,0 +1,139 @@
+//! Number theory for the puzzles where several things loop with different
+//! periods and the answer is when they all line up. Anything that can
+//! overflow returns `None` instead of wrapping.
+
+/// the greatest common divisor, with `gcd(0, 0) == 0`
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// the least common multiple, or `None` if it doesn't fit in a `u64`
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// the least common multiple of every number, `1` when there are none
+pub fn lcm_all(numbers: impl IntoIterator<Item = u64>) -> Option<u64> {
+    numbers.into_iter().try_fold(1, lcm)
+}
+
+/// `(g, x, y)` where `g` is the gcd of `a` and `b`, and `a * x + b * y == g`
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_wide(a.into(), b.into());
+    // |x| <= |b / g| and |y| <= |a / g|, so both fit unless g does not
+    (g as i64, x as i64, y as i64)
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// the `x` in `0..modulus` with `a * x ≡ 1`, if `a` and `modulus` are coprime
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd_wide(a.into(), modulus.into());
+    (g == 1).then(|| x.rem_euclid(modulus.into()) as i64)
+}
+
+/// `base.pow(exponent) % modulus` without overflowing along the way
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as u64
+}
+
+/// Solves `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair,
+/// giving the smallest non-negative `x` and the modulus of the combined
+/// congruence. The moduli don't need to be coprime. `None` if the
+/// congruences contradict each other, a modulus isn't positive, or the
+/// combined modulus doesn't fit in an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut modulus: i128 = 1;
+    for (residue, m) in congruences {
+        if m <= 0 {
+            return None;
+        }
+        let (residue, m) = (i128::from(residue).rem_euclid(m.into()), i128::from(m));
+        // x + modulus * k ≡ residue (mod m)
+        let (g, inverse, _) = extended_gcd_wide(modulus, m);
+        let difference = residue - x;
+        if difference % g != 0 {
+            return None;
+        }
+        let step = m / g;
+        let k = (difference / g % step * (inverse % step)).rem_euclid(step);
+        x += modulus * k;
+        modulus = modulus.checked_mul(step)?;
+        if modulus > i64::MAX.into() {
+            return None;
+        }
+        x = x.rem_euclid(modulus);
+    }
+    Some((x as i64, modulus as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_divisors_and_multiples() {
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm_all([2, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all([]), Some(1));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn should_solve_congruences() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli sharing a factor
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([(0, i64::MAX), (1, 2)]), None);
+    }
+}
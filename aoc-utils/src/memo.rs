@@ -0,0 +1,125 @@
+//! A cache for recursive counting, so a solution can call itself on smaller
+//! problems without working the same one out twice.
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
+
+/// Remembers the value worked out for each key. The closure passed to
+/// [`Memo::get_or_compute`] is given the memo back so it can recurse.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo {
+            cache: HashMap::new(),
+        }
+    }
+
+    /// the value for `key`, working it out with `compute` the first time
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+        // the cache can't stay borrowed while `compute` recurses into it
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// for filling in or adjusting values directly, such as seeding base cases
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        self.cache.entry(key)
+    }
+
+    /// how many values have been worked out
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Memo<K, V> {
+        Memo::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_compute(n, |memo| fibonacci(memo, n - 1) + fibonacci(memo, n - 2))
+    }
+
+    /// the ways to fill in the `?`s in `springs` so the runs of `#` match
+    /// `groups`, as in the day 12 puzzle
+    fn arrangements<'a>(
+        memo: &mut Memo<(&'a [u8], &'a [usize]), u64>,
+        springs: &'a [u8],
+        groups: &'a [usize],
+    ) -> u64 {
+        let Some((&first, rest)) = springs.split_first() else {
+            return u64::from(groups.is_empty());
+        };
+        memo.get_or_compute((springs, groups), |memo| {
+            let mut count = 0;
+            if first != b'#' {
+                count += arrangements(memo, rest, groups);
+            }
+            if let Some((&size, groups)) = groups.split_first() {
+                let fits = springs.len() >= size
+                    && !springs[..size].contains(&b'.')
+                    && springs.get(size) != Some(&b'#');
+                if first != b'.' && fits {
+                    let after = springs.get(size + 1..).unwrap_or_default();
+                    count += arrangements(memo, after, groups);
+                }
+            }
+            count
+        })
+    }
+
+    #[test]
+    fn should_remember_values() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 89);
+        assert_eq!(memo.get(&10), Some(&55));
+    }
+
+    #[test]
+    fn should_count_arrangements() {
+        let rows = [
+            ("???.###", vec![1, 1, 3], 1),
+            (".??..??...?##.", vec![1, 1, 3], 4),
+            ("?###????????", vec![3, 2, 1], 10),
+        ];
+        for (springs, groups, expected) in rows {
+            let mut memo = Memo::new();
+            assert_eq!(
+                arrangements(&mut memo, springs.as_bytes(), &groups),
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Parsing straight from the bytes of the input: splitting lines and fields,
+//! and numbers without the UTF-8 checks and sign handling that `str::parse`
+//! does on every field. Puzzle inputs are ASCII, so slicing bytes is always
+//! on a character boundary.
+
+use aoc_core::error::{AocError, Result};
+use bstr::ByteSlice;
+
+/// the lines of `bytes`, without their `\n` or `\r\n` endings, like
+/// `str::lines`. bstr finds the line endings with memchr rather than a byte
+/// at a time.
+pub fn lines(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    bytes.lines()
+}
+
+/// `bytes` either side of the first `separator`, like `str::split_once`
+pub fn split_once(bytes: &[u8], separator: u8) -> Option<(&[u8], &[u8])> {
+    let at = bytes.iter().position(|byte| *byte == separator)?;
+    Some((&bytes[..at], &bytes[at + 1..]))
+}
+
+/// the fields of `bytes` separated by ASCII whitespace, like
+/// `str::split_ascii_whitespace`
+pub fn fields(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut rest = bytes;
+    core::iter::from_fn(move || {
+        let start = rest.iter().position(|byte| !byte.is_ascii_whitespace())?;
+        let field = &rest[start..];
+        let end = field
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .unwrap_or(field.len());
+        rest = &field[end..];
+        Some(&field[..end])
+    })
+}
+
+/// `bytes` cut into fields `width` bytes wide, with the spaces padding each
+/// one trimmed off, for columns of right aligned numbers. The last field may
+/// be narrower.
+pub fn fixed_width(bytes: &[u8], width: usize) -> impl Iterator<Item = &[u8]> {
+    bytes.chunks(width.max(1)).map(<[u8]>::trim_ascii)
+}
+
+/// a number made of nothing but ASCII digits, failing the same way
+/// `str::parse::<u64>` does
+#[inline]
+pub fn parse_u64(bytes: &[u8]) -> Result<u64> {
+    digits(bytes).ok_or_else(|| parse_error(bytes))
+}
+
+/// The number `bytes` spells out, if it's only digits and fits. The
+/// `AocError` is only built once this fails, so the loop here stays as
+/// tight as the one in `str::parse`.
+#[inline]
+fn digits(bytes: &[u8]) -> Option<u64> {
+    // 19 digits can't overflow a u64, so the common case skips the checks
+    if bytes.is_empty() || bytes.len() > 19 {
+        return long_digits(bytes);
+    }
+    let mut number = 0;
+    for &byte in bytes {
+        let digit = byte.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        number = number * 10 + u64::from(digit);
+    }
+    Some(number)
+}
+
+#[cold]
+fn long_digits(bytes: &[u8]) -> Option<u64> {
+    if bytes.is_empty() {
+        return None;
+    }
+    bytes.iter().try_fold(0u64, |number, &byte| {
+        let digit = byte.wrapping_sub(b'0');
+        if digit > 9 {
+            return None;
+        }
+        number.checked_mul(10)?.checked_add(u64::from(digit))
+    })
+}
+
+/// why `bytes` isn't a number, in the words `str::parse` would use
+#[cold]
+fn parse_error(bytes: &[u8]) -> AocError {
+    AocError::new(if bytes.is_empty() {
+        "cannot parse integer from empty string"
+    } else if bytes.iter().all(u8::is_ascii_digit) {
+        "number too large to fit in target type"
+    } else {
+        "invalid digit found in string"
+    })
+}
+
+/// a number of ASCII digits with an optional leading `-` or `+`
+pub fn parse_i64(bytes: &[u8]) -> Result<i64> {
+    let (negative, digits) = match bytes {
+        [b'-', digits @ ..] => (true, digits),
+        [b'+', digits @ ..] => (false, digits),
+        digits => (false, digits),
+    };
+    let magnitude = parse_u64(digits)?;
+    let number = if negative {
+        0i64.checked_sub_unsigned(magnitude)
+    } else {
+        i64::try_from(magnitude).ok()
+    };
+    number.ok_or_else(|| AocError::new("number too large to fit in target type"))
+}
+
+/// Every run of ASCII digits in `bytes` as a number, ignoring whatever is
+/// between them, for pulling the numbers out of a line without caring about
+/// its layout. Numbers too big for a `u64` come out as `u64::MAX`.
+pub fn parse_u64s(bytes: &[u8]) -> impl Iterator<Item = u64> + '_ {
+    digit_runs(bytes).map(|(_, digits)| {
+        digits.iter().fold(0u64, |number, &byte| {
+            number
+                .saturating_mul(10)
+                .saturating_add(u64::from(byte - b'0'))
+        })
+    })
+}
+
+/// like [`parse_u64s`], but a `-` just before the digits makes the number
+/// negative. Numbers that don't fit come out as `i64::MIN` or `i64::MAX`.
+pub fn parse_i64s(bytes: &[u8]) -> impl Iterator<Item = i64> + '_ {
+    digit_runs(bytes).map(|(negative, digits)| {
+        digits.iter().fold(0i64, |number, &byte| {
+            let digit = i64::from(byte - b'0');
+            let number = number.saturating_mul(10);
+            if negative {
+                number.saturating_sub(digit)
+            } else {
+                number.saturating_add(digit)
+            }
+        })
+    })
+}
+
+/// each run of digits, and whether a `-` came right before it
+fn digit_runs(bytes: &[u8]) -> impl Iterator<Item = (bool, &[u8])> + '_ {
+    let mut rest = bytes;
+    core::iter::from_fn(move || {
+        let start = rest.iter().position(u8::is_ascii_digit)?;
+        let negative = start > 0 && rest[start - 1] == b'-';
+        let digits = &rest[start..];
+        let end = digits
+            .iter()
+            .position(|byte| !byte.is_ascii_digit())
+            .unwrap_or(digits.len());
+        rest = &digits[end..];
+        Some((negative, &digits[..end]))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_numbers_strictly() {
+        assert_eq!(parse_u64(b"4096"), Ok(4096));
+        assert_eq!(parse_u64(b"18446744073709551615"), Ok(u64::MAX));
+        for bad in [&b""[..], b"12a", b"-1", b"18446744073709551616"] {
+            let expected = core::str::from_utf8(bad)
+                .unwrap()
+                .parse::<u64>()
+                .unwrap_err();
+            assert_eq!(parse_u64(bad), Err(AocError::from(expected)));
+        }
+        assert_eq!(parse_i64(b"-9223372036854775808"), Ok(i64::MIN));
+        assert_eq!(parse_i64(b"+7"), Ok(7));
+        assert!(parse_i64(b"9223372036854775808").is_err());
+        assert!(parse_i64(b"-").is_err());
+    }
+
+    #[test]
+    fn should_split_lines_like_str() {
+        for text in ["", "a", "a\n", "a\r\nb", "a\n\nb\n", "\n"] {
+            let expected: Vec<&[u8]> = text.lines().map(str::as_bytes).collect();
+            assert_eq!(lines(text.as_bytes()).collect::<Vec<_>>(), expected);
+        }
+        assert_eq!(
+            split_once(b"Card 1: 41 | 83", b'|'),
+            Some((&b"Card 1: 41 "[..], &b" 83"[..]))
+        );
+        assert_eq!(split_once(b"Card 1", b':'), None);
+    }
+
+    #[test]
+    fn should_split_fields() {
+        let card = b" 41 48 83  6 17 ";
+        let numbers: Result<Vec<u64>> = fields(card).map(parse_u64).collect();
+        assert_eq!(numbers, Ok(vec![41, 48, 83, 6, 17]));
+        let columns: Vec<&[u8]> = fixed_width(b" 83 86  6", 3).collect();
+        assert_eq!(columns, [&b"83"[..], b"86", b"6"]);
+    }
+
+    #[test]
+    fn should_extract_numbers() {
+        let line = b"Card 12: x=-3, y=45..-6 99999999999999999999";
+        assert_eq!(
+            parse_u64s(line).collect::<Vec<_>>(),
+            [12, 3, 45, 6, u64::MAX]
+        );
+        assert_eq!(
+            parse_i64s(line).collect::<Vec<_>>(),
+            [12, -3, 45, -6, i64::MAX]
+        );
+        assert_eq!(parse_i64s(b"5-3").collect::<Vec<_>>(), [5, -3]);
+    }
+}
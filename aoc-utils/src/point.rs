@@ -0,0 +1,228 @@
+//! Points on an unbounded plane and the directions to move between them.
+//! `y` grows downwards, the way puzzle inputs are read, so `Up` is `-y`.
+
+use alloc::format;
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use aoc_core::error::AocError;
+
+use crate::grid::Position;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Point2 {
+        Point2 { x, y }
+    }
+
+    /// the distance moving only up, down, left and right
+    pub fn manhattan(self, other: Point2) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// the distance moving diagonally as well, like a king in chess
+    pub fn chebyshev(self, other: Point2) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// the point one step away in `direction`
+    pub fn step(self, direction: Direction) -> Point2 {
+        self + direction.offset()
+    }
+
+    /// the points up, down, left and right of this one
+    pub fn neighbors4(self) -> impl Iterator<Item = Point2> {
+        Direction::ORTHOGONAL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// the points around this one, diagonals included
+    pub fn neighbors8(self) -> impl Iterator<Item = Point2> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// the grid position of the point, if it's not left of or above the grid
+    pub fn position(self) -> Option<Position> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+}
+
+/// a grid position as a point, with `x` the column and `y` the row
+impl From<Position> for Point2 {
+    fn from((row, column): Position) -> Point2 {
+        Point2::new(column as i64, row as i64)
+    }
+}
+
+impl Add for Point2 {
+    type Output = Point2;
+
+    fn add(self, other: Point2) -> Point2 {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Add<Direction> for Point2 {
+    type Output = Point2;
+
+    fn add(self, direction: Direction) -> Point2 {
+        self + direction.offset()
+    }
+}
+
+impl AddAssign for Point2 {
+    fn add_assign(&mut self, other: Point2) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point2 {
+    type Output = Point2;
+
+    fn sub(self, other: Point2) -> Point2 {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point2 {
+    fn sub_assign(&mut self, other: Point2) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point2 {
+    type Output = Point2;
+
+    fn mul(self, scale: i64) -> Point2 {
+        Point2::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl Neg for Point2 {
+    type Output = Point2;
+
+    fn neg(self) -> Point2 {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+/// The eight ways to move from a point to its neighbors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// up, right, down and left, clockwise
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// every direction, clockwise from up
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// how far one step in this direction moves
+    pub const fn offset(self) -> Point2 {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::UpRight => Point2::new(1, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::DownRight => Point2::new(1, 1),
+            Direction::Down => Point2::new(0, 1),
+            Direction::DownLeft => Point2::new(-1, 1),
+            Direction::Left => Point2::new(-1, 0),
+            Direction::UpLeft => Point2::new(-1, -1),
+        }
+    }
+
+    /// `eighths` eighths of a turn clockwise, or counterclockwise when negative
+    fn rotate(self, eighths: isize) -> Direction {
+        let index = Direction::ALL.iter().position(|&d| d == self).unwrap_or(0);
+        Direction::ALL[(index as isize + eighths).rem_euclid(8) as usize]
+    }
+
+    /// a quarter turn clockwise
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// a quarter turn counterclockwise
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(4)
+    }
+}
+
+/// the arrows (`^>v<`) and letters (`URDL`) inputs use for directions
+impl TryFrom<char> for Direction {
+    type Error = AocError;
+
+    fn try_from(c: char) -> Result<Direction, AocError> {
+        match c {
+            '^' | 'U' | 'N' => Ok(Direction::Up),
+            '>' | 'R' | 'E' => Ok(Direction::Right),
+            'v' | 'D' | 'S' => Ok(Direction::Down),
+            '<' | 'L' | 'W' => Ok(Direction::Left),
+            _ => Err(AocError::new(format!("{c:?} is not a direction"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_do_point_arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(b - a, Point2::new(3, -4));
+        assert_eq!(-a * 3, Point2::new(-3, -6));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a + Direction::Up, Point2::new(1, 1));
+        assert_eq!(Point2::from((2, 5)), Point2::new(5, 2));
+        assert_eq!(Point2::new(5, 2).position(), Some((2, 5)));
+        assert_eq!(Point2::new(-1, 2).position(), None);
+        assert_eq!(Point2::ORIGIN.neighbors8().count(), 8);
+    }
+
+    #[test]
+    fn should_turn() -> Result<(), AocError> {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpLeft.turn_right(), Direction::UpRight);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+        assert_eq!(Direction::try_from('v')?, Direction::Down);
+        assert!(Direction::try_from('x').is_err());
+        Ok(())
+    }
+}
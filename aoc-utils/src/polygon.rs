@@ -0,0 +1,87 @@
+//! Areas of polygons with corners on whole coordinates, for puzzles that
+//! trace a loop and ask how much it encloses. The corners are given in
+//! order around the polygon, either way round, and the last joins back up
+//! with the first.
+
+use crate::{math::gcd, point::Point2};
+
+fn edges(vertices: &[Point2]) -> impl Iterator<Item = (Point2, Point2)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/// twice the area, from the shoelace formula, which keeps it a whole number
+pub fn twice_area(vertices: &[Point2]) -> u64 {
+    edges(vertices)
+        .map(|(a, b)| i128::from(a.x) * i128::from(b.y) - i128::from(b.x) * i128::from(a.y))
+        .sum::<i128>()
+        .unsigned_abs() as u64
+}
+
+/// the length of the boundary, measured along the grid, which is its true
+/// length when every edge is horizontal or vertical
+pub fn perimeter(vertices: &[Point2]) -> u64 {
+    edges(vertices).map(|(a, b)| a.manhattan(b)).sum()
+}
+
+/// how many whole coordinates lie on the boundary, corners included
+pub fn boundary_points(vertices: &[Point2]) -> u64 {
+    edges(vertices)
+        .map(|(a, b)| gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)))
+        .sum()
+}
+
+/// how many whole coordinates lie strictly inside, by Pick's theorem
+pub fn interior_points(vertices: &[Point2]) -> u64 {
+    // A = I + B/2 - 1, so 2I = 2A - B + 2
+    (twice_area(vertices) + 2 - boundary_points(vertices)) / 2
+}
+
+/// how many whole coordinates lie inside or on the boundary, such as the
+/// tiles of a loop and the tiles it encloses
+pub fn enclosed_points(vertices: &[Point2]) -> u64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Direction;
+
+    #[test]
+    fn should_measure_a_square() {
+        let square = [
+            Point2::new(0, 0),
+            Point2::new(4, 0),
+            Point2::new(4, 4),
+            Point2::new(0, 4),
+        ];
+        assert_eq!(twice_area(&square), 32);
+        assert_eq!(perimeter(&square), 16);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+        assert_eq!(enclosed_points(&square), 25);
+
+        let triangle = [Point2::new(0, 0), Point2::new(3, 3), Point2::new(3, 0)];
+        assert_eq!(twice_area(&triangle), 9);
+        assert_eq!(boundary_points(&triangle), 9);
+        assert_eq!(interior_points(&triangle), 1);
+    }
+
+    #[test]
+    fn should_size_the_day_18_example_lagoon() -> aoc_core::error::Result<()> {
+        let plan = "R6 D5 L2 D2 R2 D2 L5 U2 L1 U2 R2 U3 L2 U2";
+        let mut corner = Point2::ORIGIN;
+        let mut corners = Vec::new();
+        for instruction in plan.split(' ') {
+            let (direction, distance) = instruction.split_at(1);
+            let direction = Direction::try_from(direction.chars().next().unwrap_or('?'))?;
+            corner += direction.offset() * distance.parse::<i64>()?;
+            corners.push(corner);
+        }
+        assert_eq!(enclosed_points(&corners), 62);
+        Ok(())
+    }
+}
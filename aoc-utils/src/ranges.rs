@@ -0,0 +1,241 @@
+//! Sets of integers kept as sorted, non-overlapping ranges, for puzzles that
+//! push whole ranges of seeds or ratings through a pipeline instead of one
+//! value at a time.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A set of integers stored as sorted, disjoint, non-adjacent half open
+/// ranges
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Ranges {
+    spans: Vec<Range<i64>>,
+}
+
+/// Moves the values in `source` by `offset`, like one line of a day 5 map
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shift {
+    pub source: Range<i64>,
+    pub offset: i64,
+}
+
+impl Ranges {
+    pub fn new() -> Ranges {
+        Ranges::default()
+    }
+
+    /// the spans making up the set, lowest first
+    pub fn spans(&self) -> &[Range<i64>] {
+        &self.spans
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// how many values are in the set
+    pub fn len(&self) -> u64 {
+        self.spans
+            .iter()
+            .map(|span| span.start.abs_diff(span.end))
+            .sum()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        // the first span ending after value is the only one that can hold it
+        let i = self.spans.partition_point(|span| span.end <= value);
+        self.spans.get(i).is_some_and(|span| span.start <= value)
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.spans.first().map(|span| span.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.spans.last().map(|span| span.end - 1)
+    }
+
+    pub fn insert(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        self.spans.push(range);
+        self.normalize();
+    }
+
+    pub fn union(&self, other: &Ranges) -> Ranges {
+        let mut union = Ranges {
+            spans: self.spans.iter().chain(&other.spans).cloned().collect(),
+        };
+        union.normalize();
+        union
+    }
+
+    pub fn intersection(&self, other: &Ranges) -> Ranges {
+        let mut spans = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.spans.get(i), other.spans.get(j)) {
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                spans.push(overlap);
+            }
+            // whichever ends first can't overlap anything further along
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Ranges { spans }
+    }
+
+    /// the values in `self` that aren't in `other`
+    pub fn difference(&self, other: &Ranges) -> Ranges {
+        let mut spans = Vec::new();
+        let mut j = 0;
+        for span in &self.spans {
+            let mut start = span.start;
+            // skip past the removed spans that end before this one starts
+            while other
+                .spans
+                .get(j)
+                .is_some_and(|removed| removed.end <= start)
+            {
+                j += 1;
+            }
+            let mut k = j;
+            while let Some(removed) = other.spans.get(k).filter(|r| r.start < span.end) {
+                if start < removed.start {
+                    spans.push(start..removed.start);
+                }
+                start = start.max(removed.end);
+                k += 1;
+            }
+            if start < span.end {
+                spans.push(start..span.end);
+            }
+        }
+        Ranges { spans }
+    }
+
+    /// the values below `at`, and the values from `at` upwards
+    pub fn split_at(&self, at: i64) -> (Ranges, Ranges) {
+        let (mut below, mut above) = (Vec::new(), Vec::new());
+        for span in &self.spans {
+            if span.end <= at {
+                below.push(span.clone());
+            } else if span.start >= at {
+                above.push(span.clone());
+            } else {
+                below.push(span.start..at);
+                above.push(at..span.end);
+            }
+        }
+        (Ranges { spans: below }, Ranges { spans: above })
+    }
+
+    /// every value moved by `offset`
+    pub fn offset(&self, offset: i64) -> Ranges {
+        Ranges {
+            spans: self
+                .spans
+                .iter()
+                .map(|span| span.start + offset..span.end + offset)
+                .collect(),
+        }
+    }
+
+    /// the values after moving each one by the first shift whose source holds
+    /// it. Values no shift covers are kept as they are.
+    pub fn shift(&self, shifts: &[Shift]) -> Ranges {
+        let mut unshifted = self.clone();
+        let mut shifted = Ranges::new();
+        for shift in shifts {
+            let source = Ranges::from(shift.source.clone());
+            shifted = shifted.union(&unshifted.intersection(&source).offset(shift.offset));
+            unshifted = unshifted.difference(&source);
+        }
+        shifted.union(&unshifted)
+    }
+
+    /// sorts the spans and merges the ones that overlap or touch
+    fn normalize(&mut self) {
+        self.spans.retain(|span| !span.is_empty());
+        self.spans.sort_by_key(|span| span.start);
+        let mut merged: Vec<Range<i64>> = Vec::with_capacity(self.spans.len());
+        for span in self.spans.drain(..) {
+            match merged.last_mut() {
+                Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+                _ => merged.push(span),
+            }
+        }
+        self.spans = merged;
+    }
+}
+
+impl From<Range<i64>> for Ranges {
+    fn from(range: Range<i64>) -> Ranges {
+        let mut ranges = Ranges::new();
+        ranges.insert(range);
+        ranges
+    }
+}
+
+impl FromIterator<Range<i64>> for Ranges {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Ranges {
+        let mut ranges = Ranges {
+            spans: iter.into_iter().collect(),
+        };
+        ranges.normalize();
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_combine_sets() {
+        let a: Ranges = [0..5, 3..8, 10..12, 12..14].into_iter().collect();
+        assert_eq!(a.spans(), [0..8, 10..14]);
+        assert_eq!(a.len(), 12);
+        assert!(a.contains(13) && !a.contains(8));
+        assert_eq!((a.min(), a.max()), (Some(0), Some(13)));
+
+        let b: Ranges = [2..4, 6..11].into_iter().collect();
+        assert_eq!(a.union(&b), Ranges::from(0..14));
+        assert_eq!(a.intersection(&b).spans(), [2..4, 6..8, 10..11]);
+        assert_eq!(a.difference(&b).spans(), [0..2, 4..6, 11..14]);
+        assert_eq!(b.difference(&a), Ranges::from(8..10));
+    }
+
+    #[test]
+    fn should_split() {
+        let a = Ranges::from(1..4001);
+        let (below, above) = a.split_at(1351);
+        assert_eq!(below, Ranges::from(1..1351));
+        assert_eq!(above, Ranges::from(1351..4001));
+        assert_eq!(below.len() + above.len(), 4000);
+    }
+
+    #[test]
+    fn should_shift_like_a_seed_map() {
+        // seed-to-soil from the day 5 example, for seeds 79..93 and 55..68
+        let seeds: Ranges = [79..93, 55..68].into_iter().collect();
+        let shifts = [
+            Shift {
+                source: 98..100,
+                offset: -48,
+            },
+            Shift {
+                source: 50..98,
+                offset: 2,
+            },
+        ];
+        assert_eq!(seeds.shift(&shifts).spans(), [57..70, 81..95]);
+
+        let straddling = Ranges::from(95..102);
+        assert_eq!(straddling.shift(&shifts).spans(), [50..52, 97..102]);
+    }
+}
@@ -0,0 +1,376 @@
+//! Points, boxes and voxels in three dimensions, for puzzles about bricks
+//! falling into a stack or hailstones flying through the air.
+
+use alloc::{format, vec, vec::Vec};
+use core::{
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use aoc_core::error::AocError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    /// one step along each axis, both ways
+    pub const FACES: [Point3; 6] = [
+        Point3::new(1, 0, 0),
+        Point3::new(-1, 0, 0),
+        Point3::new(0, 1, 0),
+        Point3::new(0, -1, 0),
+        Point3::new(0, 0, 1),
+        Point3::new(0, 0, -1),
+    ];
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    /// the distance moving only along the axes
+    pub fn manhattan(self, other: Point3) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// the points sharing a face with this one
+    pub fn neighbors6(self) -> impl Iterator<Item = Point3> {
+        Point3::FACES.into_iter().map(move |offset| self + offset)
+    }
+}
+
+/// a point written `x,y,z`, with or without spaces, as inputs list them
+impl FromStr for Point3 {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<Point3, AocError> {
+        let mut coordinates = text.split(',').map(|n| n.trim().parse::<i64>());
+        match (
+            coordinates.next(),
+            coordinates.next(),
+            coordinates.next(),
+            coordinates.next(),
+        ) {
+            (Some(x), Some(y), Some(z), None) => Ok(Point3::new(x?, y?, z?)),
+            _ => Err(AocError::new(format!("{text:?} is not an x,y,z point"))),
+        }
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, other: Point3) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, other: Point3) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, scale: i64) -> Point3 {
+        Point3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// A box with its sides along the axes, holding every point from `min` to
+/// `max` inclusive, the way inputs give a brick by its two end cubes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Cuboid {
+    /// the box with opposite corners `a` and `b`, in either order
+    pub fn new(a: Point3, b: Point3) -> Cuboid {
+        Cuboid {
+            min: Point3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Point3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    /// the smallest box holding every one of `points`, or `None` if there
+    /// aren't any
+    pub fn bounding(points: impl IntoIterator<Item = Point3>) -> Option<Cuboid> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Cuboid::new(first, first), |bounds, point| {
+            Cuboid::new(
+                Point3::new(
+                    bounds.min.x.min(point.x),
+                    bounds.min.y.min(point.y),
+                    bounds.min.z.min(point.z),
+                ),
+                Point3::new(
+                    bounds.max.x.max(point.x),
+                    bounds.max.y.max(point.y),
+                    bounds.max.z.max(point.z),
+                ),
+            )
+        }))
+    }
+
+    /// how many points along each axis
+    pub fn size(self) -> (u64, u64, u64) {
+        (
+            self.min.x.abs_diff(self.max.x) + 1,
+            self.min.y.abs_diff(self.max.y) + 1,
+            self.min.z.abs_diff(self.max.z) + 1,
+        )
+    }
+
+    /// how many points it holds
+    pub fn volume(self) -> u64 {
+        let (x, y, z) = self.size();
+        x * y * z
+    }
+
+    pub fn contains(self, point: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// the points both boxes hold, if they overlap at all
+    pub fn intersection(self, other: Cuboid) -> Option<Cuboid> {
+        let min = Point3::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Point3::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Cuboid { min, max })
+    }
+
+    pub fn intersects(self, other: Cuboid) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// the box moved by `offset`, like a brick falling
+    pub fn translate(self, offset: Point3) -> Cuboid {
+        Cuboid {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    /// every point in the box, `x` changing fastest, then `y`, then `z`
+    pub fn points(self) -> impl Iterator<Item = Point3> {
+        (self.min.z..=self.max.z).flat_map(move |z| {
+            (self.min.y..=self.max.y)
+                .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Point3::new(x, y, z)))
+        })
+    }
+}
+
+/// the box a brick like `1,0,1~1,2,1` takes up
+impl FromStr for Cuboid {
+    type Err = AocError;
+
+    fn from_str(text: &str) -> Result<Cuboid, AocError> {
+        let (a, b) = text
+            .split_once('~')
+            .ok_or_else(|| AocError::new(format!("{text:?} is not a pair of points")))?;
+        Ok(Cuboid::new(a.parse()?, b.parse()?))
+    }
+}
+
+/// Which of the points in a box are filled, kept as a flag per point rather than
+/// in a hash set, since puzzle spaces are small and densely packed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Voxels {
+    bounds: Cuboid,
+    filled: Vec<bool>,
+    len: usize,
+}
+
+impl Voxels {
+    /// an empty space the size of `bounds`
+    pub fn new(bounds: Cuboid) -> Voxels {
+        Voxels {
+            bounds,
+            filled: vec![false; bounds.volume() as usize],
+            len: 0,
+        }
+    }
+
+    pub fn bounds(&self) -> Cuboid {
+        self.bounds
+    }
+
+    /// how many points are filled
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn index(&self, point: Point3) -> Option<usize> {
+        if !self.bounds.contains(point) {
+            return None;
+        }
+        let (width, depth, _) = self.bounds.size();
+        let offset = point - self.bounds.min;
+        Some(((offset.z as u64 * depth + offset.y as u64) * width + offset.x as u64) as usize)
+    }
+
+    /// whether `point` is filled. Points outside the bounds never are.
+    pub fn contains(&self, point: Point3) -> bool {
+        self.index(point).is_some_and(|i| self.filled[i])
+    }
+
+    /// fill `point`, returning whether it was empty. Panics if it's outside
+    /// the bounds.
+    pub fn insert(&mut self, point: Point3) -> bool {
+        self.set(point, true)
+    }
+
+    /// empty `point`, returning whether it was filled. Points outside the
+    /// bounds are already empty.
+    pub fn remove(&mut self, point: Point3) -> bool {
+        match self.index(point) {
+            Some(_) => self.set(point, false),
+            None => false,
+        }
+    }
+
+    fn set(&mut self, point: Point3, filled: bool) -> bool {
+        let i = self.index(point).unwrap_or_else(|| {
+            panic!("{point:?} is outside {:?}", self.bounds);
+        });
+        let changed = self.filled[i] != filled;
+        if changed {
+            self.filled[i] = filled;
+            self.len = match filled {
+                true => self.len + 1,
+                false => self.len - 1,
+            };
+        }
+        changed
+    }
+
+    /// fill every point in `cuboid`
+    pub fn fill(&mut self, cuboid: Cuboid) {
+        for point in cuboid.points() {
+            self.insert(point);
+        }
+    }
+
+    /// every filled point, in the order [`Cuboid::points`] gives them
+    pub fn iter(&self) -> impl Iterator<Item = Point3> + '_ {
+        self.bounds
+            .points()
+            .zip(&self.filled)
+            .filter_map(|(point, &filled)| filled.then_some(point))
+    }
+
+    /// how many faces of filled points don't touch another filled point,
+    /// the surface area of the shape they make
+    pub fn surface_area(&self) -> usize {
+        self.iter()
+            .flat_map(Point3::neighbors6)
+            .filter(|&neighbor| !self.contains(neighbor))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_do_point_arithmetic() -> Result<(), AocError> {
+        let a = Point3::new(1, 2, 3);
+        let b: Point3 = "4, -2, 0".parse()?;
+        assert_eq!(a + b, Point3::new(5, 0, 3));
+        assert_eq!(b - a, Point3::new(3, -4, -3));
+        assert_eq!(-a * 2, Point3::new(-2, -4, -6));
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(Point3::ORIGIN.neighbors6().count(), 6);
+        assert!("1,2".parse::<Point3>().is_err());
+        assert!("1,2,3,4".parse::<Point3>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn should_intersect_boxes() -> Result<(), AocError> {
+        let brick: Cuboid = "1,0,1~1,2,1".parse()?;
+        assert_eq!(brick.volume(), 3);
+        assert_eq!(
+            brick,
+            Cuboid::new(Point3::new(1, 2, 1), Point3::new(1, 0, 1))
+        );
+        let other: Cuboid = "0,1,1~2,1,1".parse()?;
+        assert_eq!(
+            brick.intersection(other),
+            Some(Cuboid::new(Point3::new(1, 1, 1), Point3::new(1, 1, 1)))
+        );
+        assert!(!brick.intersects(other.translate(Point3::new(0, 0, 1))));
+        assert_eq!(brick.points().count(), 3);
+        assert_eq!(
+            Cuboid::bounding([Point3::new(3, 0, 1), Point3::new(0, 2, -1)]),
+            Some(Cuboid::new(Point3::new(0, 0, -1), Point3::new(3, 2, 1)))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_fill_voxels() {
+        let mut voxels = Voxels::new(Cuboid::new(Point3::ORIGIN, Point3::new(3, 3, 3)));
+        assert!(voxels.insert(Point3::new(1, 1, 1)));
+        assert!(!voxels.insert(Point3::new(1, 1, 1)));
+        assert_eq!(voxels.surface_area(), 6);
+        // two cubes side by side hide a face each
+        voxels.insert(Point3::new(2, 1, 1));
+        assert_eq!(voxels.surface_area(), 10);
+        assert!(!voxels.contains(Point3::new(9, 9, 9)));
+        assert!(voxels.remove(Point3::new(1, 1, 1)));
+        assert_eq!(
+            voxels.iter().collect::<Vec<_>>(),
+            vec![Point3::new(2, 1, 1)]
+        );
+
+        voxels.fill(Cuboid::new(Point3::ORIGIN, Point3::new(1, 1, 0)));
+        assert_eq!(voxels.len(), 5);
+    }
+}
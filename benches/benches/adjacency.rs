@@ -0,0 +1,66 @@
+//! `cargo bench -p benches --bench adjacency` compares the two ways of
+//! finding day 3's part numbers: keeping the positions next to a symbol in a
+//! `FastSet` keyed by `(row, column)`, or in a row-major `Grid<bool>` the
+//! size of the schematic, as the solver does. Each builds the table and
+//! then checks every digit against it, on a real sized schematic and on one
+//! crowded with symbols.
+
+use std::hint::black_box;
+
+use aoc_core::collections::FastSet;
+use aoc_utils::grid::{Grid, Position};
+use benches::Rng;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// the size of a real day 3 input
+const SIZE: usize = 140;
+
+fn is_symbol(cell: u8) -> bool {
+    cell != b'.' && !cell.is_ascii_digit()
+}
+
+/// every position next to a symbol, diagonals included
+fn near_symbols(grid: &Grid<u8>) -> impl Iterator<Item = Position> + '_ {
+    grid.cells()
+        .filter(|(_, cell)| is_symbol(**cell))
+        .flat_map(|(position, _)| grid.neighbors8(position))
+}
+
+/// how many digits are next to a symbol, looked up in a hash set
+fn hashed(grid: &Grid<u8>) -> usize {
+    let near: FastSet<Position> = near_symbols(grid).collect();
+    grid.cells()
+        .filter(|(position, cell)| cell.is_ascii_digit() && near.contains(position))
+        .count()
+}
+
+/// how many digits are next to a symbol, looked up in a bitmap
+fn dense(grid: &Grid<u8>) -> usize {
+    let mut near = Grid::new(grid.width(), grid.height(), false);
+    for position in near_symbols(grid) {
+        near[position] = true;
+    }
+    grid.cells()
+        .filter(|(position, cell)| cell.is_ascii_digit() && near[*position])
+        .count()
+}
+
+fn adjacency(c: &mut Criterion) {
+    let mut group = c.benchmark_group("adjacency");
+    for (name, symbols) in [("real", 1), ("dense", 5)] {
+        let text = benches::schematic(&mut Rng::new(2023), SIZE, symbols);
+        let grid = Grid::parse(&text, |c| c as u8).expect("a rectangular schematic");
+        assert_eq!(hashed(&grid), dense(&grid));
+
+        group.bench_with_input(BenchmarkId::new("hashed", name), &grid, |b, grid| {
+            b.iter(|| hashed(black_box(grid)))
+        });
+        group.bench_with_input(BenchmarkId::new("bitmap", name), &grid, |b, grid| {
+            b.iter(|| dense(black_box(grid)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, adjacency);
+criterion_main!(benches);
@@ -0,0 +1,72 @@
+//! `cargo bench -p benches --bench calibration` compares finding day 1's
+//! first and last digits by collecting every digit on a line into a `Vec`,
+//! as the solver used to, with the single fold it does now, on an input a
+//! hundred times the size of a real one. Before timing, it prints how many
+//! allocations each makes: one per line when collecting, and a handful per
+//! solve however long the input is when folding.
+
+use std::hint::black_box;
+
+use aoc_core::{solver::Solver, strategy::ExecutionStrategy};
+use benches::{allocations, Counting, Rng};
+use criterion::{criterion_group, criterion_main, Criterion};
+use day1::Day1;
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// a hundred real inputs' worth of lines
+const LINES: usize = 100_000;
+
+const NUMERICS: [&str; 20] = [
+    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "zero", "one", "two", "three", "four",
+    "five", "six", "seven", "eight", "nine",
+];
+
+/// part two the way it used to be solved, collecting each line's digits
+/// before taking the first and last
+fn collected(lines: &[&[u8]]) -> u64 {
+    lines
+        .iter()
+        .map(|line| {
+            let digits: Vec<u64> = (0..line.len())
+                .filter_map(|start| {
+                    NUMERICS
+                        .iter()
+                        .position(|numeric| line[start..].starts_with(numeric.as_bytes()))
+                })
+                .map(|index| index as u64 % 10)
+                .collect();
+            match (digits.first(), digits.last()) {
+                (Some(first), Some(last)) => first * 10 + last,
+                _ => 0,
+            }
+        })
+        .sum()
+}
+
+fn calibration(c: &mut Criterion) {
+    let strategy = ExecutionStrategy::Sequential;
+    let text = benches::day1(&mut Rng::new(2023), LINES);
+    let lines = Day1::parse(&text, strategy).expect("generated lines to parse");
+    let folded = || Day1::part2(&lines, strategy).expect("every line has a digit");
+    assert_eq!(folded(), collected(&lines));
+
+    println!(
+        "allocations solving part two of {LINES} lines: {} collecting, {} folding",
+        allocations(|| collected(&lines)),
+        allocations(folded)
+    );
+
+    let mut group = c.benchmark_group("calibration");
+    group.bench_function("part2/collected", |b| {
+        b.iter(|| collected(black_box(&lines)))
+    });
+    group.bench_function("part2/folded", |b| {
+        b.iter(|| Day1::part2(black_box(&lines), strategy))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, calibration);
+criterion_main!(benches);
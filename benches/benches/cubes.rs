@@ -0,0 +1,77 @@
+//! `cargo bench -p benches --bench cubes` compares finding the most cubes of
+//! each color drawn in day 2's games with the colors copied into `String`
+//! keys, as the solver used to, with keying them by the color's text in the
+//! record, as it does now, timing part two on a generated record. Before
+//! timing, it prints how many allocations each makes: a `String` per color
+//! drawn when copying, and only each game's map when borrowing.
+
+use std::hint::black_box;
+
+use aoc_core::{
+    answer::Answer,
+    collections::{hash_map::Entry, FastMap},
+    solver::Solver,
+    strategy::ExecutionStrategy,
+};
+use benches::{allocations, Counting, Rng};
+use criterion::{criterion_group, criterion_main, Criterion};
+use day2::Day2;
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// a hundred real records' worth of games
+const GAMES: usize = 10_000;
+
+/// a game's id and the cubes drawn in each of its rounds, as day 2 parses it
+type Game<'a> = (u64, Vec<Vec<(u64, &'a str)>>);
+
+/// part two the way it used to be solved, copying each color drawn into a
+/// `String` to look it up
+fn copied(games: &[Game<'_>]) -> u128 {
+    games
+        .iter()
+        .map(|(_, rounds)| {
+            let mut counts: FastMap<String, u64> = FastMap::default();
+            for &(count, color) in rounds.iter().flatten() {
+                match counts.entry(color.to_string()) {
+                    Entry::Occupied(mut entry) => {
+                        let highest = entry.get_mut();
+                        *highest = (*highest).max(count);
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(count);
+                    }
+                }
+            }
+            counts
+                .values()
+                .map(|count| u128::from(*count))
+                .product::<u128>()
+        })
+        .sum()
+}
+
+fn cubes(c: &mut Criterion) {
+    let strategy = ExecutionStrategy::Sequential;
+    let text = benches::day2(&mut Rng::new(2023), GAMES);
+    let games = Day2::parse(&text, strategy).expect("generated games to parse");
+    let borrowed = || Day2::part2(&games, strategy).expect("generated games to solve");
+    assert_eq!(borrowed(), Answer::from(copied(&games)));
+
+    println!(
+        "allocations solving part two of {GAMES} games: {} copying, {} borrowing",
+        allocations(|| copied(&games)),
+        allocations(borrowed)
+    );
+
+    let mut group = c.benchmark_group("cubes");
+    group.bench_function("part2/copied", |b| b.iter(|| copied(black_box(&games))));
+    group.bench_function("part2/borrowed", |b| {
+        b.iter(|| Day2::part2(black_box(&games), strategy))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cubes);
+criterion_main!(benches);
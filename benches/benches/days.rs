@@ -0,0 +1,51 @@
+//! `cargo bench -p benches` times the parse and both parts of every day on
+//! its examples and on a generated input the size of a real one. Filter by
+//! day or step with criterion's usual arguments, e.g.
+//! `cargo bench -p benches -- day4/part2`.
+
+use std::hint::black_box;
+
+use aoc_core::{solver::Solver, strategy::ExecutionStrategy};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// the same seed every run, so results stay comparable
+const SEED: u64 = 2023;
+
+/// single threaded, so the numbers don't depend on the machine's cores
+const STRATEGY: ExecutionStrategy = ExecutionStrategy::Sequential;
+
+/// Benchmarks one day. Part one is timed on the first example and part two
+/// on the second, since some days' examples only make sense for one part.
+fn bench_day<S: Solver>(c: &mut Criterion, examples: [&str; 2]) {
+    let mut group = c.benchmark_group(format!("day{}", S::DAY));
+    let generated = benches::generate(S::DAY, SEED);
+    let mut inputs = vec![("example", examples[0], examples[1])];
+    if let Some(text) = &generated {
+        inputs.push(("generated", text, text));
+    }
+
+    for (name, part_one_text, part_two_text) in inputs {
+        group.bench_with_input(BenchmarkId::new("parse", name), part_one_text, |b, text| {
+            b.iter(|| S::parse(black_box(text), STRATEGY))
+        });
+        let parsed = S::parse(part_one_text, STRATEGY).expect("benchmark input to parse");
+        group.bench_with_input(BenchmarkId::new("part1", name), &parsed, |b, parsed| {
+            b.iter(|| S::part1(black_box(parsed), STRATEGY))
+        });
+        let parsed = S::parse(part_two_text, STRATEGY).expect("benchmark input to parse");
+        group.bench_with_input(BenchmarkId::new("part2", name), &parsed, |b, parsed| {
+            b.iter(|| S::part2(black_box(parsed), STRATEGY))
+        });
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day1::Day1>(c, [day1::PART_ONE_EXAMPLE, day1::PART_TWO_EXAMPLE]);
+    bench_day::<day2::Day2>(c, [day2::PART_ONE_EXAMPLE, day2::PART_TWO_EXAMPLE]);
+    bench_day::<day3::Day3>(c, [day3::PART_ONE_EXAMPLE, day3::PART_TWO_EXAMPLE]);
+    bench_day::<day4::Day4>(c, [day4::PART_ONE_EXAMPLE, day4::PART_TWO_EXAMPLE]);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
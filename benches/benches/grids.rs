@@ -0,0 +1,66 @@
+//! `cargo bench -p benches --bench grids` compares the heap allocated `Grid`
+//! with a `FixedGrid` bounded at compile time, on the loop grid puzzles
+//! spend their time in: looking at every cell's neighbors, here to find the
+//! digits next to a symbol in a day 3 sized schematic.
+
+use std::hint::black_box;
+
+use aoc_utils::grid::{FixedGrid, Grid};
+use benches::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// the size of a real day 3 input
+const SIZE: usize = 140;
+
+fn is_symbol(cell: u8) -> bool {
+    cell != b'.' && !cell.is_ascii_digit()
+}
+
+/// how many digits have a symbol next to them
+fn touching(grid: &Grid<u8>) -> usize {
+    grid.cells()
+        .filter(|(position, cell)| {
+            cell.is_ascii_digit()
+                && grid
+                    .neighbors8(*position)
+                    .any(|neighbor| is_symbol(grid[neighbor]))
+        })
+        .count()
+}
+
+/// [`touching`] on a fixed grid
+fn touching_fixed(grid: &FixedGrid<u8, SIZE, SIZE>) -> usize {
+    grid.cells()
+        .filter(|(position, cell)| {
+            cell.is_ascii_digit()
+                && grid
+                    .neighbors8(*position)
+                    .any(|neighbor| is_symbol(grid[neighbor]))
+        })
+        .count()
+}
+
+fn grids(c: &mut Criterion) {
+    let text = benches::day3(&mut Rng::new(2023), SIZE);
+    let grid = Grid::parse(&text, |c| c as u8).expect("a rectangular schematic");
+    let fixed: FixedGrid<u8, SIZE, SIZE> = FixedGrid::from_grid(&grid, b'.').expect("it fits");
+    assert_eq!(touching(&grid), touching_fixed(&fixed));
+
+    let mut group = c.benchmark_group("grids");
+    group.bench_function("parse/dynamic", |b| {
+        b.iter(|| Grid::parse(black_box(&text), |c| c as u8))
+    });
+    group.bench_function("parse/fixed", |b| {
+        b.iter(|| FixedGrid::<u8, SIZE, SIZE>::parse(black_box(&text), b'.', |c| c as u8))
+    });
+    group.bench_function("neighbors/dynamic", |b| {
+        b.iter(|| touching(black_box(&grid)))
+    });
+    group.bench_function("neighbors/fixed", |b| {
+        b.iter(|| touching_fixed(black_box(&fixed)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, grids);
+criterion_main!(benches);
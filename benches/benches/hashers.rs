@@ -0,0 +1,60 @@
+//! `cargo bench -p benches --bench hashers` compares the standard library's
+//! hasher with the `FastMap` hasher the day crates use, on the kinds of keys
+//! they hash: card ids counting copies, as in day 4, and grid positions
+//! collecting numbers, as in day 3.
+
+use std::{collections::HashMap, hash::BuildHasher, hint::black_box};
+
+use aoc_core::collections::FastHasher;
+use benches::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// about the number of cards or gears in a real input
+const KEYS: usize = 1_000;
+
+/// count copies of each card, then look every card up again
+fn count_ids<S: BuildHasher + Default>(ids: &[usize]) -> u128 {
+    let mut counts: HashMap<usize, u128, S> = HashMap::default();
+    for &id in ids {
+        *counts.entry(id).or_insert(1) += 1;
+    }
+    ids.iter().filter_map(|id| counts.get(id)).sum()
+}
+
+/// collect the numbers next to each position
+fn collect_positions<S: BuildHasher + Default>(positions: &[(usize, usize)]) -> usize {
+    let mut numbers: HashMap<(usize, usize), Vec<u64>, S> = HashMap::default();
+    for (i, &position) in positions.iter().enumerate() {
+        numbers.entry(position).or_default().push(i as u64);
+    }
+    numbers
+        .values()
+        .filter(|numbers| numbers.len() == 2)
+        .count()
+}
+
+fn hashers(c: &mut Criterion) {
+    let mut rng = Rng::new(2023);
+    let ids: Vec<usize> = (0..KEYS).map(|_| rng.below(KEYS as u64) as usize).collect();
+    let positions: Vec<(usize, usize)> = (0..KEYS)
+        .map(|_| (rng.below(140) as usize, rng.below(140) as usize))
+        .collect();
+
+    let mut group = c.benchmark_group("hashers");
+    group.bench_function("ids/std", |b| {
+        b.iter(|| count_ids::<std::hash::RandomState>(black_box(&ids)))
+    });
+    group.bench_function("ids/fast", |b| {
+        b.iter(|| count_ids::<FastHasher>(black_box(&ids)))
+    });
+    group.bench_function("positions/std", |b| {
+        b.iter(|| collect_positions::<std::hash::RandomState>(black_box(&positions)))
+    });
+    group.bench_function("positions/fast", |b| {
+        b.iter(|| collect_positions::<FastHasher>(black_box(&positions)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, hashers);
+criterion_main!(benches);
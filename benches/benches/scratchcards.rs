@@ -0,0 +1,66 @@
+//! `cargo bench -p benches --bench scratchcards` compares parsing day 4's
+//! number lists a field at a time, splitting on whitespace and keeping the
+//! winning numbers in a `FastSet`, with reading them in three byte columns
+//! and keeping the winning numbers as bits, as the solver does, reporting
+//! the throughput of each on a generated pile of cards.
+
+use std::hint::black_box;
+
+use aoc_core::{
+    collections::FastSet,
+    error::{AocError, Result},
+    solver::Solver,
+    strategy::ExecutionStrategy,
+};
+use aoc_utils::parse::{fields, lines, parse_u64, split_once};
+use benches::Rng;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use day4::Day4;
+
+/// fifty real inputs' worth of cards
+const CARDS: usize = 10_000;
+
+/// every card's matches, parsing the lists a field at a time as the solver
+/// used to
+fn by_fields(text: &str) -> Result<Vec<usize>> {
+    lines(text.as_bytes())
+        .map(|line| {
+            let (_, lists) = split_once(line, b':').ok_or(AocError::new("no colon"))?;
+            let (winning, ours) = split_once(lists, b'|').ok_or(AocError::new("no bar"))?;
+            let winning = fields(winning)
+                .map(parse_u64)
+                .collect::<Result<FastSet<u64>>>()?;
+            let mut matches = 0;
+            for number in fields(ours) {
+                if winning.contains(&parse_u64(number)?) {
+                    matches += 1;
+                }
+            }
+            Ok(matches)
+        })
+        .collect()
+}
+
+fn scratchcards(c: &mut Criterion) {
+    let strategy = ExecutionStrategy::Sequential;
+    let text = benches::day4(&mut Rng::new(2023), CARDS);
+    let points: u64 = by_fields(&text)
+        .expect("generated cards to parse")
+        .into_iter()
+        .filter(|matches| *matches > 0)
+        .map(|matches| 1 << (matches - 1))
+        .sum();
+    let parsed = Day4::parse(&text, strategy).expect("generated cards to parse");
+    assert_eq!(Day4::part1(&parsed, strategy).ok(), Some(points.into()));
+
+    let mut group = c.benchmark_group("scratchcards");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("parse/fields", |b| b.iter(|| by_fields(black_box(&text))));
+    group.bench_function("parse/columns", |b| {
+        b.iter(|| Day4::parse(black_box(&text), strategy))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, scratchcards);
+criterion_main!(benches);
@@ -0,0 +1,198 @@
+//! Inputs for the criterion benchmarks in `benches/days.rs`. The examples
+//! from the puzzle descriptions are tiny, so each day also gets a generated
+//! input the size of a real one. Generation is seeded, so every run
+//! benchmarks the same text. Benches comparing allocations count them with
+//! [`Counting`].
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The system allocator, counting the allocations made through it. A bench
+/// that reports allocations installs it with `#[global_allocator]`.
+pub struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// how many allocations `f` makes, when [`Counting`] is the global allocator
+pub fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+/// splitmix64, which is plenty for making up puzzle inputs
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// a number less than `end`, which must not be 0
+    pub fn below(&mut self, end: u64) -> u64 {
+        self.next_u64() % end
+    }
+
+    /// a number from `start` to `end` inclusive
+    pub fn between(&mut self, start: u64, end: u64) -> u64 {
+        start + self.below(end - start + 1)
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+}
+
+/// A generated input for `day`, the size of a real puzzle input, or `None`
+/// for a day without a generator
+pub fn generate(day: usize, seed: u64) -> Option<String> {
+    let mut rng = Rng::new(seed);
+    let text = match day {
+        1 => day1(&mut rng, 1000),
+        2 => day2(&mut rng, 100),
+        3 => day3(&mut rng, 140),
+        4 => day4(&mut rng, 200),
+        _ => return None,
+    };
+    Some(text)
+}
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// calibration lines of letters, digit words and at least one digit
+pub fn day1(rng: &mut Rng, lines: usize) -> String {
+    let mut text = String::new();
+    for _ in 0..lines {
+        let length = rng.between(3, 30) as usize;
+        let digit_at = rng.below(length as u64) as usize;
+        for i in 0..length {
+            match rng.below(4) {
+                _ if i == digit_at => text.push(char::from(b'1' + rng.below(9) as u8)),
+                0 => text.push_str(rng.pick(&DIGIT_WORDS)),
+                1 => text.push(char::from(b'0' + rng.below(10) as u8)),
+                _ => text.push(char::from(b'a' + rng.below(26) as u8)),
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// games of a few handfuls of red, green and blue cubes
+pub fn day2(rng: &mut Rng, games: usize) -> String {
+    let mut text = String::new();
+    for game in 1..=games {
+        let handfuls: Vec<String> = (0..rng.between(1, 6))
+            .map(|_| {
+                let mut colors = vec!["red", "green", "blue"];
+                colors.truncate(rng.between(1, 3) as usize);
+                colors
+                    .iter()
+                    .map(|color| format!("{} {color}", rng.between(1, 20)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        text.push_str(&format!("Game {game}: {}\n", handfuls.join("; ")));
+    }
+    text
+}
+
+/// a square engine schematic with numbers and symbols scattered about
+pub fn day3(rng: &mut Rng, size: usize) -> String {
+    schematic(rng, size, 1)
+}
+
+/// a square engine schematic where, of every ten picks of what comes next,
+/// two start a number, `symbols` are a symbol and the rest are periods
+pub fn schematic(rng: &mut Rng, size: usize, symbols: u64) -> String {
+    const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+    let mut text = String::new();
+    for _ in 0..size {
+        let mut row = Vec::with_capacity(size);
+        while row.len() < size {
+            match rng.below(10) {
+                0..=1 => {
+                    let digits = rng.between(1, 3).min((size - row.len()) as u64);
+                    row.extend((0..digits).map(|_| b'0' + rng.below(10) as u8));
+                    if row.len() < size {
+                        row.push(b'.');
+                    }
+                }
+                n if n < 2 + symbols => row.push(rng.pick(SYMBOLS)),
+                _ => row.push(b'.'),
+            }
+        }
+        text.push_str(std::str::from_utf8(&row).unwrap_or_default());
+        text.push('\n');
+    }
+    text
+}
+
+/// scratchcards with 10 winning numbers and 25 of ours, laid out in columns
+pub fn day4(rng: &mut Rng, cards: usize) -> String {
+    let mut text = String::new();
+    let width = cards.to_string().len();
+    let mut numbers = |count| {
+        (0..count)
+            .map(|_| format!("{:>2}", rng.between(1, 99)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    for card in 1..=cards {
+        let winning = numbers(10);
+        let ours = numbers(25);
+        text.push_str(&format!("Card {card:>width$}: {winning} | {ours}\n"));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::{solver::find, strategy::ExecutionStrategy};
+    // only linked crates register their solvers
+    use {::day1 as _, ::day2 as _, ::day3 as _, ::day4 as _};
+
+    #[test]
+    fn should_generate_inputs_every_day_solves() {
+        for day in 1..=4 {
+            let text = generate(day, 2023).expect("a generator for the day");
+            assert_eq!(generate(day, 2023).as_ref(), Some(&text));
+            let solver = find(day).expect("the day to be registered").solver;
+            let strategy = ExecutionStrategy::Sequential;
+            assert!(
+                solver.solve_part_one(&text, strategy).is_ok(),
+                "day {day} part 1"
+            );
+            assert!(
+                solver.solve_part_two(&text, strategy).is_ok(),
+                "day {day} part 2"
+            );
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::{env, fs, path::Path, process::Command};
+
+fn main() {
+    link_days();
+    #[cfg(feature = "grpc")]
+    compile_protos();
+
+    // embed the git revision the cli was built from, for the answer history
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-changed=../.git/refs");
+    let revision = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    if let Some(revision) = revision {
+        println!("cargo:rustc-env=AOC_GIT_REVISION={}", revision.trim());
+    }
+}
+
+/// Days register their solvers with `aoc_core::register!`, but a crate the
+/// cli never names isn't linked and its registration is lost. Name every
+/// `day<N>` dependency so that adding a day only takes a dependency.
+fn link_days() {
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    let manifest = fs::read_to_string("Cargo.toml").expect("bin has a manifest");
+    let uses: String = manifest
+        .lines()
+        .filter_map(|line| {
+            // `day1.workspace = true` or `day1 = { workspace = true, ... }`
+            line.strip_suffix(".workspace = true").or_else(|| {
+                let (name, table) = line.split_once(" = {")?;
+                table.contains("workspace = true").then_some(name)
+            })
+        })
+        .filter(|name| {
+            name.strip_prefix("day")
+                .is_some_and(|day| day.parse::<usize>().is_ok())
+        })
+        .map(|name| format!("use {name} as _;\n"))
+        .collect();
+    let out_dir = env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    fs::write(Path::new(&out_dir).join("link_days.rs"), uses).expect("OUT_DIR is writable");
+}
+
+/// Generate the gRPC server from `proto/aoc.proto`, with a vendored `protoc`
+/// so building it doesn't need one installed
+#[cfg(feature = "grpc")]
+fn compile_protos() {
+    println!("cargo:rerun-if-changed=proto/aoc.proto");
+    let protoc =
+        protoc_bin_vendored::protoc_bin_path().expect("protoc is vendored for this platform");
+    env::set_var("PROTOC", protoc);
+    tonic_build::configure()
+        .build_client(false)
+        .compile_protos(&["proto/aoc.proto"], &["proto"])
+        .expect("proto/aoc.proto compiles");
+}
@@ -0,0 +1,125 @@
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use anstyle::{AnsiColor, Style};
+use anyhow::Result;
+use aoc_core::visualize::{self, Frame, Mark, Visualize};
+
+use crate::color;
+
+/// clears the terminal and moves the cursor home, so each frame is drawn over
+/// the last
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Keeps every frame a solve emits, to play back once it's done
+#[derive(Default)]
+struct Recorder(Mutex<Vec<Frame>>);
+
+impl Visualize for Recorder {
+    fn frame(&self, frame: Frame) {
+        if let Ok(mut frames) = self.0.lock() {
+            frames.push(frame);
+        }
+    }
+}
+
+/// run `f`, keeping the frames emitted while it runs
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Vec<Frame>) {
+    let recorder = Arc::new(Recorder::default());
+    let result = visualize::scoped(recorder.clone(), f);
+    let frames = recorder
+        .0
+        .lock()
+        .map(|mut frames| std::mem::take(&mut *frames))
+        .unwrap_or_default();
+    (result, frames)
+}
+
+fn style(mark: Mark) -> Style {
+    match mark {
+        Mark::Current => AnsiColor::Yellow.on_default().bold(),
+        Mark::Accepted => color::GOOD.bold(),
+        Mark::Rejected => color::BAD,
+    }
+}
+
+/// a frame as text, with its highlighted cells colored. Without color they're
+/// shown as `#`, `+` and `-` for current, accepted and rejected cells.
+pub fn render(frame: &Frame, color: bool) -> String {
+    let mut text = String::new();
+    for (row, cells) in frame.rows.iter().enumerate() {
+        for (column, cell) in cells.chars().enumerate() {
+            match (frame.mark(row, column), color) {
+                (None, _) => text.push(cell),
+                (Some(mark), true) => text.push_str(&color::paint(cell, style(mark), true)),
+                (Some(Mark::Current), false) => text.push('#'),
+                (Some(Mark::Accepted), false) => text.push('+'),
+                (Some(Mark::Rejected), false) => text.push('-'),
+            }
+        }
+        text.push('\n');
+    }
+    for annotation in &frame.annotations {
+        text.push_str(&color::paint(annotation, color::DIM, color));
+        text.push('\n');
+    }
+    text
+}
+
+/// draw `frames` one after another, `fps` a second. On a terminal each frame
+/// replaces the last, otherwise they're written one after another.
+pub fn play(
+    frames: &[Frame],
+    fps: u32,
+    live: bool,
+    color: bool,
+    out: &mut impl Write,
+) -> Result<()> {
+    let delay = Duration::from_secs(1) / fps.max(1);
+    for (i, frame) in frames.iter().enumerate() {
+        if live {
+            write!(out, "{CLEAR}")?;
+        }
+        write!(out, "{}", render(frame, color))?;
+        if live {
+            writeln!(out, "frame {} of {}", i + 1, frames.len())?;
+            out.flush()?;
+            thread::sleep(delay);
+        } else {
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        Frame::new(vec!["12.".to_string(), "..*".to_string()])
+            .highlight(0, 0, Mark::Accepted)
+            .highlight(0, 1, Mark::Accepted)
+            .highlight(1, 2, Mark::Current)
+            .annotate("12 is a part number")
+    }
+
+    #[test]
+    fn should_render_marks_without_color() {
+        assert_eq!(render(&frame(), false), "++.\n..#\n12 is a part number\n");
+    }
+
+    #[test]
+    fn should_write_every_frame_when_not_live() -> Result<()> {
+        let mut out = vec![];
+        play(&[frame(), frame()], 1000, false, false, &mut out)?;
+        let out = String::from_utf8(out)?;
+        assert_eq!(out.matches("12 is a part number").count(), 2);
+        assert!(!out.contains(CLEAR));
+        Ok(())
+    }
+}
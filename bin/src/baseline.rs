@@ -0,0 +1,198 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::Path, time::Duration};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::bench::Stats;
+
+/// default percentage a part's median can slow down by before it counts as
+/// a regression
+pub const MAX_REGRESSION: f64 = 10.0;
+
+/// The median time of each part of a day, in nanoseconds. Either part may
+/// be left out, so a baseline can be trimmed to the parts worth guarding.
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+pub struct Medians {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<u64>,
+}
+
+impl Medians {
+    pub fn from_stats(part_one: &Stats, part_two: &Stats) -> Medians {
+        Medians {
+            part1: Some(part_one.median.as_nanos() as u64),
+            part2: Some(part_two.median.as_nanos() as u64),
+        }
+    }
+}
+
+/// Stored measurements keyed by day, read from and written to JSON such as
+///
+/// ```json
+/// {
+///   "day1": { "part1": 52000, "part2": 61000 },
+///   "day4": { "part1": 18000 }
+/// }
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline(pub BTreeMap<usize, Medians>);
+
+impl Baseline {
+    pub fn parse(text: &str) -> Result<Baseline> {
+        let days: BTreeMap<String, Medians> = serde_json::from_str(text)?;
+        let mut baseline = BTreeMap::new();
+        for (name, medians) in days {
+            let day = name
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| anyhow!("expected a key named day<N>, found {name:?}"))?;
+            baseline.insert(day, medians);
+        }
+        Ok(Baseline(baseline))
+    }
+
+    pub fn load(path: &Path) -> Result<Baseline> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("unable to read baseline {}", path.display()))?;
+        Baseline::parse(&text).with_context(|| format!("invalid baseline {}", path.display()))
+    }
+
+    /// `day`'s medians in the baseline, or an error saying the file has none
+    pub fn day(&self, day: usize, path: &Path) -> Result<&Medians> {
+        self.0
+            .get(&day)
+            .ok_or_else(|| anyhow!("{} has no baseline for day {day}", path.display()))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        let days: BTreeMap<String, &Medians> = self
+            .0
+            .iter()
+            .map(|(day, medians)| (format!("day{day}"), medians))
+            .collect();
+        Ok(serde_json::to_string_pretty(&days)?)
+    }
+
+    /// write `medians` as `day`'s entry in the baseline at `path`, keeping
+    /// the other days already saved there
+    pub fn save(path: &Path, day: usize, medians: Medians) -> Result<()> {
+        let mut baseline = match fs::read_to_string(path) {
+            Ok(text) => Baseline::parse(&text)
+                .with_context(|| format!("invalid baseline {}", path.display()))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Baseline::default(),
+            Err(e) => return Err(e.into()),
+        };
+        baseline.0.insert(day, medians);
+        fs::write(path, baseline.to_json()? + "\n")
+            .with_context(|| format!("unable to write baseline {}", path.display()))
+    }
+}
+
+/// One part's median compared with its baseline
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    /// how much slower the part is than its baseline, as a percentage, with
+    /// speedups negative
+    pub fn change(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64();
+        if baseline == 0.0 {
+            return 0.0;
+        }
+        (self.current.as_secs_f64() - baseline) / baseline * 100.0
+    }
+
+    pub fn regressed(&self, max_regression: f64) -> bool {
+        self.change() > max_regression
+    }
+}
+
+/// compare each part of a day that has a baseline
+pub fn compare(current: &Medians, baseline: &Medians) -> Vec<Comparison> {
+    [
+        (1, current.part1, baseline.part1),
+        (2, current.part2, baseline.part2),
+    ]
+    .into_iter()
+    .filter_map(|(part, current, baseline)| {
+        Some(Comparison {
+            part,
+            baseline: Duration::from_nanos(baseline?),
+            current: Duration::from_nanos(current?),
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    #[test]
+    fn should_parse_medians_by_day() -> Result<()> {
+        let baseline = Baseline::parse(
+            r#"{"day1": {"part1": 52000, "part2": 61000}, "day4": {"part1": 18000}}"#,
+        )?;
+        assert_eq!(baseline.0.len(), 2);
+        assert_eq!(
+            baseline.0[&4],
+            Medians {
+                part1: Some(18000),
+                part2: None
+            }
+        );
+        assert_eq!(Baseline::parse(&baseline.to_json()?)?, baseline);
+        assert!(Baseline::parse(r#"{"one": {}}"#).is_err());
+        assert!(Baseline::parse(r#"{"day1": {"part3": 1}}"#).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn should_flag_parts_slower_than_allowed() {
+        let baseline = Medians {
+            part1: Some(100_000),
+            part2: Some(100_000),
+        };
+        let current = Medians {
+            part1: Some(105_000),
+            part2: Some(125_000),
+        };
+        let comparisons = compare(&current, &baseline);
+        assert!((comparisons[0].change() - 5.0).abs() < 1e-9);
+        assert!(!comparisons[0].regressed(MAX_REGRESSION));
+        assert!(comparisons[1].regressed(MAX_REGRESSION));
+        assert!(!comparisons[1].regressed(30.0));
+
+        let partial = Medians {
+            part2: None,
+            ..baseline
+        };
+        assert_eq!(compare(&current, &partial).len(), 1);
+    }
+
+    #[test]
+    fn should_save_a_day_alongside_the_others() -> Result<()> {
+        let path = env::temp_dir().join(format!("aoc-baseline-{}.json", process::id()));
+        let medians = Medians {
+            part1: Some(1),
+            part2: Some(2),
+        };
+        Baseline::save(&path, 1, medians)?;
+        Baseline::save(&path, 4, medians)?;
+        Baseline::save(&path, 1, Medians::default())?;
+        let baseline = Baseline::load(&path)?;
+        assert_eq!(baseline.0[&1], Medians::default());
+        assert_eq!(baseline.0[&4], medians);
+        fs::remove_file(&path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+use std::{fmt::Write, fs, path::Path};
+
+use anyhow::{anyhow, Result};
+
+use crate::{
+    client,
+    output::{self, Format, Run},
+};
+
+/// The outcome of solving one of the inputs in a batch
+pub type Row = (String, Result<Run, String>);
+
+/// expand the `--input` arguments into the inputs to run. Directories stand
+/// for every file in them and glob patterns for every file they match, both
+/// in sorted order. Anything else, urls included, is kept as is.
+pub fn expand(inputs: &[String]) -> Result<Vec<String>> {
+    let mut expanded = vec![];
+    for input in inputs {
+        if client::is_url(input) {
+            expanded.push(input.clone());
+        } else if Path::new(input).is_dir() {
+            let mut files = vec![];
+            for entry in fs::read_dir(input)? {
+                let path = entry?.path();
+                if path.is_file() {
+                    files.push(path.to_string_lossy().into_owned());
+                }
+            }
+            files.sort();
+            expanded.extend(files);
+        } else if input.contains(['*', '?', '[']) {
+            let mut files = vec![];
+            for path in glob::glob(input)? {
+                files.push(path?.to_string_lossy().into_owned());
+            }
+            if files.is_empty() {
+                return Err(anyhow!("no inputs match {input}"));
+            }
+            files.sort();
+            expanded.extend(files);
+        } else {
+            expanded.push(input.clone());
+        }
+    }
+    Ok(expanded)
+}
+
+/// a solved input's run as an object with an extra `input` field
+fn with_input(input: &str, run: &Run) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(run)?;
+    value["input"] = input.into();
+    Ok(value)
+}
+
+/// render the results of a batch as a table of inputs and their answers, or
+/// as the machine formats with an extra `input` field
+pub fn render(rows: &[Row], format: Format) -> Result<Vec<u8>> {
+    let mut lines = vec![];
+    match format {
+        Format::Text => {
+            let width = rows.iter().map(|(input, _)| input.len()).max().unwrap_or(0);
+            lines.push(format!(
+                "{:width$}  {:>16}  {:>16}  {:>12}",
+                "input", "part one", "part two", "time"
+            ));
+            for (input, run) in rows {
+                lines.push(match run {
+                    Ok(run) => format!(
+                        "{input:width$}  {:>16}  {:>16}  {:>12}",
+                        run.part1,
+                        run.part2,
+                        format!("{:?}", run.timings.total())
+                    ),
+                    Err(e) => format!("{input:width$}  error: {e}"),
+                });
+            }
+        }
+        Format::Json => {
+            for (input, run) in rows.iter().filter_map(|(i, r)| Some((i, r.as_ref().ok()?))) {
+                lines.push(with_input(input, run)?.to_string());
+            }
+        }
+        Format::Csv => {
+            lines.push("input,part,answer,duration_ns".to_string());
+            for (input, run) in rows.iter().filter_map(|(i, r)| Some((i, r.as_ref().ok()?))) {
+                let mut line = String::new();
+                write!(
+                    line,
+                    "{input:?},1,{},{}",
+                    output::csv_answer(&run.part1),
+                    run.timings.part1.as_nanos()
+                )?;
+                write!(
+                    line,
+                    "\n{input:?},2,{},{}",
+                    output::csv_answer(&run.part2),
+                    run.timings.part2.as_nanos()
+                )?;
+                lines.push(line);
+            }
+        }
+        Format::Raw => {
+            for run in rows.iter().filter_map(|(_, run)| run.as_ref().ok()) {
+                lines.push(format!("{}\n{}", run.part1, run.part2));
+            }
+        }
+        Format::Msgpack => {
+            let mut out = vec![];
+            for (input, run) in rows.iter().filter_map(|(i, r)| Some((i, r.as_ref().ok()?))) {
+                out.extend(output::msgpack(&with_input(input, run)?)?);
+            }
+            return Ok(out);
+        }
+    }
+    Ok(format!("{}\n", lines.join("\n")).into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Timings;
+    use aoc_core::answer::Answer;
+    use std::{env, process, time::Duration};
+
+    #[test]
+    fn should_expand_directories_and_globs() -> Result<()> {
+        let dir = env::temp_dir().join(format!("aoc-batch-{}", process::id()));
+        fs::create_dir_all(&dir)?;
+        for name in ["b.txt", "a.txt", "notes.md"] {
+            fs::write(dir.join(name), "")?;
+        }
+        let dir_name = dir.to_string_lossy().into_owned();
+
+        let all = expand(std::slice::from_ref(&dir_name))?;
+        assert_eq!(all.len(), 3);
+        assert!(all[0].ends_with("a.txt"));
+
+        let texts = expand(&[format!("{dir_name}/*.txt"), "https://x.test/day4".into()])?;
+        assert_eq!(texts.len(), 3);
+        assert!(texts[1].ends_with("b.txt"));
+        assert_eq!(texts[2], "https://x.test/day4");
+
+        assert!(expand(&[format!("{dir_name}/*.csv")]).is_err());
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_table_of_inputs() -> Result<()> {
+        let run = Run {
+            day: 4,
+            part1: Answer::U64(13),
+            part2: Answer::U64(30),
+            timings: Timings {
+                input: Duration::from_micros(1),
+                parse: None,
+                part1: Duration::from_micros(2),
+                part2: Duration::from_micros(3),
+            },
+            memory: None,
+            input_hash: String::new(),
+        };
+        let rows = vec![
+            ("mine.txt".to_string(), Ok(run)),
+            ("friend.txt".to_string(), Err("bad card".to_string())),
+        ];
+
+        let table = String::from_utf8(render(&rows, Format::Text)?)?;
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[1],
+            "mine.txt                  13                30           6µs"
+        );
+        assert_eq!(lines[2], "friend.txt  error: bad card");
+
+        let csv = String::from_utf8(render(&rows, Format::Csv)?)?;
+        assert_eq!(
+            csv,
+            "input,part,answer,duration_ns\n\"mine.txt\",1,13,2000\n\"mine.txt\",2,30,3000\n"
+        );
+
+        let msgpack = render(&rows, Format::Msgpack)?;
+        let solved: serde_json::Value = rmp_serde::from_read(&mut msgpack.as_slice())?;
+        assert_eq!(solved["input"], "mine.txt");
+        assert_eq!(solved["part2"], 30);
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+#[cfg(target_os = "linux")]
+use std::{io, mem};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::timing::timed;
+use tracing::debug;
+
+use crate::days::{Day, Solver};
+
+/// Summary statistics for a set of measured iterations
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// compute the summary of a non-empty set of samples
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let count = sorted.len();
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+
+        let seconds: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+        let mean = seconds.iter().sum::<f64>() / count as f64;
+        let variance = seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / count as f64;
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// runs in each of the batches `settle` compares
+const SETTLE_BATCH: usize = 5;
+
+/// how much a batch's median can differ from the one before it, as a
+/// fraction of that one, for run times to count as settled
+const SETTLED_WITHIN: f64 = 0.05;
+
+/// the longest `settle` keeps warming up for
+const SETTLE_AT_MOST: Duration = Duration::from_secs(2);
+
+/// the smallest page size of the platforms the cli runs on
+const PAGE: usize = 4096;
+
+/// whether `current` is within [`SETTLED_WITHIN`] of `previous`
+fn settled(previous: Duration, current: Duration) -> bool {
+    let previous = previous.as_secs_f64();
+    (current.as_secs_f64() - previous).abs() <= previous * SETTLED_WITHIN
+}
+
+/// keep running a part solver in batches until a batch's median is close to
+/// the one before it, by when the CPU's clock has ramped up and its caches
+/// hold what the solver uses, or until [`SETTLE_AT_MOST`] has passed.
+/// Returns how many runs it took.
+fn settle(solver: &Solver, input: &[u8]) -> Result<usize> {
+    let start = Instant::now();
+    let mut previous = None;
+    let mut runs = 0;
+    loop {
+        let mut batch = Vec::with_capacity(SETTLE_BATCH);
+        for _ in 0..SETTLE_BATCH {
+            let (result, elapsed) = timed(|| solver(input));
+            result?;
+            batch.push(elapsed);
+        }
+        runs += SETTLE_BATCH;
+        let median = Stats::from_samples(&batch).median;
+        if previous.is_some_and(|previous| settled(previous, median))
+            || start.elapsed() >= SETTLE_AT_MOST
+        {
+            return Ok(runs);
+        }
+        previous = Some(median);
+    }
+}
+
+/// read a byte from every page of `input`, so a mapped input's pages are
+/// faulted in before it's measured rather than during the first runs
+fn prefault(input: &[u8]) {
+    let touched = input.iter().step_by(PAGE).fold(0, |acc, byte| acc ^ byte);
+    black_box(touched);
+}
+
+/// pin the calling thread to CPU `core`, or to the one it's running on, so
+/// the scheduler can't move it between runs. Threads it starts afterwards
+/// share the pin. Returns the core it was pinned to.
+#[cfg(target_os = "linux")]
+pub fn pin_cpu(core: Option<usize>) -> Result<usize> {
+    let core = match core {
+        Some(core) => core,
+        // SAFETY: sched_getcpu has no preconditions
+        None => usize::try_from(unsafe { libc::sched_getcpu() })
+            .map_err(|_| io::Error::last_os_error())
+            .context("unable to find which cpu the benchmark is running on")?,
+    };
+    if core >= libc::CPU_SETSIZE as usize {
+        return Err(anyhow!("there is no cpu {core}"));
+    }
+    // SAFETY: an all zero cpu_set_t is an empty set, and `core` was checked
+    // to fit in it
+    let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+    unsafe { libc::CPU_SET(core, &mut set) };
+    // SAFETY: `set` is a cpu_set_t of the size given, and pid 0 is the
+    // calling thread
+    if unsafe { libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) } != 0 {
+        return Err(io::Error::last_os_error())
+            .with_context(|| format!("unable to pin to cpu {core}"));
+    }
+    Ok(core)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_cpu(_core: Option<usize>) -> Result<usize> {
+    Err(anyhow!("--pin-cpu is only supported on Linux"))
+}
+
+/// run a part solver `warmup` times without measuring, and then until its
+/// run times settle if `quiesce` is set, then `iterations` times while
+/// recording how long each run took
+fn measure(
+    solver: &Solver,
+    input: &[u8],
+    warmup: usize,
+    iterations: usize,
+    quiesce: bool,
+) -> Result<Stats> {
+    for _ in 0..warmup {
+        solver(input)?;
+    }
+    if quiesce {
+        let runs = settle(solver, input)?;
+        debug!(runs, "run times settled");
+    }
+
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let (result, elapsed) = timed(|| solver(input));
+        result?;
+        samples.push(elapsed);
+    }
+    Ok(Stats::from_samples(&samples))
+}
+
+/// benchmark both parts of a day, returning the stats for part one and part
+/// two. With `quiesce` set every page of the input is read first, and each
+/// part warms up until its run times settle.
+pub fn run(
+    day: &Day,
+    input: &[u8],
+    warmup: usize,
+    iterations: usize,
+    quiesce: bool,
+) -> Result<(Stats, Stats)> {
+    if quiesce {
+        prefault(input);
+    }
+    let part_one = measure(&day.part_one, input, warmup, iterations, quiesce)?;
+    let part_two = measure(&day.part_two, input, warmup, iterations, quiesce)?;
+    Ok((part_one, part_two))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_summarize_samples() {
+        let samples = [
+            Duration::from_micros(4),
+            Duration::from_micros(2),
+            Duration::from_micros(6),
+            Duration::from_micros(8),
+        ];
+        let result = Stats::from_samples(&samples);
+        assert_eq!(result.min, Duration::from_micros(2));
+        assert_eq!(result.median, Duration::from_micros(5));
+        assert_eq!(result.mean, Duration::from_micros(5));
+        // population standard deviation of 2, 4, 6, 8 is sqrt(5)
+        assert_eq!(result.stddev.as_nanos(), 2236);
+    }
+
+    #[test]
+    fn should_take_middle_sample_as_median() {
+        let samples = [
+            Duration::from_micros(9),
+            Duration::from_micros(1),
+            Duration::from_micros(3),
+        ];
+        let result = Stats::from_samples(&samples);
+        assert_eq!(result.median, Duration::from_micros(3));
+    }
+
+    #[test]
+    fn should_settle_once_medians_are_close() {
+        let previous = Duration::from_micros(100);
+        assert!(settled(previous, Duration::from_micros(104)));
+        assert!(settled(previous, Duration::from_micros(96)));
+        assert!(!settled(previous, Duration::from_micros(110)));
+        assert!(!settled(previous, Duration::from_micros(80)));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn should_pin_to_the_current_cpu() -> Result<()> {
+        // pinning a thread of its own leaves the test harness's threads be
+        std::thread::spawn(|| {
+            let core = pin_cpu(None)?;
+            // SAFETY: sched_getcpu has no preconditions
+            assert_eq!(unsafe { libc::sched_getcpu() }, core as i32);
+            assert!(pin_cpu(Some(libc::CPU_SETSIZE as usize)).is_err());
+            Ok(())
+        })
+        .join()
+        .expect("the pinned thread doesn't panic")
+    }
+}
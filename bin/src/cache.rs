@@ -0,0 +1,71 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{anyhow, Result};
+
+use crate::{client::Client, config::Config};
+
+/// directory holding downloaded inputs, following the XDG convention of
+/// `$XDG_CACHE_HOME/aoc2023`, or `~/.cache/aoc2023` when that is unset
+pub fn dir() -> Result<PathBuf> {
+    let base = match env::var_os("XDG_CACHE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => {
+            let home = env::var_os("HOME")
+                .ok_or_else(|| anyhow!("unable to locate cache directory, HOME is not set"))?;
+            PathBuf::from(home).join(".cache")
+        }
+    };
+    Ok(base.join("aoc2023"))
+}
+
+/// adventofcode.com asks that private leaderboards aren't fetched more often
+/// than this
+const LEADERBOARD_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// location of the cached input for a given day
+pub fn path(day: usize) -> Result<PathBuf> {
+    Ok(dir()?.join(format!("day{day}.txt")))
+}
+
+/// read a day's input from the cache, downloading and caching it first if
+/// it has never been fetched
+pub fn load_or_fetch(day: usize, config: &Config) -> Result<String> {
+    let path = path(day)?;
+    if let Ok(text) = fs::read_to_string(&path) {
+        return Ok(text);
+    }
+
+    let text = Client::from_config(config)
+        .map_err(|e| anyhow!("no cached input for day {day} and unable to download it: {e}"))?
+        .input(day)?;
+    fs::create_dir_all(dir()?)?;
+    fs::write(&path, &text)?;
+    Ok(text)
+}
+
+/// read a private leaderboard's JSON, downloading it again only once the
+/// cached copy is older than fifteen minutes
+pub fn load_or_fetch_leaderboard(id: u64, config: &Config) -> Result<String> {
+    let path = dir()?.join(format!("leaderboard-{id}.json"));
+    let fresh = fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .is_ok_and(|modified| {
+            SystemTime::now()
+                .duration_since(modified)
+                .is_ok_and(|age| age < LEADERBOARD_MAX_AGE)
+        });
+    if fresh {
+        if let Ok(json) = fs::read_to_string(&path) {
+            return Ok(json);
+        }
+    }
+
+    let json = Client::from_config(config)?.leaderboard(id)?;
+    fs::create_dir_all(dir()?)?;
+    fs::write(&path, &json)?;
+    Ok(json)
+}
@@ -0,0 +1,200 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// puzzles unlock at midnight US Eastern Standard Time, which is UTC-5
+/// throughout December
+const RELEASE_UTC_OFFSET_SECONDS: i64 = -5 * 60 * 60;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// A calendar date in AoC's release timezone
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+/// seconds since the unix epoch, shifted into the release timezone
+fn release_seconds(now: SystemTime) -> i64 {
+    let seconds = match now.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs() as i64,
+        Err(before) => -(before.duration().as_secs() as i64),
+    };
+    seconds + RELEASE_UTC_OFFSET_SECONDS
+}
+
+/// the date it is in AoC's release timezone at the given instant
+pub fn release_date(now: SystemTime) -> Date {
+    civil_from_days(release_seconds(now).div_euclid(SECONDS_PER_DAY))
+}
+
+/// the day of the puzzle released most recently, if `now` falls within the
+/// event (December 1st through 25th)
+pub fn puzzle_day(now: SystemTime) -> Option<usize> {
+    let date = release_date(now);
+    (date.month == 12 && date.day <= 25).then_some(date.day as usize)
+}
+
+/// a timestamp as `YYYY-MM-DD HH:MM:SS` in AoC's release timezone
+pub fn release_time(at: SystemTime) -> String {
+    let seconds = release_seconds(at);
+    let date = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+    let time = seconds.rem_euclid(SECONDS_PER_DAY);
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02}:{:02}",
+        date.year,
+        date.month,
+        date.day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// The next puzzle to unlock
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Unlock {
+    pub day: usize,
+    pub at: SystemTime,
+}
+
+/// the first puzzle to unlock after `now`: tomorrow's during the event, or
+/// the next December 1st outside it
+pub fn next_unlock(now: SystemTime) -> Unlock {
+    let today = release_seconds(now).div_euclid(SECONDS_PER_DAY);
+    let date = civil_from_days(today);
+    let (day, unlock_day) = match date {
+        Date { month: 12, day, .. } if day < 25 => (day as usize + 1, today + 1),
+        Date {
+            month: 12, year, ..
+        } => (1, days_from_civil(year + 1, 12, 1)),
+        Date { year, .. } => (1, days_from_civil(year, 12, 1)),
+    };
+    let seconds = unlock_day * SECONDS_PER_DAY - RELEASE_UTC_OFFSET_SECONDS;
+    Unlock {
+        day,
+        at: UNIX_EPOCH + Duration::from_secs(seconds as u64),
+    }
+}
+
+/// a countdown such as `2d 03:04:05`, leaving off the days when there are none
+pub fn countdown(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    let (days, hours) = (seconds / 86_400, seconds % 86_400 / 3600);
+    let clock = format!("{hours:02}:{:02}:{:02}", seconds % 3600 / 60, seconds % 60);
+    match days {
+        0 => clock,
+        _ => format!("{days}d {clock}"),
+    }
+}
+
+/// convert a calendar date into a count of days since 1970-01-01, the
+/// inverse of [`civil_from_days`]
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = i64::from((month + 9) % 12);
+    let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// convert a count of days since 1970-01-01 into a calendar date, using
+/// Howard Hinnant's `civil_from_days` algorithm
+fn civil_from_days(days: i64) -> Date {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    Date { year, month, day }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn should_convert_epoch_to_date() {
+        let expected = Date {
+            year: 1970,
+            month: 1,
+            day: 1,
+        };
+        assert_eq!(civil_from_days(0), expected);
+    }
+
+    #[test]
+    fn should_unlock_at_midnight_eastern() {
+        // 2023-12-01T05:00:00Z is midnight in EST
+        assert_eq!(puzzle_day(at(1_701_406_800)), Some(1));
+        // one second earlier it is still November 30th
+        assert_eq!(puzzle_day(at(1_701_406_799)), None);
+    }
+
+    #[test]
+    fn should_find_the_next_unlock() {
+        // 2023-12-04T12:00:00Z unlocks day 5 at 2023-12-05T05:00:00Z
+        let during = next_unlock(at(1_701_691_200));
+        assert_eq!(
+            during,
+            Unlock {
+                day: 5,
+                at: at(1_701_752_400)
+            }
+        );
+
+        // 2023-11-30T12:00:00Z waits for 2023-12-01T05:00:00Z
+        assert_eq!(next_unlock(at(1_701_345_600)).at, at(1_701_406_800));
+
+        // 2023-12-25T12:00:00Z waits for 2024-12-01T05:00:00Z
+        let after = next_unlock(at(1_703_505_600));
+        assert_eq!(
+            after,
+            Unlock {
+                day: 1,
+                at: at(1_733_029_200)
+            }
+        );
+    }
+
+    #[test]
+    fn should_format_release_times() {
+        // 2023-12-01T05:00:07Z
+        assert_eq!(release_time(at(1_701_406_807)), "2023-12-01 00:00:07");
+    }
+
+    #[test]
+    fn should_format_countdowns() {
+        assert_eq!(countdown(Duration::from_secs(65)), "00:01:05");
+        assert_eq!(
+            countdown(Duration::from_secs(2 * 86_400 + 3 * 3600 + 4 * 60 + 5)),
+            "2d 03:04:05"
+        );
+    }
+
+    #[test]
+    fn should_only_infer_days_during_the_event() {
+        // 2023-12-25T12:00:00Z
+        assert_eq!(puzzle_day(at(1_703_505_600)), Some(25));
+        // 2023-12-26T05:00:00Z
+        assert_eq!(puzzle_day(at(1_703_566_800)), None);
+        // 2024-12-10T06:00:00Z
+        assert_eq!(puzzle_day(at(1_733_810_400)), Some(10));
+    }
+}
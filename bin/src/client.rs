@@ -0,0 +1,317 @@
+use std::{
+    env, fs,
+    io::Read,
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{cache, config::Config};
+
+const BASE_URL: &str = "https://adventofcode.com/2023";
+
+/// adventofcode.com asks automated tools to identify themselves with a
+/// User-Agent pointing back at the source of the tool
+pub const USER_AGENT: &str = "github.com/internet-diglett/aoc2023 aoc-cli";
+
+/// minimum time we leave between two requests to adventofcode.com
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A small blocking client for the parts of adventofcode.com we talk to
+pub struct Client {
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// build a client using the session token found in `AOC_SESSION`, or
+    /// in the configured session file
+    pub fn from_config(config: &Config) -> Result<Client> {
+        let session = match (env::var("AOC_SESSION"), &config.session_file) {
+            (Ok(session), _) => session,
+            (Err(_), Some(file)) => fs::read_to_string(file)
+                .with_context(|| format!("failed to read session file {}", file.display()))?,
+            (Err(_), None) => {
+                return Err(anyhow!(
+                    "AOC_SESSION must be set to your adventofcode.com session token"
+                ))
+            }
+        };
+        Ok(Client::new(session.trim()))
+    }
+
+    pub fn new(session: &str) -> Client {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+        Client {
+            session: session.to_string(),
+            agent,
+        }
+    }
+
+    /// download any page from adventofcode.com with the session token
+    pub fn get(&self, url: &str) -> Result<Vec<u8>> {
+        throttle()?;
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .with_context(|| format!("failed to download {url}"))?;
+        read_body(response)
+    }
+
+    /// download the puzzle input for a given day
+    pub fn input(&self, day: usize) -> Result<String> {
+        throttle()?;
+        let response = self
+            .agent
+            .get(&format!("{BASE_URL}/day/{day}/input"))
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .with_context(|| format!("failed to download input for day {day}"))?;
+        Ok(response.into_string()?)
+    }
+
+    /// download the JSON for a private leaderboard the session can see
+    pub fn leaderboard(&self, id: u64) -> Result<String> {
+        let body = self.get(&format!("{BASE_URL}/leaderboard/private/view/{id}.json"))?;
+        Ok(String::from_utf8(body)?)
+    }
+
+    /// post an answer for one part of a day's puzzle, returning how
+    /// adventofcode.com judged it
+    pub fn submit(&self, day: usize, part: u8, answer: &str) -> Result<Verdict> {
+        throttle()?;
+        let response = self
+            .agent
+            .post(&format!("{BASE_URL}/day/{day}/answer"))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .with_context(|| format!("failed to submit answer for day {day} part {part}"))?;
+        Ok(Verdict::parse(&response.into_string()?))
+    }
+}
+
+/// The outcome of submitting an answer
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// we're being throttled and need to wait this long before trying again
+    Wait(Duration),
+    /// the part has already been solved (or is not unlocked yet)
+    WrongLevel,
+    /// a response we don't recognize, holding the text of the message
+    Unknown(String),
+}
+
+impl Verdict {
+    /// interpret the HTML page returned after submitting an answer
+    pub fn parse(html: &str) -> Verdict {
+        // the message we care about lives in the page's only <article>
+        let message = html
+            .split_once("<article>")
+            .and_then(|(_, rest)| rest.split_once("</article>"))
+            .map(|(article, _)| strip_tags(article))
+            .unwrap_or_else(|| strip_tags(html));
+
+        if message.contains("That's the right answer") {
+            Verdict::Correct
+        } else if message.contains("too high") {
+            Verdict::TooHigh
+        } else if message.contains("too low") {
+            Verdict::TooLow
+        } else if message.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::Wait(parse_wait(&message).unwrap_or(Duration::from_secs(60)))
+        } else if message.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown(message.trim().to_string())
+        }
+    }
+}
+
+/// remove html tags, leaving only the text content
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// pull the wait time out of a throttling message such as
+/// "You have 1m 3s left to wait." or "You have 45s left to wait."
+fn parse_wait(message: &str) -> Option<Duration> {
+    let (_, rest) = message.split_once("You have ")?;
+    let (amount, _) = rest.split_once(" left to wait")?;
+
+    let mut seconds = 0;
+    for token in amount.split_whitespace() {
+        let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+        let value: u64 = value.parse().ok()?;
+        seconds += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+fn read_body(response: ureq::Response) -> Result<Vec<u8>> {
+    let mut body = vec![];
+    response.into_reader().read_to_end(&mut body)?;
+    Ok(body)
+}
+
+/// whether `input` is a url rather than a path to a file
+pub fn is_url(input: &str) -> bool {
+    input.starts_with("https://") || input.starts_with("http://")
+}
+
+/// whether a url points at adventofcode.com, so needs the session token
+pub fn is_aoc_url(url: &str) -> bool {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    matches!(host, "adventofcode.com" | "www.adventofcode.com")
+}
+
+/// the day whose puzzle input a url points at, if it is one of this year's
+/// input urls such as `https://adventofcode.com/2023/day/4/input`
+pub fn input_day(url: &str) -> Option<usize> {
+    let url = url
+        .replacen("://www.", "://", 1)
+        .replacen("http://", "https://", 1);
+    url.strip_prefix(BASE_URL)?
+        .strip_prefix("/day/")?
+        .trim_end_matches('/')
+        .strip_suffix("/input")?
+        .parse()
+        .ok()
+}
+
+/// download a url that isn't on adventofcode.com, so no session is sent
+pub fn download(url: &str) -> Result<Vec<u8>> {
+    let response = ureq::AgentBuilder::new()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(30))
+        .build()
+        .get(url)
+        .call()
+        .with_context(|| format!("failed to download {url}"))?;
+    read_body(response)
+}
+
+/// file recording when we last sent a request, shared between invocations
+/// so back-to-back runs of the cli are rate limited too
+fn last_request_marker() -> Result<PathBuf> {
+    let dir = cache::dir()?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join("last-request"))
+}
+
+/// sleep until at least `MIN_REQUEST_INTERVAL` has passed since the previous
+/// request, then record the current time as the latest request
+fn throttle() -> Result<()> {
+    let marker = last_request_marker()?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+
+    let previous = fs::read_to_string(&marker)
+        .ok()
+        .and_then(|text| text.trim().parse::<u64>().ok())
+        .map(Duration::from_millis);
+
+    if let Some(previous) = previous {
+        let elapsed = now.saturating_sub(previous);
+        if elapsed < MIN_REQUEST_INTERVAL {
+            thread::sleep(MIN_REQUEST_INTERVAL - elapsed);
+        }
+    }
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    fs::write(&marker, now.as_millis().to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!("<html><main><article><p>{message}</p></article></main></html>")
+    }
+
+    #[test]
+    fn should_parse_correct_answer() {
+        let html = page("That's the right answer! You are <em>one gold star</em> closer.");
+        assert_eq!(Verdict::parse(&html), Verdict::Correct);
+    }
+
+    #[test]
+    fn should_parse_too_high_and_too_low() {
+        let html = page("That's not the right answer; your answer is too high.");
+        assert_eq!(Verdict::parse(&html), Verdict::TooHigh);
+        let html = page("That's not the right answer; your answer is too low.");
+        assert_eq!(Verdict::parse(&html), Verdict::TooLow);
+    }
+
+    #[test]
+    fn should_parse_wait_time() {
+        let html = page("You gave an answer too recently. You have 1m 3s left to wait.");
+        assert_eq!(
+            Verdict::parse(&html),
+            Verdict::Wait(Duration::from_secs(63))
+        );
+        let html = page("You gave an answer too recently. You have 45s left to wait.");
+        assert_eq!(
+            Verdict::parse(&html),
+            Verdict::Wait(Duration::from_secs(45))
+        );
+    }
+
+    #[test]
+    fn should_parse_wrong_level() {
+        let html =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(Verdict::parse(&html), Verdict::WrongLevel);
+    }
+
+    #[test]
+    fn should_recognise_input_urls() {
+        assert_eq!(
+            input_day("https://adventofcode.com/2023/day/4/input"),
+            Some(4)
+        );
+        assert_eq!(
+            input_day("https://www.adventofcode.com/2023/day/12/input/"),
+            Some(12)
+        );
+        assert_eq!(input_day("https://adventofcode.com/2022/day/4/input"), None);
+        assert_eq!(input_day("https://example.com/2023/day/4/input"), None);
+
+        assert!(is_aoc_url("https://adventofcode.com/2022/day/4/input"));
+        assert!(!is_aoc_url("https://adventofcode.com.example.com/day4.txt"));
+        assert!(is_url("http://localhost:8000/day4.txt"));
+        assert!(!is_url("inputs/day4.txt"));
+    }
+}
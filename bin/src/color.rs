@@ -0,0 +1,71 @@
+use std::{
+    env,
+    io::{self, IsTerminal},
+    time::Duration,
+};
+
+use anstyle::{AnsiColor, Style};
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// Whether the text format is colored
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    /// when stdout is a terminal and `NO_COLOR` isn't set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl Color {
+    /// decide once whether to emit escape codes
+    pub fn enabled(self) -> bool {
+        match self {
+            Color::Always => true,
+            Color::Never => false,
+            Color::Auto => {
+                let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                !no_color && io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+pub const HEADER: Style = AnsiColor::Cyan.on_default().bold();
+pub const ANSWER: Style = Style::new().bold();
+pub const DIM: Style = Style::new().dimmed();
+pub const GOOD: Style = AnsiColor::Green.on_default();
+pub const BAD: Style = AnsiColor::Red.on_default().bold();
+
+/// `text` wrapped in `style`'s escape codes, or unchanged when color is off
+pub fn paint(text: impl std::fmt::Display, style: Style, enabled: bool) -> String {
+    match enabled {
+        true => format!("{style}{text}{style:#}"),
+        false => text.to_string(),
+    }
+}
+
+/// green for durations that are fast, yellow for ones worth a look and red
+/// for the slow ones
+pub fn duration(elapsed: Duration) -> Style {
+    match elapsed {
+        d if d < Duration::from_millis(1) => GOOD,
+        d if d < Duration::from_secs(1) => AnsiColor::Yellow.on_default(),
+        _ => AnsiColor::Red.on_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_only_paint_when_enabled() {
+        assert_eq!(paint(42, ANSWER, false), "42");
+        assert_eq!(paint(42, ANSWER, true), "\u{1b}[1m42\u{1b}[0m");
+        assert_eq!(duration(Duration::from_micros(5)), GOOD);
+        assert!(!Color::Never.enabled());
+    }
+}
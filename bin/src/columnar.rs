@@ -0,0 +1,172 @@
+//! Timings and parsed inputs as Parquet files, so they can be loaded straight
+//! into pandas, polars or duckdb for analysis the cli doesn't do itself.
+
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Result};
+use arrow_json::reader::{infer_json_schema_from_iterator, ReaderBuilder};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+use rusqlite::Connection;
+use serde_json::{json, Value};
+
+/// rows decoded into each record batch, which is also the size of the
+/// Parquet row groups' pages
+const BATCH_ROWS: usize = 1024;
+
+/// write `rows`, JSON objects with a field per column, to `path` as Parquet
+fn write(path: &Path, schema: Arc<Schema>, rows: &[Value]) -> Result<()> {
+    let file =
+        File::create(path).with_context(|| format!("unable to create {}", path.display()))?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), Some(properties))?;
+    let mut decoder = ReaderBuilder::new(schema)
+        .with_batch_size(BATCH_ROWS)
+        .build_decoder()?;
+    for chunk in rows.chunks(BATCH_ROWS) {
+        decoder.serialize(chunk)?;
+        if let Some(batch) = decoder.flush()? {
+            writer.write(&batch)?;
+        }
+    }
+    writer.close()?;
+    Ok(())
+}
+
+/// write every timing recorded for `days`, or for every day when `None`, to
+/// `timings.parquet` in `dir`, returning its path and how many rows it has
+pub fn write_timings(
+    connection: &Connection,
+    days: Option<&[usize]>,
+    dir: &Path,
+) -> Result<(PathBuf, usize)> {
+    let mut select = connection.prepare(
+        "SELECT timestamp, day, part, duration_ns, input_sha256, revision FROM timings
+         ORDER BY timestamp, rowid",
+    )?;
+    let rows = select.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, usize>(1)?,
+            row.get::<_, u8>(2)?,
+            row.get::<_, i64>(3)?,
+            row.get::<_, String>(4)?,
+            row.get::<_, Option<String>>(5)?,
+        ))
+    })?;
+    let mut timings = vec![];
+    for row in rows {
+        let (timestamp, day, part, duration_ns, input_sha256, revision) = row?;
+        if days.is_some_and(|days| !days.contains(&day)) {
+            continue;
+        }
+        timings.push(json!({
+            "timestamp": timestamp,
+            "day": day,
+            "part": part,
+            "duration_ns": duration_ns,
+            "input_sha256": input_sha256,
+            "revision": revision,
+        }));
+    }
+
+    // spelled out rather than inferred, so an empty database still makes a
+    // file with the right columns
+    let schema = Schema::new(vec![
+        Field::new("timestamp", DataType::Int64, false),
+        Field::new("day", DataType::UInt32, false),
+        Field::new("part", DataType::UInt8, false),
+        Field::new("duration_ns", DataType::Int64, false),
+        Field::new("input_sha256", DataType::Utf8, false),
+        Field::new("revision", DataType::Utf8, true),
+    ]);
+    let path = dir.join("timings.parquet");
+    write(&path, Arc::new(schema), &timings)?;
+    Ok((path, timings.len()))
+}
+
+/// the tables a day's parsed input makes. A model that's a list is one table
+/// with a row per element, such as a game or a card, named after the day. A
+/// model that's an object is a table per list in it, named after the day and
+/// the field, such as day 3's part numbers and symbols.
+fn tables(day: usize, model: Value) -> Vec<(String, Vec<Value>)> {
+    match model {
+        Value::Array(rows) => vec![(format!("day{day}"), rows)],
+        Value::Object(fields) => fields
+            .into_iter()
+            .filter_map(|(name, value)| match value {
+                Value::Array(rows) => Some((format!("day{day}_{name}"), rows)),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// write what `day`'s input parsed into, as [`aoc_core::export::Export`]
+/// described it, to a Parquet file per table in `dir`. The columns are
+/// inferred from the rows, lists and nested objects included. Returns each
+/// file written and how many rows it has; tables without rows are skipped,
+/// since there'd be no columns to give them.
+pub fn write_parsed(day: usize, model: Value, dir: &Path) -> Result<Vec<(PathBuf, usize)>> {
+    let mut written = vec![];
+    for (name, rows) in tables(day, model) {
+        if rows.is_empty() {
+            continue;
+        }
+        let schema = infer_json_schema_from_iterator(rows.iter().map(Ok))?;
+        let path = dir.join(format!("{name}.parquet"));
+        write(&path, Arc::new(schema), &rows)?;
+        written.push((path, rows.len()));
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::strategy::ExecutionStrategy;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use std::{env, fs, process};
+
+    fn rows(path: &Path) -> Result<i64> {
+        let reader = SerializedFileReader::new(File::open(path)?)?;
+        Ok(reader.metadata().file_metadata().num_rows())
+    }
+
+    #[test]
+    fn should_write_a_table_per_entity() -> Result<()> {
+        let dir = env::temp_dir().join(format!("aoc-columnar-{}", process::id()));
+        fs::create_dir_all(&dir)?;
+
+        let model = crate::days::exporter(3)
+            .context("day 3 can't export")?
+            .export(day3::PART_ONE_EXAMPLE, ExecutionStrategy::Sequential)?;
+        let written = write_parsed(3, model, &dir)?;
+        let names: Vec<_> = written
+            .iter()
+            .filter_map(|(path, _)| path.file_name()?.to_str())
+            .collect();
+        assert_eq!(names, ["day3_part_numbers.parquet", "day3_symbols.parquet"]);
+        assert_eq!(rows(&written[0].0)?, 10);
+
+        let connection = Connection::open_in_memory()?;
+        crate::trend::create(&connection)?;
+        connection.execute(
+            "INSERT INTO timings VALUES (1, 3, 1, 500, 'abc', NULL), (2, 4, 2, 700, 'def', 'v1')",
+            [],
+        )?;
+        let (path, count) = write_timings(&connection, Some(&[4]), &dir)?;
+        assert_eq!(count, 1);
+        assert_eq!(rows(&path)?, 1);
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use std::{io::Read, path::Path};
+
+use anyhow::{anyhow, Context, Result};
+use flate2::read::GzDecoder;
+use ruzstd::StreamingDecoder;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// How an input file is compressed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// work out the compression from the file's magic bytes or, failing
+    /// that, its extension
+    pub fn detect(path: &Path, bytes: &[u8]) -> Compression {
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        if bytes.starts_with(&GZIP_MAGIC) || extension == Some("gz") {
+            Compression::Gzip
+        } else if bytes.starts_with(&ZSTD_MAGIC) || extension == Some("zst") {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+}
+
+/// decode the contents of an input file, decompressing `.gz` and `.zst`
+/// files on the way. The bytes are left for the solvers to read, so they
+/// aren't checked to be UTF-8 here.
+pub fn decode(path: &Path, bytes: Vec<u8>) -> Result<Vec<u8>> {
+    let compression = Compression::detect(path, &bytes);
+    let mut decoded = vec![];
+    match compression {
+        Compression::None => return Ok(bytes),
+        Compression::Gzip => GzDecoder::new(bytes.as_slice()).read_to_end(&mut decoded),
+        Compression::Zstd => StreamingDecoder::new(bytes.as_slice())
+            .map_err(|e| anyhow!("{e}"))?
+            .read_to_end(&mut decoded),
+    }
+    .with_context(|| format!("unable to decompress {} as {compression:?}", path.display()))?;
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression as Level};
+    use std::io::Write;
+
+    #[test]
+    fn should_decode_gzip_by_magic_bytes() -> Result<()> {
+        let mut encoder = GzEncoder::new(vec![], Level::default());
+        encoder.write_all(b"Card 1: 41 | 41\n")?;
+        let bytes = encoder.finish()?;
+
+        // the extension doesn't matter when the magic bytes are there
+        let text = decode(Path::new("day4.txt"), bytes)?;
+        assert_eq!(text, b"Card 1: 41 | 41\n");
+        Ok(())
+    }
+
+    #[test]
+    fn should_decode_zstd() -> Result<()> {
+        // "hello\n" compressed with `zstd`
+        let bytes = vec![
+            0x28, 0xb5, 0x2f, 0xfd, 0x04, 0x58, 0x31, 0x00, 0x00, 0x68, 0x65, 0x6c, 0x6c, 0x6f,
+            0x0a, 0x53, 0x88, 0xbd, 0x91,
+        ];
+        assert_eq!(decode(Path::new("day1.txt.zst"), bytes)?, b"hello\n");
+        Ok(())
+    }
+
+    #[test]
+    fn should_pass_plain_text_through() -> Result<()> {
+        assert_eq!(decode(Path::new("day1.txt"), b"1abc2".to_vec())?, b"1abc2");
+        assert!(decode(Path::new("day1.txt.gz"), b"1abc2".to_vec()).is_err());
+        Ok(())
+    }
+}
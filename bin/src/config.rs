@@ -0,0 +1,265 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+use crate::{
+    color::Color,
+    days::{Mode, Overflow},
+    output::Format,
+};
+
+/// name of the config file looked for in the working directory
+const FILE_NAME: &str = "aoc.toml";
+
+/// Defaults for the cli, read from `aoc.toml` and overridden by `AOC_*`
+/// environment variables. Flags given on the command line take precedence
+/// over both.
+#[derive(Deserialize, Default, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// directory holding `day<N>.txt` inputs (`AOC_INPUTS_DIR`)
+    pub inputs_dir: Option<PathBuf>,
+    /// file containing the adventofcode.com session token (`AOC_SESSION_FILE`)
+    pub session_file: Option<PathBuf>,
+    /// size of the rayon thread pool (`AOC_THREADS`)
+    pub threads: Option<usize>,
+    /// how many days are solved at once when several are selected (`AOC_JOBS`)
+    pub jobs: Option<usize>,
+    /// output format for answers (`AOC_FORMAT`)
+    pub format: Option<Format>,
+    /// preferred execution mode (`AOC_MODE`)
+    pub mode: Option<Mode>,
+    /// what solvers do when their arithmetic overflows (`AOC_OVERFLOW`)
+    pub overflow: Option<Overflow>,
+    /// what solvers that use randomness draw from (`AOC_SEED`)
+    pub seed: Option<u64>,
+    /// the most bytes a solve may reserve in all (`AOC_MEMORY_BUDGET`)
+    pub memory_budget: Option<usize>,
+    /// whether the text format is colored (`AOC_COLOR`)
+    pub color: Option<Color>,
+    /// whether answers are appended to the history file (`AOC_HISTORY`)
+    pub history: Option<bool>,
+    /// where the answer history is appended to (`AOC_HISTORY_FILE`)
+    pub history_file: Option<PathBuf>,
+    /// whether parsed inputs are saved and solved from on later runs
+    /// (`AOC_PARSE_CACHE`)
+    pub parse_cache: Option<bool>,
+    /// the sqlite database each run's timings are recorded in (`AOC_TIMINGS_FILE`)
+    pub timings_file: Option<PathBuf>,
+    /// directory plugin executables are discovered in (`AOC_PLUGINS_DIR`)
+    pub plugins_dir: Option<PathBuf>,
+    /// url a summary is posted to after a run, or when `verify` finds a
+    /// regression (`AOC_WEBHOOK`)
+    pub webhook: Option<String>,
+}
+
+impl Config {
+    /// load the config from `./aoc.toml`, falling back to
+    /// `$XDG_CONFIG_HOME/aoc2023/aoc.toml`, then apply environment overrides
+    pub fn load() -> Result<Config> {
+        let config = match locate() {
+            Some(path) => {
+                let text = fs::read_to_string(&path)?;
+                Config::parse(&text).with_context(|| format!("invalid {}", path.display()))?
+            }
+            None => Config::default(),
+        };
+        config.with_overrides(|name| env::var(name).ok())
+    }
+
+    pub fn parse(text: &str) -> Result<Config> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// replace any settings that have a value in the given variable lookup
+    fn with_overrides(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Config> {
+        if let Some(dir) = var("AOC_INPUTS_DIR") {
+            self.inputs_dir = Some(dir.into());
+        }
+        if let Some(file) = var("AOC_SESSION_FILE") {
+            self.session_file = Some(file.into());
+        }
+        if let Some(history) = var("AOC_HISTORY") {
+            let history = history
+                .parse()
+                .map_err(|_| anyhow!("AOC_HISTORY must be true or false, found {history:?}"))?;
+            self.history = Some(history);
+        }
+        if let Some(parse_cache) = var("AOC_PARSE_CACHE") {
+            let parse_cache = parse_cache.parse().map_err(|_| {
+                anyhow!("AOC_PARSE_CACHE must be true or false, found {parse_cache:?}")
+            })?;
+            self.parse_cache = Some(parse_cache);
+        }
+        if let Some(file) = var("AOC_HISTORY_FILE") {
+            self.history_file = Some(file.into());
+        }
+        if let Some(file) = var("AOC_TIMINGS_FILE") {
+            self.timings_file = Some(file.into());
+        }
+        if let Some(dir) = var("AOC_PLUGINS_DIR") {
+            self.plugins_dir = Some(dir.into());
+        }
+        if let Some(url) = var("AOC_WEBHOOK") {
+            self.webhook = Some(url);
+        }
+        if let Some(threads) = var("AOC_THREADS") {
+            let threads = threads
+                .parse()
+                .map_err(|_| anyhow!("AOC_THREADS must be a number, found {threads:?}"))?;
+            self.threads = Some(threads);
+        }
+        if let Some(jobs) = var("AOC_JOBS") {
+            let jobs = jobs
+                .parse()
+                .map_err(|_| anyhow!("AOC_JOBS must be a number, found {jobs:?}"))?;
+            self.jobs = Some(jobs);
+        }
+        if let Some(format) = var("AOC_FORMAT") {
+            self.format = Some(parse_value_enum("AOC_FORMAT", &format)?);
+        }
+        if let Some(mode) = var("AOC_MODE") {
+            self.mode = Some(parse_value_enum("AOC_MODE", &mode)?);
+        }
+        if let Some(overflow) = var("AOC_OVERFLOW") {
+            self.overflow = Some(parse_value_enum("AOC_OVERFLOW", &overflow)?);
+        }
+        if let Some(seed) = var("AOC_SEED") {
+            let seed = seed
+                .parse()
+                .map_err(|_| anyhow!("AOC_SEED must be a number, found {seed:?}"))?;
+            self.seed = Some(seed);
+        }
+        if let Some(budget) = var("AOC_MEMORY_BUDGET") {
+            let budget = budget.parse().map_err(|_| {
+                anyhow!("AOC_MEMORY_BUDGET must be a number of bytes, found {budget:?}")
+            })?;
+            self.memory_budget = Some(budget);
+        }
+        if let Some(color) = var("AOC_COLOR") {
+            self.color = Some(parse_value_enum("AOC_COLOR", &color)?);
+        }
+        Ok(self)
+    }
+
+    /// directory inputs are read from and downloaded to
+    pub fn inputs_dir(&self) -> &Path {
+        self.inputs_dir
+            .as_deref()
+            .unwrap_or_else(|| Path::new("inputs"))
+    }
+}
+
+/// where the cli keeps its data: `$XDG_DATA_HOME/aoc2023`, or
+/// `~/.local/share/aoc2023`
+pub fn data_dir() -> Result<PathBuf> {
+    let base = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => {
+            let home = env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set"))?;
+            PathBuf::from(home).join(".local").join("share")
+        }
+    };
+    Ok(base.join("aoc2023"))
+}
+
+fn parse_value_enum<T: clap::ValueEnum>(name: &str, value: &str) -> Result<T> {
+    T::from_str(value, true).map_err(|_| anyhow!("unsupported value {value:?} for {name}"))
+}
+
+fn locate() -> Option<PathBuf> {
+    let local = PathBuf::from(FILE_NAME);
+    if local.exists() {
+        return Some(local);
+    }
+
+    let base = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    let global = base.join("aoc2023").join(FILE_NAME);
+    global.exists().then_some(global)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_config_file() -> Result<()> {
+        let text = r#"
+            inputs_dir = "puzzles"
+            session_file = "/home/elf/.aoc-session"
+            threads = 4
+            jobs = 2
+            format = "json"
+            mode = "parallel"
+            overflow = "widen"
+            seed = 42
+            memory_budget = 1073741824
+            color = "never"
+            history = false
+            history_file = "history.jsonl"
+            parse_cache = true
+            timings_file = "timings.sqlite3"
+            plugins_dir = "plugins"
+            webhook = "https://hooks.slack.com/services/T0/B0/x"
+        "#;
+        let expected = Config {
+            inputs_dir: Some("puzzles".into()),
+            session_file: Some("/home/elf/.aoc-session".into()),
+            threads: Some(4),
+            jobs: Some(2),
+            format: Some(Format::Json),
+            mode: Some(Mode::Parallel),
+            overflow: Some(Overflow::Widen),
+            seed: Some(42),
+            memory_budget: Some(1 << 30),
+            color: Some(Color::Never),
+            history: Some(false),
+            history_file: Some("history.jsonl".into()),
+            parse_cache: Some(true),
+            timings_file: Some("timings.sqlite3".into()),
+            plugins_dir: Some("plugins".into()),
+            webhook: Some("https://hooks.slack.com/services/T0/B0/x".into()),
+        };
+        assert_eq!(Config::parse(text)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_unknown_keys() {
+        assert!(Config::parse("thread = 4").is_err());
+    }
+
+    #[test]
+    fn should_override_with_env_vars() -> Result<()> {
+        let config = Config::parse("threads = 4\nformat = \"json\"")?;
+        let result = config.with_overrides(|name| match name {
+            "AOC_THREADS" => Some("2".to_string()),
+            "AOC_MODE" => Some("sequential".to_string()),
+            "AOC_OVERFLOW" => Some("saturating".to_string()),
+            "AOC_SEED" => Some("7".to_string()),
+            "AOC_MEMORY_BUDGET" => Some("4096".to_string()),
+            "AOC_PARSE_CACHE" => Some("true".to_string()),
+            "AOC_WEBHOOK" => Some("https://discord.com/api/webhooks/1/x".to_string()),
+            _ => None,
+        })?;
+        assert_eq!(result.threads, Some(2));
+        assert_eq!(result.format, Some(Format::Json));
+        assert_eq!(result.mode, Some(Mode::Sequential));
+        assert_eq!(result.overflow, Some(Overflow::Saturating));
+        assert_eq!(result.seed, Some(7));
+        assert_eq!(result.memory_budget, Some(4096));
+        assert_eq!(result.parse_cache, Some(true));
+        assert_eq!(
+            result.webhook.as_deref(),
+            Some("https://discord.com/api/webhooks/1/x")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,363 @@
+use std::{ops::RangeInclusive, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use aoc_core::{
+    answer::{Answer, Answers},
+    diagnostic::Diagnostic,
+    error,
+    export::Export,
+    incremental::Incremental,
+    overflow::OverflowPolicy,
+    solver,
+    strategy::ExecutionStrategy,
+};
+use clap::ValueEnum;
+use serde::Deserialize;
+
+// a `use` of every day crate, so their registrations are linked in
+include!(concat!(env!("OUT_DIR"), "/link_days.rs"));
+
+/// Signature shared by every part solver in the day crates
+pub type PartSolver = fn(&str) -> error::Result<Answer>;
+
+/// A part solver as the cli runs it, either one of the day crates' functions
+/// or a closure that hands the input to a plugin. It's given the input's
+/// bytes so a mapped input reaches the day crates without being checked to
+/// be UTF-8 first. Those that need text check it themselves.
+pub type Solver = Arc<dyn Fn(&[u8]) -> Result<Answer> + Send + Sync>;
+
+/// Solves both parts from a single parse of the input, timing each step
+pub type Both = Arc<dyn Fn(&[u8]) -> Result<Answers> + Send + Sync>;
+
+/// The pair of solvers for a single day's puzzle
+pub struct Day {
+    pub part_one: Solver,
+    pub part_two: Solver,
+    /// both parts at once, for a day solved by its registered solver. Part
+    /// solvers that were swapped in for another algorithm, a plugin or the
+    /// parse cache each parse the input themselves, so they leave this out.
+    pub both: Option<Both>,
+}
+
+/// a day crate's part solver, with its errors saying which day and part failed
+fn part(day: usize, part: u8, solver: PartSolver) -> Solver {
+    Arc::new(move |input: &[u8]| {
+        let text = std::str::from_utf8(input)?;
+        Ok(solver(text).map_err(|e| e.with_day(day).with_part(part))?)
+    })
+}
+
+/// Whether to solve on the calling thread or spread the work over rayon
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    #[default]
+    Sequential,
+    Parallel,
+}
+
+impl From<Mode> for ExecutionStrategy {
+    /// parallel solvers run on whichever pool is current, which `--threads`
+    /// and `jobs` have already sized
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Sequential => ExecutionStrategy::Sequential,
+            Mode::Parallel => ExecutionStrategy::Rayon { threads: None },
+        }
+    }
+}
+
+/// What the solvers do when a sum or product outgrows a `u64`
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Overflow {
+    /// fail the day with an error
+    #[default]
+    Checked,
+    /// clamp the answer at `u64::MAX`
+    Saturating,
+    /// carry on in `u128`
+    Widen,
+}
+
+impl From<Overflow> for OverflowPolicy {
+    fn from(overflow: Overflow) -> Self {
+        match overflow {
+            Overflow::Checked => OverflowPolicy::Checked,
+            Overflow::Saturating => OverflowPolicy::Saturating,
+            Overflow::Widen => OverflowPolicy::Widen,
+        }
+    }
+}
+
+/// look up the solvers for a given day, returning `None` if the day has not
+/// been implemented
+pub fn lookup(day: usize, mode: Mode) -> Option<Day> {
+    let solver = solver::find(day)?.solver;
+    let strategy = ExecutionStrategy::from(mode);
+    Some(Day {
+        part_one: Arc::new(move |input: &[u8]| Ok(solver.solve_part_one_bytes(input, strategy)?)),
+        part_two: Arc::new(move |input: &[u8]| Ok(solver.solve_part_two_bytes(input, strategy)?)),
+        both: Some(Arc::new(move |input: &[u8]| {
+            Ok(solver.solve_bytes(input, strategy)?)
+        })),
+    })
+}
+
+/// the example inputs from the puzzle descriptions for part one and part two
+pub fn examples(day: usize) -> Option<[&'static str; 2]> {
+    solver::find(day).map(|registration| registration.examples)
+}
+
+/// Checks a puzzle input without solving it
+pub type Validator = fn(&str) -> Vec<Diagnostic>;
+
+/// the input validator for a given day, if it has been implemented
+pub fn validator(day: usize) -> Option<Validator> {
+    solver::find(day).map(|registration| registration.validate)
+}
+
+/// how to describe what a given day's input parses into, if it can be
+pub fn exporter(day: usize) -> Option<&'static dyn Export> {
+    solver::find(day).and_then(|registration| registration.export)
+}
+
+/// Starts a solve that keeps its answers up to date as the input is edited
+pub type Start = fn() -> Box<dyn Incremental>;
+
+/// how to solve a given day incrementally, if it can be
+pub fn incremental(day: usize) -> Option<Start> {
+    solver::find(day).and_then(|registration| registration.incremental)
+}
+
+/// the names of the implementations each day registers, selectable with
+/// `--algo`. The first listed is the one `lookup` returns.
+pub fn algorithms(day: usize) -> Vec<&'static str> {
+    solver::find(day).map_or_else(Vec::new, |registration| {
+        registration
+            .algorithms
+            .iter()
+            .map(|algorithm| algorithm.name)
+            .collect()
+    })
+}
+
+/// swap in the named variant of a day's solvers, failing if the day doesn't
+/// register an algorithm with that name
+pub fn with_algo(day: usize, solvers: Day, algo: &str) -> Result<Day> {
+    let Some(algorithm) = solver::find(day).and_then(|registration| registration.algorithm(algo))
+    else {
+        return match algorithms(day)[..] {
+            [] => Err(anyhow!("day {day} has only one algorithm")),
+            ref names => Err(anyhow!(
+                "unknown algorithm {algo:?} for day {day}, expected one of: {}",
+                names.join(", ")
+            )),
+        };
+    };
+    Ok(match algorithm.parts {
+        [None, None] => solvers,
+        [part_one, part_two] => Day {
+            part_one: part_one.map_or(solvers.part_one, |solve| part(day, 1, solve)),
+            part_two: part_two.map_or(solvers.part_two, |solve| part(day, 2, solve)),
+            both: None,
+        },
+    })
+}
+
+/// The days picked with `--day`, in the order they should be run
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selection(pub Vec<usize>);
+
+/// the days of December with a puzzle
+const DAYS: RangeInclusive<usize> = 1..=25;
+
+/// parse a selection of days such as `3` or `1-4,7` into the list of days
+/// it covers, in the order given. Days outside 1-25 are rejected here, so a
+/// range like `1-100000` never gets expanded.
+pub fn parse_selection(text: &str) -> Result<Selection> {
+    let mut days = vec![];
+    for item in text.split(',') {
+        let item = item.trim();
+        match item.split_once('-') {
+            Some((first, last)) => {
+                let first = parse_day(first, item)?;
+                let last = parse_day(last, item)?;
+                if first > last {
+                    return Err(anyhow!("invalid day range {item}, start is after end"));
+                }
+                days.extend(first..=last);
+            }
+            None => days.push(parse_day(item, item)?),
+        }
+    }
+    Ok(Selection(days))
+}
+
+/// a day from the `item` of a selection it's part of, which errors name
+fn parse_day(text: &str, item: &str) -> Result<usize> {
+    let day: usize = text
+        .trim()
+        .parse()
+        .map_err(|e| anyhow!("invalid day {item:?}: {e}"))?;
+    match DAYS.contains(&day) {
+        true => Ok(day),
+        false => Err(anyhow!(
+            "invalid day {item:?}, days run from {} to {}",
+            DAYS.start(),
+            DAYS.end()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::context::{self, SolveContext};
+
+    /// Every registered day, in both modes and with each algorithm, against
+    /// the answers its registration expects for the examples. Failures are
+    /// collected so one run reports every day that's off.
+    #[test]
+    fn should_solve_every_example() -> Result<()> {
+        let mut failures = vec![];
+        let mut checked = 0;
+        for registration in solver::registered() {
+            let day = registration.solver.day();
+            let expected = (registration.expected)();
+            let mut variants = vec![];
+            for mode in [Mode::Sequential, Mode::Parallel] {
+                let solvers = lookup(day, mode).ok_or(anyhow!("day {day} missing"))?;
+                variants.push((format!("{mode:?}"), solvers));
+            }
+            for algo in algorithms(day) {
+                let solvers = lookup(day, Mode::Sequential).ok_or(anyhow!("day {day} missing"))?;
+                variants.push((algo.to_string(), with_algo(day, solvers, algo)?));
+            }
+
+            for (variant, solvers) in variants {
+                let parts = [&solvers.part_one, &solvers.part_two];
+                for (i, solve) in parts.into_iter().enumerate() {
+                    let Some(expected) = &expected[i] else {
+                        continue;
+                    };
+                    checked += 1;
+                    match solve(registration.examples[i].as_bytes()) {
+                        Ok(answer) if answer == *expected => {}
+                        Ok(answer) => failures.push(format!(
+                            "day {day} part {} ({variant}): expected {expected}, got {answer}",
+                            i + 1
+                        )),
+                        Err(e) => failures.push(format!("{e} ({variant})")),
+                    }
+                }
+            }
+        }
+        assert!(checked > 0, "no examples were checked");
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+        Ok(())
+    }
+
+    /// Every registered day, with each algorithm, reserves what it parses
+    /// its example into against the memory budget
+    #[test]
+    fn should_hold_every_day_to_the_memory_budget() -> Result<()> {
+        let budgeted = SolveContext::default().with_memory_budget(1);
+        for registration in solver::registered() {
+            let day = registration.solver.day();
+            let mut variants =
+                vec![lookup(day, Mode::Sequential).ok_or(anyhow!("day {day} missing"))?];
+            for algo in algorithms(day) {
+                let solvers = lookup(day, Mode::Sequential).ok_or(anyhow!("day {day} missing"))?;
+                variants.push(with_algo(day, solvers, algo)?);
+            }
+            for solvers in variants {
+                let error = context::scoped(&budgeted, || {
+                    (solvers.part_one)(registration.examples[0].as_bytes())
+                })
+                .expect_err("a byte is too small a budget for any day");
+                let over = crate::exit::aoc_error(&error).and_then(|e| e.memory);
+                assert!(over.is_some(), "day {day}: {error}");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_select_algorithm_variants() -> Result<()> {
+        assert_eq!(algorithms(2), ["vec", "arena"]);
+        assert_eq!(algorithms(4), ["hashmap", "vec"]);
+        assert!(algorithms(1).is_empty());
+        for algo in algorithms(4) {
+            let solvers = lookup(4, Mode::Sequential).ok_or(anyhow!("day 4 missing"))?;
+            assert!(with_algo(4, solvers, algo).is_ok());
+        }
+        let solvers = lookup(4, Mode::Sequential).ok_or(anyhow!("day 4 missing"))?;
+        assert!(with_algo(4, solvers, "bitset").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn should_export_parsed_inputs() -> Result<()> {
+        let export = |day: usize| -> Result<serde_json::Value> {
+            let exporter = exporter(day).ok_or(anyhow!("day {day} can't export"))?;
+            let examples = examples(day).ok_or(anyhow!("day {day} missing"))?;
+            Ok(exporter.export(examples[0], ExecutionStrategy::Sequential)?)
+        };
+        let games = export(2)?;
+        assert_eq!(games[0]["id"], 1);
+        assert_eq!(
+            games[0]["rounds"][0],
+            serde_json::json!([{ "count": 3, "color": "blue" }, { "count": 4, "color": "red" }])
+        );
+        let schematic = export(3)?;
+        assert_eq!(schematic["part_numbers"][0]["number"], 467);
+        assert_eq!(
+            schematic["symbols"][0],
+            serde_json::json!({ "row": 1, "column": 3, "symbol": "*" })
+        );
+        assert_eq!(
+            export(4)?[0],
+            serde_json::json!({ "number": 1, "matches": 4 })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_single_day() -> Result<()> {
+        assert_eq!(parse_selection("3")?.0, vec![3]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_lists_and_ranges() -> Result<()> {
+        assert_eq!(parse_selection("1-4,7")?.0, vec![1, 2, 3, 4, 7]);
+        assert_eq!(parse_selection("9, 2-3")?.0, vec![9, 2, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_malformed_selections() {
+        assert!(parse_selection("4-1").is_err());
+        assert!(parse_selection("1,,2").is_err());
+        assert!(parse_selection("one").is_err());
+    }
+
+    #[test]
+    fn should_reject_days_outside_december() -> Result<()> {
+        for (text, item) in [
+            ("1-18446744073709551615", "1-18446744073709551615"),
+            ("0", "0"),
+            ("3,26-30", "26-30"),
+        ] {
+            let error = parse_selection(text).unwrap_err().to_string();
+            assert_eq!(
+                error,
+                format!("invalid day {item:?}, days run from 1 to 25"),
+                "{text}"
+            );
+        }
+        assert_eq!(parse_selection("25")?.0, vec![25]);
+        Ok(())
+    }
+}
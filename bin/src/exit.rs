@@ -0,0 +1,107 @@
+use std::{error::Error, fmt};
+
+use aoc_core::error::AocError;
+
+/// Why solving a day failed, which decides the exit code of the process.
+/// Any other error exits with 1, and clap exits with 2 on usage errors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Failure {
+    /// there is no solver for the day
+    NoSolver,
+    /// the input isn't in the format the day expects
+    Parse,
+    /// the input looked fine but solving it failed
+    Solve,
+    /// solving needed more memory than `--memory-budget` allowed
+    Memory,
+}
+
+impl Failure {
+    pub fn code(self) -> u8 {
+        match self {
+            Failure::NoSolver => 3,
+            Failure::Parse => 4,
+            Failure::Solve => 5,
+            Failure::Memory => 6,
+        }
+    }
+
+    /// tag `error` as this kind of failure
+    pub fn tag(self, error: anyhow::Error) -> anyhow::Error {
+        anyhow::Error::new(Failed { kind: self, error })
+    }
+}
+
+/// An error tagged with the kind of failure it represents by [`Failure::tag`].
+/// It displays as the underlying error so tagging doesn't change any messages.
+#[derive(Debug)]
+pub struct Failed {
+    pub kind: Failure,
+    error: anyhow::Error,
+}
+
+impl fmt::Display for Failed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl Error for Failed {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// the kind of failure an error was tagged with, if any
+pub fn failure(error: &anyhow::Error) -> Option<Failure> {
+    error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<Failed>())
+        .map(|failed| failed.kind)
+}
+
+/// the day crate error behind `error`, if it came from a day crate
+pub fn aoc_error(error: &anyhow::Error) -> Option<&AocError> {
+    error
+        .chain()
+        .find_map(|cause| match cause.downcast_ref::<Failed>() {
+            Some(failed) => failed.error.downcast_ref::<AocError>(),
+            None => cause.downcast_ref::<AocError>(),
+        })
+}
+
+/// the exit code for a run that ended with `error`
+pub fn code(error: &anyhow::Error) -> u8 {
+    failure(error).map_or(1, Failure::code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Context};
+
+    #[test]
+    fn should_pick_exit_code_from_tagged_errors() {
+        let parse = Failure::Parse.tag(anyhow!("bad card"));
+        assert_eq!(parse.to_string(), "bad card");
+        assert_eq!(code(&parse), 4);
+
+        let wrapped = Err::<(), _>(Failure::NoSolver.tag(anyhow!("no day 9")))
+            .context("while solving")
+            .unwrap_err();
+        assert_eq!(code(&wrapped), 3);
+
+        let memory = AocError::memory_budget_exceeded("schematic grid", 2048, 1024);
+        assert_eq!(code(&Failure::Memory.tag(memory.into())), 6);
+
+        assert_eq!(code(&anyhow!("unable to read input")), 1);
+    }
+
+    #[test]
+    fn should_find_day_crate_errors() {
+        let error = AocError::new("malformatted card id").at_line(2);
+        let tagged = Failure::Parse.tag(error.clone().into());
+        assert_eq!(aoc_error(&tagged), Some(&error));
+        assert_eq!(aoc_error(&anyhow!("unable to read input")), None);
+    }
+}
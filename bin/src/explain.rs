@@ -0,0 +1,44 @@
+use std::{
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use aoc_core::explain::{self, Explain};
+
+use crate::progress;
+
+/// set by `--explain`, and off otherwise so solvers don't format steps nobody
+/// will read
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Writes each step a solver explains to stderr as it happens, indented under
+/// the part it belongs to, leaving stdout to the answers
+struct Printer;
+
+impl Explain for Printer {
+    fn step(&self, step: String) {
+        let _ = writeln!(io::stderr().lock(), "  {step}");
+    }
+}
+
+/// print the steps solvers explain for the rest of the run. Progress bars
+/// are turned off since they'd draw over the steps.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+    progress::disable();
+}
+
+/// run a solver, printing the steps it explains under `label` when explaining
+/// is enabled
+pub fn with_heading<T>(label: String, f: impl FnOnce() -> T) -> T {
+    match ENABLED.load(Ordering::Relaxed) {
+        true => {
+            eprintln!("{label}");
+            explain::scoped(Arc::new(Printer), f)
+        }
+        false => f(),
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use aoc_core::solver::{self, Registration};
+use async_graphql::{Context, EmptySubscription, Object, Schema, SimpleObject};
+
+use crate::{output::Run, serve::Solving};
+
+/// The schema `serve` answers `/graphql` requests with
+pub type AocSchema = Schema<Query, Mutation, EmptySubscription>;
+
+/// a schema solving and remembering runs with `solving`
+pub fn schema(solving: Arc<Solving>) -> AocSchema {
+    Schema::build(Query, Mutation, EmptySubscription)
+        .data(solving)
+        .finish()
+}
+
+/// A day with a solver, and what it made of the last input it was given
+#[derive(SimpleObject)]
+pub struct Day {
+    day: usize,
+    title: String,
+    /// the techniques the solver uses, like `grid` or `graph`
+    tags: Vec<String>,
+    /// the answers to the last input solved for the day, if there's been one
+    run: Option<Answers>,
+}
+
+impl Day {
+    fn new(registration: &Registration, solving: &Solving) -> Day {
+        let metadata = registration.solver.metadata();
+        Day {
+            day: metadata.day,
+            title: metadata.title.to_string(),
+            tags: metadata.tags.iter().map(|tag| tag.to_string()).collect(),
+            run: solving.latest(metadata.day).map(Answers::from),
+        }
+    }
+}
+
+/// Both parts' answers to an input, and how long each took. Answers are
+/// strings, since some are too big for GraphQL's numbers and some are text.
+#[derive(SimpleObject)]
+pub struct Answers {
+    part1: String,
+    part2: String,
+    timings: Timings,
+    /// sha256 of the input, as hex
+    input_sha256: String,
+}
+
+/// How long each step of a solve took, in nanoseconds
+#[derive(SimpleObject)]
+pub struct Timings {
+    input_ns: u64,
+    /// only measured when both parts were solved from one parse
+    parse_ns: Option<u64>,
+    part1_ns: u64,
+    part2_ns: u64,
+}
+
+impl From<Run> for Answers {
+    fn from(run: Run) -> Answers {
+        Answers {
+            part1: run.part1.to_string(),
+            part2: run.part2.to_string(),
+            timings: Timings {
+                input_ns: run.timings.input.as_nanos() as u64,
+                parse_ns: run.timings.parse.map(|parse| parse.as_nanos() as u64),
+                part1_ns: run.timings.part1.as_nanos() as u64,
+                part2_ns: run.timings.part2.as_nanos() as u64,
+            },
+            input_sha256: run.input_hash,
+        }
+    }
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// every day with a solver, in order
+    async fn days(&self, context: &Context<'_>) -> async_graphql::Result<Vec<Day>> {
+        let solving = context.data::<Arc<Solving>>()?;
+        Ok(solver::registered()
+            .into_iter()
+            .map(|registration| Day::new(registration, solving))
+            .collect())
+    }
+
+    /// one day, or null if it has no solver
+    async fn day(&self, context: &Context<'_>, day: usize) -> async_graphql::Result<Option<Day>> {
+        let solving = context.data::<Arc<Solving>>()?;
+        Ok(solver::find(day).map(|registration| Day::new(registration, solving)))
+    }
+}
+
+pub struct Mutation;
+
+#[Object]
+impl Mutation {
+    /// solve `input` as the day's puzzle input. The answers become the day's
+    /// `run`.
+    async fn submit_input(
+        &self,
+        context: &Context<'_>,
+        day: usize,
+        input: String,
+    ) -> async_graphql::Result<Answers> {
+        let solving = context.data::<Arc<Solving>>()?;
+        match solving.solve(day, &input) {
+            Ok(run) => Ok(run.into()),
+            Err(reply) => Err(async_graphql::Error::new(reply.message())),
+        }
+    }
+}
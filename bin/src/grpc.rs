@@ -0,0 +1,324 @@
+//! The solvers as a gRPC service, described by `proto/aoc.proto`, for internal
+//! tooling that wants generated clients and progress as a solve runs rather
+//! than the HTTP API's single response.
+
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use aoc_core::{
+    answer::Answer,
+    context::{self, CancellationToken, SolveContext},
+    progress::Progress,
+    timing::timed,
+};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{transport::Server, Request, Response, Status};
+
+use crate::{days::Mode, days::Solver, exit, lookup};
+
+mod proto {
+    tonic::include_proto!("aoc");
+}
+
+use proto::{
+    solve_update::Update,
+    solver_server::{self, SolverServer},
+    SolveRequest, SolveUpdate,
+};
+
+/// updates waiting for a slow client before progress starts being dropped.
+/// The answer is never dropped.
+const BUFFERED_UPDATES: usize = 64;
+
+type Updates = mpsc::Sender<Result<SolveUpdate, Status>>;
+
+/// The phase a solver is working through
+#[derive(Default)]
+struct Phase {
+    name: String,
+    done: u64,
+    total: u64,
+}
+
+impl Phase {
+    fn percent(&self) -> u64 {
+        self.done * 100 / self.total.max(1)
+    }
+}
+
+/// Sends a solver's progress to the client that asked for the solve, at most
+/// once per percent so busy solvers don't flood it, and cancels the solve if
+/// the client goes away
+struct Forward {
+    updates: Updates,
+    phase: Mutex<Phase>,
+    cancellation: CancellationToken,
+}
+
+impl Forward {
+    fn send(&self, phase: &Phase) {
+        let update = Update::Progress(proto::Progress {
+            phase: phase.name.clone(),
+            done: phase.done,
+            total: phase.total,
+        });
+        match self.updates.try_send(Ok(SolveUpdate {
+            update: Some(update),
+        })) {
+            // a later update says everything this one would have
+            Ok(()) | Err(TrySendError::Full(_)) => {}
+            Err(TrySendError::Closed(_)) => self.cancellation.cancel(),
+        }
+    }
+
+    fn phase(&self) -> std::sync::MutexGuard<'_, Phase> {
+        self.phase
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Progress for Forward {
+    fn start(&self, phase: &str, total: u64) {
+        let mut current = self.phase();
+        *current = Phase {
+            name: phase.to_string(),
+            done: 0,
+            total,
+        };
+        self.send(&current);
+    }
+
+    fn advance(&self, delta: u64) {
+        let mut current = self.phase();
+        let before = current.percent();
+        current.done += delta;
+        if current.percent() != before {
+            self.send(&current);
+        }
+    }
+}
+
+/// How solves are run, shared by every request
+struct Solving {
+    mode: Mode,
+    timeout: Option<Duration>,
+    context: SolveContext,
+}
+
+impl Solving {
+    /// run `solver` on `input`, giving up once it has taken longer than the
+    /// timeout, and send the client the answer or why there isn't one
+    fn solve(&self, day: usize, solver: Solver, input: &str, forward: Arc<Forward>) {
+        let updates = forward.updates.clone();
+        let mut solve_context = SolveContext {
+            strategy: self.mode.into(),
+            ..self.context.clone()
+        }
+        .with_cancellation(forward.cancellation.clone())
+        .with_progress(forward);
+        if let Some(timeout) = self.timeout {
+            solve_context = solve_context.with_deadline(Instant::now() + timeout);
+        }
+        let (answer, duration) =
+            timed(|| context::scoped(&solve_context, || solver(input.as_bytes())));
+        let solved = match answer {
+            Ok(answer) => Ok(answered(&answer, duration)),
+            Err(_) if solve_context.check().is_err() => Err(Status::deadline_exceeded(format!(
+                "solving day {day} took longer than {:?}",
+                self.timeout
+            ))),
+            Err(e) if exit::aoc_error(&e).is_some_and(|e| e.memory.is_some()) => {
+                Err(Status::resource_exhausted(e.to_string()))
+            }
+            Err(e) => Err(Status::invalid_argument(e.to_string())),
+        };
+        // the client has gone if this fails, and there's nobody to tell
+        let _ = updates.blocking_send(solved);
+    }
+}
+
+fn answered(answer: &Answer, duration: Duration) -> SolveUpdate {
+    SolveUpdate {
+        update: Some(Update::Answer(proto::Answer {
+            answer: answer.to_string(),
+            duration_ns: duration.as_nanos() as u64,
+        })),
+    }
+}
+
+/// The `aoc.Solver` service
+pub struct Grpc {
+    solving: Arc<Solving>,
+    algo: Option<String>,
+}
+
+impl Grpc {
+    /// solve with `mode` and `algo` under `context`, giving up on a solve
+    /// once it has taken longer than `timeout` or more than the context's
+    /// memory budget
+    pub fn new(
+        mode: Mode,
+        algo: Option<&str>,
+        timeout: Option<Duration>,
+        context: SolveContext,
+    ) -> Grpc {
+        Grpc {
+            solving: Arc::new(Solving {
+                mode,
+                timeout,
+                context,
+            }),
+            algo: algo.map(str::to_string),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl solver_server::Solver for Grpc {
+    type SolveStream = ReceiverStream<Result<SolveUpdate, Status>>;
+
+    async fn solve(
+        &self,
+        request: Request<SolveRequest>,
+    ) -> Result<Response<Self::SolveStream>, Status> {
+        let SolveRequest { day, part, input } = request.into_inner();
+        let day = day as usize;
+        let solvers = lookup(day, self.solving.mode, self.algo.as_deref())
+            .map_err(|e| Status::not_found(e.to_string()))?;
+        let solver = match part {
+            1 => solvers.part_one,
+            2 => solvers.part_two,
+            _ => {
+                return Err(Status::invalid_argument(format!(
+                    "part must be 1 or 2, not {part}"
+                )))
+            }
+        };
+
+        let (updates, receiver) = mpsc::channel(BUFFERED_UPDATES);
+        let forward = Arc::new(Forward {
+            updates: updates.clone(),
+            phase: Mutex::default(),
+            cancellation: CancellationToken::new(),
+        });
+        let solving = self.solving.clone();
+        tokio::spawn(async move {
+            let solve = move || solving.solve(day, solver, &input, forward);
+            if tokio::task::spawn_blocking(solve).await.is_err() {
+                let panicked = Status::internal(format!("solver for day {day} panicked"));
+                let _ = updates.send(Err(panicked)).await;
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+/// serve the `aoc.Solver` gRPC service on `address` until the process is
+/// stopped. Requests are solved concurrently, each on a blocking thread of
+/// its own.
+pub fn run(
+    address: &str,
+    mode: Mode,
+    algo: Option<&str>,
+    timeout: Option<Duration>,
+    context: SolveContext,
+) -> Result<()> {
+    let address: SocketAddr = address
+        .parse()
+        .map_err(|e| anyhow!("invalid address {address}: {e}"))?;
+    let service = SolverServer::new(Grpc::new(mode, algo, timeout, context));
+    tokio::runtime::Runtime::new()?.block_on(async {
+        println!("serving gRPC on {address}");
+        Server::builder().add_service(service).serve(address).await
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{solver_server::Solver as _, *};
+    use tonic::Code;
+
+    const EXAMPLE: &str = include_str!("../../day4/src/part1_example.txt");
+
+    /// every update `Solve` streams for a request, or the status it was
+    /// refused with
+    fn solve(day: u32, part: u32, input: &str) -> Result<Vec<Result<Update, Code>>, Code> {
+        let runtime = tokio::runtime::Runtime::new().expect("a runtime starts");
+        runtime.block_on(async {
+            let request = Request::new(SolveRequest {
+                day,
+                part,
+                input: input.to_string(),
+            });
+            let grpc = Grpc::new(Mode::Sequential, None, None, SolveContext::default());
+            let mut stream = grpc
+                .solve(request)
+                .await
+                .map_err(|status| status.code())?
+                .into_inner()
+                .into_inner();
+            let mut updates = vec![];
+            while let Some(update) = stream.recv().await {
+                updates.push(
+                    update
+                        .map(|update| update.update.expect("updates say what they are"))
+                        .map_err(|status| status.code()),
+                );
+            }
+            Ok(updates)
+        })
+    }
+
+    #[test]
+    fn should_stream_the_answer() {
+        let updates = solve(4, 2, EXAMPLE).expect("day 4 is solved");
+        let Some(Ok(Update::Answer(answer))) = updates.last() else {
+            panic!("expected an answer last, got {updates:?}");
+        };
+        assert_eq!(answer.answer, "30");
+    }
+
+    #[test]
+    fn should_forward_progress_once_per_percent() {
+        let (updates, mut receiver) = mpsc::channel(1000);
+        let forward = Forward {
+            updates,
+            phase: Mutex::default(),
+            cancellation: CancellationToken::new(),
+        };
+        forward.start("cards", 4000);
+        for _ in 0..4000 {
+            forward.advance(1);
+        }
+
+        let mut sent = vec![];
+        while let Ok(Ok(update)) = receiver.try_recv() {
+            if let Some(Update::Progress(progress)) = update.update {
+                sent.push(progress.done);
+            }
+        }
+        assert_eq!(sent.len(), 101);
+        assert_eq!(sent[..3], [0, 40, 80]);
+        assert_eq!(sent.last(), Some(&4000));
+
+        // nobody is listening any more, so the solve should stop
+        drop(receiver);
+        forward.start("cards", 1);
+        assert!(forward.cancellation.is_cancelled());
+    }
+
+    #[test]
+    fn should_report_errors_as_statuses() {
+        assert_eq!(solve(99, 1, EXAMPLE), Err(Code::NotFound));
+        assert_eq!(solve(4, 3, EXAMPLE), Err(Code::InvalidArgument));
+        let failed = solve(4, 1, "not a card").expect("day 4 has a solver");
+        assert_eq!(failed.last(), Some(&Err(Code::InvalidArgument)));
+    }
+}
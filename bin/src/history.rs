@@ -0,0 +1,137 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use aoc_core::answer::Answer;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    config::{self, Config},
+    output::Run,
+};
+
+/// the git revision the cli was built from, when built from a checkout
+pub const REVISION: Option<&str> = option_env!("AOC_GIT_REVISION");
+
+/// A single answer as recorded in the history file
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Entry<'a> {
+    /// seconds since the unix epoch
+    pub timestamp: u64,
+    pub day: usize,
+    pub part: u8,
+    pub answer: &'a Answer,
+    pub input_sha256: &'a str,
+    pub duration_ns: u64,
+    pub revision: Option<&'a str>,
+}
+
+/// hex encoded sha256 of a puzzle input
+pub fn hash(input: impl AsRef<[u8]>) -> String {
+    Sha256::digest(input)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// the history entries for both parts of a run
+pub fn entries(run: &Run, timestamp: u64) -> [Entry<'_>; 2] {
+    [
+        (1, &run.part1, run.timings.part1),
+        (2, &run.part2, run.timings.part2),
+    ]
+    .map(|(part, answer, duration)| Entry {
+        timestamp,
+        day: run.day,
+        part,
+        answer,
+        input_sha256: &run.input_hash,
+        duration_ns: duration.as_nanos() as u64,
+        revision: REVISION,
+    })
+}
+
+/// the configured history file, defaulting to
+/// `$XDG_DATA_HOME/aoc2023/history.jsonl` (or `~/.local/share/aoc2023/history.jsonl`)
+pub fn path(config: &Config) -> Result<PathBuf> {
+    if let Some(path) = &config.history_file {
+        return Ok(path.clone());
+    }
+    Ok(config::data_dir()
+        .context("unable to locate history file")?
+        .join("history.jsonl"))
+}
+
+/// append a line per part of each run to the history file
+pub fn record(runs: &[Run], config: &Config) -> Result<()> {
+    let path = path(config)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    let mut lines = String::new();
+    for run in runs {
+        for entry in entries(run, timestamp) {
+            lines.push_str(&serde_json::to_string(&entry)?);
+            lines.push('\n');
+        }
+    }
+    // a single write keeps concurrent runs from interleaving partial lines
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(lines.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Timings;
+    use std::time::Duration;
+
+    #[test]
+    fn should_hash_inputs() {
+        assert_eq!(
+            hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn should_record_one_entry_per_part() -> Result<()> {
+        let run = Run {
+            day: 4,
+            part1: Answer::U64(13),
+            part2: Answer::U64(30),
+            timings: Timings {
+                input: Duration::ZERO,
+                parse: None,
+                part1: Duration::from_nanos(5),
+                part2: Duration::from_nanos(7),
+            },
+            memory: None,
+            input_hash: hash("abc"),
+        };
+        let [one, two] = entries(&run, 1_701_406_800);
+        assert_eq!(
+            (one.part, one.answer, one.duration_ns),
+            (1, &Answer::U64(13), 5)
+        );
+        assert_eq!(
+            (two.part, two.answer, two.duration_ns),
+            (2, &Answer::U64(30), 7)
+        );
+
+        let line = serde_json::to_string(&one)?;
+        assert!(line.starts_with(r#"{"timestamp":1701406800,"day":4,"part":1,"answer":13,"#));
+        Ok(())
+    }
+}
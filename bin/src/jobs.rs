@@ -0,0 +1,92 @@
+use std::{
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use anyhow::Result;
+use aoc_core::context;
+
+/// the number of threads to share between jobs when `--threads` isn't given
+pub fn available_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Run `solve` for each day with up to `jobs` days in flight at once,
+/// returning the results in the order of `days`. Each job gets its own rayon
+/// pool with an even share of `threads`, so parallel solvers running side by
+/// side don't oversubscribe the machine.
+pub fn run<T: Send>(
+    days: &[usize],
+    jobs: usize,
+    threads: usize,
+    solve: impl Fn(usize) -> T + Sync,
+) -> Result<Vec<T>> {
+    let jobs = jobs.clamp(1, days.len().max(1));
+    let budget = (threads / jobs).max(1);
+    let next = AtomicUsize::new(0);
+    // the workers follow whatever solve context the caller installed
+    let solve = context::carry(&solve);
+
+    let mut done = thread::scope(|scope| -> Result<Vec<(usize, T)>> {
+        let workers: Vec<_> = (0..jobs)
+            .map(|_| {
+                scope.spawn(|| -> Result<Vec<(usize, T)>> {
+                    let pool = rayon::ThreadPoolBuilder::new()
+                        .num_threads(budget)
+                        .build()?;
+                    let mut done = vec![];
+                    // take the next day nobody has started until there are none left
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(&day) = days.get(index) else {
+                            return Ok(done);
+                        };
+                        done.push((index, pool.install(|| solve(day))));
+                    }
+                })
+            })
+            .collect();
+
+        let mut done = vec![];
+        for worker in workers {
+            // a panicking solver should look the same as it does when run sequentially
+            done.extend(worker.join().unwrap_or_else(|e| panic::resume_unwind(e))?);
+        }
+        Ok(done)
+    })?;
+
+    done.sort_by_key(|(index, _)| *index);
+    Ok(done.into_iter().map(|(_, result)| result).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_keep_results_in_day_order() -> Result<()> {
+        let days = [3, 1, 4, 2];
+        let results = run(&days, 3, 6, |day| (day, rayon::current_num_threads()))?;
+        assert_eq!(results, vec![(3, 2), (1, 2), (4, 2), (2, 2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_give_each_job_at_least_one_thread() -> Result<()> {
+        let results = run(&[1, 2], 8, 1, |_| rayon::current_num_threads())?;
+        assert_eq!(results, vec![1, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_solve_under_the_callers_context() -> Result<()> {
+        let cancelled = context::SolveContext::default();
+        cancelled.cancellation.cancel();
+        let results = context::scoped(&cancelled, || {
+            run(&[1, 2], 2, 2, |_| context::checkpoint().is_err())
+        })?;
+        assert_eq!(results, vec![true, true]);
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::calendar;
+
+/// A private leaderboard as served by
+/// `https://adventofcode.com/2023/leaderboard/private/view/<id>.json`
+#[derive(Deserialize, Debug)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+/// A member of a private leaderboard
+#[derive(Deserialize, Debug, Clone)]
+pub struct Member {
+    pub id: u64,
+    /// anonymous users have no name
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u32,
+    /// seconds since the epoch, or 0 when the member has no stars yet
+    pub last_star_ts: u64,
+}
+
+impl Member {
+    fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Leaderboard> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// the members ranked by score, then by who got their last star first
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+}
+
+/// a table of the members in rank order. Times are in AoC's release
+/// timezone, like the puzzle unlocks.
+pub fn render(board: &Leaderboard) -> String {
+    let members = board.ranked();
+    let width = members
+        .iter()
+        .map(|member| member.display_name().chars().count())
+        .chain(["name".len()])
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![format!(
+        "{:>4}  {:<width$}  {:>5}  {:>5}  last star (EST)",
+        "rank", "name", "score", "stars"
+    )];
+    for (rank, member) in members.iter().enumerate() {
+        let last_star = match member.last_star_ts {
+            0 => "-".to_string(),
+            ts => calendar::release_time(UNIX_EPOCH + Duration::from_secs(ts)),
+        };
+        lines.push(format!(
+            "{:>4}  {:<width$}  {:>5}  {:>5}  {last_star}",
+            rank + 1,
+            member.display_name(),
+            member.local_score,
+            member.stars
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {"id": 1, "name": "elf", "local_score": 20, "stars": 4, "global_score": 0,
+                  "last_star_ts": 1701406807, "completion_day_level": {}},
+            "2": {"id": 2, "name": null, "local_score": 20, "stars": 4, "global_score": 0,
+                  "last_star_ts": 1701406800, "completion_day_level": {}},
+            "3": {"id": 3, "name": "santa", "local_score": 0, "stars": 0, "global_score": 0,
+                  "last_star_ts": 0, "completion_day_level": {}}
+        }
+    }"#;
+
+    #[test]
+    fn should_rank_by_score_then_last_star() -> Result<()> {
+        let board = Leaderboard::parse(JSON)?;
+        let ids: Vec<u64> = board.ranked().iter().map(|member| member.id).collect();
+        assert_eq!(ids, vec![2, 1, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_table() -> Result<()> {
+        let table = render(&Leaderboard::parse(JSON)?);
+        let expected = "\
+rank  name                 score  stars  last star (EST)
+   1  (anonymous user #2)     20      4  2023-12-01 00:00:00
+   2  elf                     20      4  2023-12-01 00:00:07
+   3  santa                    0      0  -";
+        assert_eq!(table, expected);
+        Ok(())
+    }
+}
@@ -1,38 +1,1863 @@
-use std::fs;
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    thread,
+    time::{Duration, SystemTime},
+};
 
-use anyhow::{anyhow, Result};
-use clap::Parser;
+use anyhow::{anyhow, Context, Result};
+use aoc_core::{
+    context::{self, SolveContext},
+    timing::timed,
+};
+use clap::{ArgAction, CommandFactory, Parser, Subcommand};
+use tracing::{info, info_span, warn};
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+
+mod animate;
+mod baseline;
+mod batch;
+mod bench;
+mod cache;
+mod calendar;
+mod client;
+mod color;
+#[cfg(feature = "parquet")]
+mod columnar;
+#[cfg(all(feature = "sqlite", target_os = "wasi"))]
+compile_error!("the sqlite feature records timings with sqlite, which isn't built for WASI");
+mod compress;
+mod config;
+mod days;
+mod exit;
+mod explain;
+mod graphql;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod history;
+mod jobs;
+mod leaderboard;
+mod mapped;
+mod metrics;
+mod output;
+mod parsed;
+mod plugin;
+#[cfg(feature = "profile")]
+mod profile;
+mod progress;
+mod render;
+mod report;
+mod rpc;
+mod scaffold;
+mod scramble;
+mod serve;
+mod stats;
+#[cfg(feature = "sqlite")]
+mod trend;
+#[cfg(feature = "tui")]
+mod tui;
+mod verify;
+mod watch;
+mod webhook;
+
+use baseline::{Baseline, Medians};
+use client::Verdict;
+use config::Config;
+use days::{Day, Mode, Overflow, Selection};
+use exit::Failure;
+use leaderboard::Leaderboard;
+use mapped::InputText;
+use output::{Format, Run, Timings};
+use stats::Memory;
+
+#[cfg(feature = "stats")]
+#[global_allocator]
+static ALLOCATOR: stats::Counting = stats::Counting;
 
 /// Args for running the CLI program for the AoC puzzle solver
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
-    /// which day's puzzle are you solving?
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// which day's puzzle are you solving? accepts lists and ranges such as `1-4,7`.
+    /// Defaults to today's puzzle during the event.
+    #[arg(short, long, value_parser = days::parse_selection)]
+    day: Option<Selection>,
+
+    /// plaintext file or url containing your unique puzzle input, defaults to
+    /// `day<N>.txt` in the inputs directory or the cached download for the day.
+    /// Repeat it, or give a directory or glob, to compare several inputs.
+    /// Only valid when a single day is selected.
     #[arg(short, long)]
-    day: usize,
+    input: Vec<String>,
+
+    /// solve the example inputs from the puzzle descriptions instead of your own
+    #[arg(short, long, conflicts_with = "input")]
+    example: bool,
 
-    /// plaintext file containing your unique puzzle input
+    /// print the wall-clock time spent reading the input and solving each part
     #[arg(short, long)]
-    input: String,
+    time: bool,
+
+    /// output format for the answers [default: text]
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+
+    /// report the peak heap allocated while reading the input and solving each part
+    #[cfg(feature = "stats")]
+    #[arg(long, conflicts_with = "jobs")]
+    stats: bool,
+
+    /// print the steps the solvers take to stderr as they go, like which
+    /// numbers counted towards the answer and why. Days are solved one at a
+    /// time with the sequential solvers, so the steps come out in order.
+    #[arg(long, conflicts_with_all = ["jobs", "mode"])]
+    explain: bool,
+
+    /// print only the answers, one per line, for use in scripts. Same as `--format raw`
+    #[arg(short, long, conflicts_with_all = ["format", "time"])]
+    quiet: bool,
+
+    /// whether to use the sequential or parallel solvers
+    #[arg(short, long, value_enum)]
+    mode: Option<Mode>,
+
+    /// how many of the selected days to solve at once, overriding `jobs` from
+    /// the config. The threads are shared evenly between them [default: 1]
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: Option<u32>,
+
+    /// number of threads in the rayon pool used by the parallel solvers,
+    /// overriding `threads` from the config [default: one per cpu]
+    #[arg(long, global = true, value_parser = clap::value_parser!(u32).range(1..))]
+    threads: Option<u32>,
+
+    /// which of a day's implementations to run, for days that ship more than
+    /// one (e.g. `--algo vec` for day 4)
+    #[arg(short, long, global = true)]
+    algo: Option<String>,
+
+    /// solve with the named plugin from the plugins directory instead of the
+    /// built in solvers (see `aoc plugins`)
+    #[arg(long, global = true, conflicts_with = "algo")]
+    plugin: Option<String>,
+
+    /// don't append the answers to the history file
+    #[arg(long, global = true)]
+    no_history: bool,
+
+    /// save each input's parse in the cache directory and solve from it on
+    /// later runs, skipping parsing, for days that can
+    #[arg(long, global = true)]
+    parse_cache: bool,
+
+    /// what to do when a solver's arithmetic overflows a `u64`, overriding
+    /// `overflow` from the config [default: checked]
+    #[arg(long, value_enum, global = true)]
+    overflow: Option<Overflow>,
+
+    /// what solvers that use randomness draw from, so a run can be repeated
+    /// exactly, overriding `seed` from the config [default: 2023]
+    #[arg(long, global = true)]
+    seed: Option<u64>,
+
+    /// fail a solve with exit code 6 once what it reserves adds up to more
+    /// than this many bytes, overriding `memory_budget` from the config
+    /// [default: no budget]
+    #[arg(long, global = true)]
+    memory_budget: Option<usize>,
+
+    /// color the text output, overriding `color` from the config [default: auto]
+    #[arg(long, value_enum, global = true)]
+    color: Option<color::Color>,
+
+    /// post a summary to this Slack or Discord compatible webhook after a
+    /// run, or when `verify` finds a regression, overriding `webhook` from
+    /// the config
+    #[arg(long, global = true)]
+    webhook: Option<String>,
+
+    /// log what the solvers are doing to stderr, repeat for more detail (-v, -vv, -vvv)
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// repeatedly solve a day's puzzle and report timing statistics for each part
+    Bench {
+        /// which day's puzzle to benchmark
+        #[arg(short, long)]
+        day: usize,
+
+        /// plaintext file containing your unique puzzle input, defaults to
+        /// `day<N>.txt` in the inputs directory or the cached download for the day
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// number of unmeasured runs performed before measuring
+        #[arg(short, long, default_value_t = 3)]
+        warmup: usize,
+
+        /// number of measured runs
+        #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+
+        /// whether to use the sequential or parallel solvers
+        #[arg(short, long, value_enum)]
+        mode: Option<Mode>,
+
+        /// JSON file of earlier medians to compare against, failing if
+        /// either part is slower by more than `--max-regression`
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// percentage a part's median can grow by over its baseline before
+        /// it counts as a regression
+        #[arg(long, default_value_t = baseline::MAX_REGRESSION)]
+        max_regression: f64,
+
+        /// write this run's medians to a JSON file, as the day's entry
+        /// alongside any other days already saved there
+        #[arg(long)]
+        save_baseline: Option<PathBuf>,
+
+        /// pin the benchmark to a CPU core, the one it starts on unless one
+        /// is given, so it isn't moved between cores mid-run. Linux only, and
+        /// meant for sequential runs, since a parallel one shares the core.
+        #[arg(long, value_name = "CORE", num_args = 0..=1)]
+        pin_cpu: Option<Option<usize>>,
+
+        /// before measuring, read every page of the input and keep warming
+        /// up each part until its run times settle
+        #[arg(long)]
+        quiesce: bool,
+    },
+
+    /// download your puzzle input from adventofcode.com (requires AOC_SESSION)
+    Fetch {
+        /// which day's input to download
+        #[arg(short, long)]
+        day: usize,
+
+        /// directory the input is written to, as `day<N>.txt` [default: inputs]
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// download again even if the input already exists
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// show how long until the next puzzle unlocks
+    Countdown {
+        /// wait for the puzzle to unlock, then download its input (requires AOC_SESSION)
+        #[arg(long)]
+        fetch: bool,
+
+        /// directory the input is written to, as `day<N>.txt` [default: inputs]
+        #[arg(long, requires = "fetch")]
+        dir: Option<PathBuf>,
+    },
+
+    /// show a private leaderboard, ranked by score (requires AOC_SESSION)
+    Leaderboard {
+        /// the leaderboard's id, the number at the end of its url
+        #[arg(long)]
+        id: u64,
+    },
+
+    /// solve a part of a day's puzzle and submit the answer to adventofcode.com
+    Submit {
+        /// which day's puzzle to submit
+        #[arg(short, long)]
+        day: usize,
+
+        /// which part of the puzzle to submit
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// plaintext file containing your unique puzzle input, defaults to
+        /// `day<N>.txt` in the inputs directory or the cached download for the day
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// how many times to wait and resubmit when being throttled
+        #[arg(long, default_value_t = 3)]
+        retries: usize,
+    },
+
+    /// print a shell completion script for the cli
+    Completions {
+        /// the shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+
+    /// solve every implemented day and write a markdown report of the answers and timings
+    Report {
+        /// only report on these days, e.g. `1-4,7` [default: every implemented day]
+        #[arg(short, long, value_parser = days::parse_selection)]
+        day: Option<Selection>,
+
+        /// write an html page instead of markdown
+        #[arg(long)]
+        html: bool,
+
+        /// file to write the report to [default: stdout]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// whether to use the sequential or parallel solvers
+        #[arg(short, long, value_enum)]
+        mode: Option<Mode>,
+    },
+
+    /// check a puzzle input for problems without solving it
+    Validate {
+        /// which day's input to check
+        #[arg(short, long)]
+        day: usize,
+
+        /// plaintext file containing your unique puzzle input, defaults to
+        /// `day<N>.txt` in the inputs directory or the cached download for the day
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// output format for the diagnostics [default: text]
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+
+    /// parse a puzzle input without solving it and print what it was parsed
+    /// into, such as day 2's games or day 3's part numbers and symbols
+    Parse {
+        /// which day's input to parse
+        #[arg(short, long)]
+        day: usize,
+
+        /// plaintext file containing your unique puzzle input, defaults to
+        /// `day<N>.txt` in the inputs directory or the cached download for the day
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// output format, where text is indented JSON [default: text]
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+
+    /// scramble the values in a puzzle input but keep its shape, so it can be
+    /// attached to a bug report without sharing your own input
+    Scramble {
+        /// which day's input to scramble
+        #[arg(short, long)]
+        day: usize,
+
+        /// plaintext file containing your unique puzzle input, defaults to
+        /// `day<N>.txt` in the inputs directory or the cached download for the day
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// file to write the scrambled input to [default: stdout]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// scramble the same way every time [default: a different seed each run]
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+
+    /// check the solvers against a file of known-correct answers
+    Verify {
+        /// toml file with a `[day<N>]` table of `part1`/`part2` answers per day
+        #[arg(long, default_value = verify::FILE_NAME)]
+        answers: PathBuf,
+
+        /// only verify these days, e.g. `1-4,7` [default: every day in the answers file]
+        #[arg(short, long, value_parser = days::parse_selection)]
+        day: Option<Selection>,
+
+        /// whether to use the sequential or parallel solvers
+        #[arg(short, long, value_enum)]
+        mode: Option<Mode>,
+    },
+
+    /// serve the solvers over HTTP: `POST /solve/{day}` with the puzzle input as the
+    /// body responds with both answers as JSON
+    Serve {
+        /// address to listen on
+        #[arg(long, default_value = "127.0.0.1:8023")]
+        address: String,
+
+        /// whether to use the sequential or parallel solvers
+        #[arg(short, long, value_enum)]
+        mode: Option<Mode>,
+
+        /// give up on a request's solve after this many seconds
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+
+    /// answer JSON-RPC 2.0 requests on stdin and stdout, for editors that
+    /// keep the cli running to solve and check inputs as they're edited
+    Rpc {
+        /// whether to use the sequential or parallel solvers
+        #[arg(short, long, value_enum)]
+        mode: Option<Mode>,
+
+        /// give up on a request's solve after this many seconds
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+
+    /// serve the `aoc.Solver` gRPC service described by `bin/proto/aoc.proto`,
+    /// which streams progress while it solves a part
+    #[cfg(feature = "grpc")]
+    Grpc {
+        /// address to listen on
+        #[arg(long, default_value = "127.0.0.1:8024")]
+        address: String,
+
+        /// whether to use the sequential or parallel solvers
+        #[arg(short, long, value_enum)]
+        mode: Option<Mode>,
+
+        /// give up on a request's solve after this many seconds
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+
+    /// generate a crate for a new day and add it to the workspace and the cli
+    NewDay {
+        /// which day's crate to create
+        day: usize,
+    },
+
+    /// re-solve a day's puzzle whenever its input file changes
+    Watch {
+        /// which day's puzzle to solve
+        #[arg(short, long)]
+        day: usize,
+
+        /// plaintext file to watch, defaults to `day<N>.txt` in the inputs directory
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// also rebuild and restart when the day's source changes (only under `cargo run`)
+        #[arg(long)]
+        source: bool,
+
+        /// solve from scratch on every change, even for days that can update
+        /// their answers from only the lines that changed
+        #[arg(long)]
+        full: bool,
+
+        /// print the wall-clock time spent reading the input and solving each part
+        #[arg(short, long)]
+        time: bool,
+
+        /// whether to use the sequential or parallel solvers
+        #[arg(short, long, value_enum)]
+        mode: Option<Mode>,
+    },
+
+    /// run a day's solvers under a sampling profiler and write a flamegraph SVG
+    #[cfg(feature = "profile")]
+    Profile {
+        /// which day's puzzle to profile
+        #[arg(short, long)]
+        day: usize,
+
+        /// plaintext file containing your unique puzzle input, defaults to
+        /// `day<N>.txt` in the inputs directory or the cached download for the day
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// profile only this part [default: both]
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// where the flamegraph is written [default: day<N>-flamegraph.svg]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// keep re-running the solvers for at least this many seconds
+        #[arg(short, long, default_value_t = 5)]
+        seconds: u64,
+
+        /// stack samples taken per second
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(i32).range(1..))]
+        frequency: i32,
+
+        /// whether to use the sequential or parallel solvers
+        #[arg(short, long, value_enum)]
+        mode: Option<Mode>,
+    },
+
+    /// list the implemented days with their puzzle titles, tags and expected runtimes
+    List {
+        /// output format for the list [default: text]
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+
+    /// play back the frames a day's solver emits as a terminal animation, for
+    /// days that visualize their solve
+    Animate {
+        /// which day's puzzle to animate
+        #[arg(short, long)]
+        day: usize,
+
+        /// plaintext file containing your unique puzzle input, defaults to
+        /// `day<N>.txt` in the inputs directory or the cached download for the day
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// animate the example inputs from the puzzle descriptions instead of your own
+        #[arg(short, long, conflicts_with = "input")]
+        example: bool,
+
+        /// animate only this part [default: both]
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// frames shown per second
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        fps: u32,
+
+        /// render the frames to a file instead of playing them: an animated GIF
+        /// for a `.gif` path, or an SVG per frame, numbered, for a `.svg` one
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// show how each part's runtime has changed across code revisions, from
+    /// the timings recorded with every run
+    #[cfg(feature = "sqlite")]
+    Stats {
+        /// only show these days, e.g. `1-4,7` [default: every day with timings]
+        #[arg(short, long, value_parser = days::parse_selection)]
+        day: Option<Selection>,
+
+        /// output format for the trends [default: text]
+        #[arg(short, long, value_enum)]
+        format: Option<Format>,
+    },
+
+    /// list the plugins found in the plugins directory and the days they solve
+    Plugins,
+
+    /// write the recorded timings, and what each day's input parses into, as
+    /// Parquet files for analysis in pandas, polars or duckdb
+    #[cfg(feature = "parquet")]
+    Parquet {
+        /// only these days, e.g. `1-4,7` [default: every day]
+        #[arg(short, long, value_parser = days::parse_selection)]
+        day: Option<Selection>,
+
+        /// directory to write the files to, created if it doesn't exist
+        #[arg(short, long, default_value = "parquet")]
+        output: PathBuf,
+    },
+
+    /// interactive dashboard of every day's status, answers and timings
+    #[cfg(feature = "tui")]
+    Tui {
+        /// whether to use the sequential or parallel solvers
+        #[arg(short, long, value_enum)]
+        mode: Option<Mode>,
+    },
+}
+
+fn lookup(day: usize, mode: Mode, algo: Option<&str>) -> Result<Day> {
+    if let Some(plugin) = plugin::selected() {
+        return plugin.day(day).ok_or_else(|| {
+            Failure::NoSolver.tag(anyhow!(
+                "plugin {} has no solver for day {day}",
+                plugin.name
+            ))
+        });
+    }
+    let solvers = days::lookup(day, mode)
+        .ok_or_else(|| Failure::NoSolver.tag(anyhow!("Solver not implemented for day {day}")))?;
+    match algo {
+        Some(algo) => days::with_algo(day, solvers, algo),
+        None => Ok(parsed::cached(day, mode, solvers)),
+    }
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
-    let text = fs::read_to_string(args.input)?;
+/// read the puzzle input from the given file, falling back to `day<N>.txt`
+/// (or `day<N>.txt.gz`, `day<N>.txt.zst`) in the inputs directory and then to
+/// the input cache. Under WASI only the directories preopened for the cli can
+/// be read, and there's no network to fill the cache from.
+fn read_input(day: usize, input: Option<&str>, config: &Config) -> Result<InputText> {
+    match input {
+        Some(url) if client::is_url(url) => return read_url(day, url, config).map(Into::into),
+        Some(path) => return read_file(Path::new(path)),
+        None => {}
+    }
 
-    #[cfg(feature = "singlethread")]
-    match args.day {
-        1 => day1::print_answers(&text)?,
-        2 => day2::print_answers(&text)?,
-        3 => day3::print_answers(&text)?,
-        4 => day4::print_answers(&text)?,
-        _ => return Err(anyhow!("Solver not implemented for day {}", args.day)),
+    for name in [".txt", ".txt.gz", ".txt.zst"].map(|suffix| format!("day{day}{suffix}")) {
+        let local = config.inputs_dir().join(name);
+        if local.exists() {
+            return read_file(&local);
+        }
+    }
+    if cfg!(target_os = "wasi") {
+        return Err(anyhow!(
+            "no input for day {day} in {}, which must be preopened with wasmtime's --dir",
+            config.inputs_dir().display()
+        ));
+    }
+    cache::load_or_fetch(day, config).map(Into::into)
+}
+
+/// download an input. This year's inputs on adventofcode.com go through the
+/// input cache, anything else on adventofcode.com is fetched with the session.
+fn read_url(day: usize, url: &str, config: &Config) -> Result<Vec<u8>> {
+    if let Some(input_day) = client::input_day(url) {
+        if input_day != day {
+            return Err(anyhow!(
+                "{url} is the input for day {input_day}, not day {day}"
+            ));
+        }
+        return cache::load_or_fetch(day, config).map(String::into_bytes);
+    }
+    let bytes = match client::is_aoc_url(url) {
+        true => client::Client::from_config(config)?.get(url)?,
+        false => client::download(url)?,
     };
+    // the query string would hide a `.gz` or `.zst` extension
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    compress::decode(Path::new(path), bytes)
+}
+
+/// read an input file, decompressing it if it's gzip or zstd compressed, or
+/// map it if it's large and isn't
+fn read_file(path: &Path) -> Result<InputText> {
+    if let Some(mapped) = mapped::map(path)? {
+        return Ok(mapped);
+    }
+    Ok(compress::decode(path, fs::read(path)?)?.into())
+}
+
+fn solve(
+    day: usize,
+    input: Option<&str>,
+    mode: Mode,
+    algo: Option<&str>,
+    config: &Config,
+) -> Result<Run> {
+    let _span = info_span!("solve", day, ?mode, algo).entered();
+    let solvers = lookup(day, mode, algo)?;
+
+    let ((text, input_memory), input_time) =
+        timed(|| stats::measure(|| read_input(day, input, config)));
+    let text = text?;
+    info!(bytes = text.len(), elapsed = ?input_time, "read input");
+
+    let mut run = solve_text(day, &text, &solvers)?;
+    run.timings.input = input_time;
+    if let (Some(memory), Some(input)) = (&mut run.memory, input_memory) {
+        memory.input = input;
+    }
+    Ok(run)
+}
 
-    #[cfg(feature = "multithread")]
-    match args.day {
-        1 => day1::mt::print_answers(&text)?,
-        _ => return Err(anyhow!("Solver not implemented for day {}", args.day)),
+/// solve both parts of a day against a puzzle input that has already been read
+fn solve_text(day: usize, input: &[u8], solvers: &Day) -> Result<Run> {
+    solve_texts(day, [input, input], solvers)
+}
+
+/// solve a day's puzzle against the examples bundled with its crate
+fn solve_example(day: usize, mode: Mode, algo: Option<&str>) -> Result<Run> {
+    let _span = info_span!("solve", day, ?mode, algo, example = true).entered();
+    let solvers = lookup(day, mode, algo)?;
+    let examples =
+        days::examples(day).ok_or_else(|| anyhow!("no examples bundled for day {day}"))?;
+    solve_texts(day, examples.map(str::as_bytes), &solvers)
+}
+
+/// solve each part against its own text, since some puzzles give a
+/// different example for each part. When both parts have the same text and
+/// the day is solved by its registered solver, it's parsed once for both.
+fn solve_texts(day: usize, [part_one, part_two]: [&[u8]; 2], solvers: &Day) -> Result<Run> {
+    let classify = |error| classify(day, &[part_one, part_two], error);
+    if let Some(both) = solvers.both.as_ref().filter(|_| part_one == part_two) {
+        let (answers, memory) = explain::with_heading(format!("day {day}"), || {
+            progress::with_bar(format!("day {day}"), || stats::measure(|| both(part_one)))
+        });
+        let answers = answers.map_err(classify)?;
+        return Ok(Run {
+            day,
+            part1: answers.part1,
+            part2: answers.part2,
+            timings: Timings {
+                input: Duration::ZERO,
+                parse: Some(answers.timings.parse),
+                part1: answers.timings.part1,
+                part2: answers.timings.part2,
+            },
+            memory: memory.map(|solve| Memory { input: 0, solve }),
+            input_hash: history::hash(part_one),
+        });
+    }
+
+    let ((part1, part1_memory), part1_time) =
+        explain::with_heading(format!("day {day} part one"), || {
+            progress::with_bar(format!("day {day} part one"), || {
+                timed(|| stats::measure(|| (solvers.part_one)(part_one)))
+            })
+        });
+    let ((part2, part2_memory), part2_time) =
+        explain::with_heading(format!("day {day} part two"), || {
+            progress::with_bar(format!("day {day} part two"), || {
+                timed(|| stats::measure(|| (solvers.part_two)(part_two)))
+            })
+        });
+
+    Ok(Run {
+        day,
+        part1: part1.map_err(classify)?,
+        part2: part2.map_err(classify)?,
+        // each part parsed its text itself, so there's no parse to time apart
+        timings: Timings {
+            input: Duration::ZERO,
+            parse: None,
+            part1: part1_time,
+            part2: part2_time,
+        },
+        memory: part1_memory.zip(part2_memory).map(|(part1, part2)| Memory {
+            input: 0,
+            solve: part1.max(part2),
+        }),
+        input_hash: history::hash(part_one),
+    })
+}
+
+/// tag a failed solve as over its memory budget when it says so, as a parse
+/// error when the day's validator finds problems with the input, or as a solve
+/// error when the input looked fine. Only a failed solve is validated, so only
+/// then are the inputs decoded as text.
+fn classify(day: usize, inputs: &[&[u8]], error: anyhow::Error) -> anyhow::Error {
+    if exit::aoc_error(&error).is_some_and(|e| e.memory.is_some()) {
+        return Failure::Memory.tag(error);
+    }
+    let invalid = days::validator(day).is_some_and(|validate| {
+        inputs.iter().any(|input| {
+            validate(&String::from_utf8_lossy(input))
+                .iter()
+                .any(|d| d.is_error())
+        })
+    });
+    match invalid {
+        true => Failure::Parse.tag(error),
+        false => Failure::Solve.tag(error),
+    }
+}
+
+/// append runs to the answer history. A history that can't be written
+/// shouldn't cost the user their answers, so failures are only logged.
+fn record_history(runs: &[Run], config: &Config) {
+    if !config.history.unwrap_or(true) {
+        return;
+    }
+    if let Err(e) = history::record(runs, config) {
+        warn!("unable to record answer history: {e}");
+    }
+    #[cfg(feature = "sqlite")]
+    {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        if let Err(e) =
+            trend::open(config).and_then(|mut db| trend::record(&mut db, runs, timestamp))
+        {
+            warn!("unable to record timings: {e}");
+        }
+    }
+}
+
+/// Where `run_days` reads each day's puzzle input from
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Input<'a> {
+    /// `day<N>.txt` in the inputs directory, or the input cache
+    Default,
+    /// a file given with `--input`
+    File(&'a str),
+    /// the examples bundled with the day's crate
+    Example,
+}
+
+/// solve each of the selected days in order, printing their answers followed
+/// by a summary. A day that fails is reported without stopping the others.
+fn run_days(
+    days: &[usize],
+    input: Input,
+    mode: Mode,
+    algo: Option<&str>,
+    format: Format,
+    time: bool,
+    config: &Config,
+) -> Result<()> {
+    if matches!(input, Input::File(_)) && days.len() > 1 {
+        return Err(anyhow!("--input can only be used with a single day"));
+    }
+
+    let solve_day = |day| match input {
+        Input::Default => solve(day, None, mode, algo, config),
+        Input::File(path) => solve(day, Some(path), mode, algo, config),
+        Input::Example => solve_example(day, mode, algo),
     };
+    let jobs = config.jobs.unwrap_or(1).clamp(1, days.len().max(1));
+    let (results, wall_time) = timed(|| match jobs {
+        1 => Ok(days.iter().map(|&day| solve_day(day)).collect()),
+        _ => {
+            progress::disable();
+            let threads = config.threads.unwrap_or_else(jobs::available_threads);
+            jobs::run(days, jobs, threads, solve_day)
+        }
+    });
+
+    let mut runs = vec![];
+    let mut failed = vec![];
+    for (&day, run) in days.iter().zip(results?) {
+        match run {
+            Ok(run) => runs.push(run),
+            Err(e) => {
+                let failure = output::render_failure(day, &e, format)?;
+                match format {
+                    // machine readable failures go with the answers
+                    Format::Json => println!("{failure}"),
+                    _ => eprintln!("{failure}"),
+                }
+                failed.push((day, exit::failure(&e)));
+            }
+        }
+    }
+
+    let color = config.color.unwrap_or_default().enabled();
+    if !runs.is_empty() {
+        output::print(&output::render(&runs, format, time, color)?)?;
+        // answers to the examples aren't worth remembering
+        if input != Input::Example {
+            record_history(&runs, config);
+        }
+    }
+    if format == Format::Text && days.len() > 1 {
+        let total: Duration = runs.iter().map(|run| run.timings.total()).sum();
+        let style = match failed.is_empty() {
+            true => color::GOOD,
+            false => color::BAD,
+        };
+        let solved = format!("solved {} of {} days", runs.len(), days.len());
+        print!("{} in {total:?}", color::paint(solved, style, color));
+        match jobs {
+            1 => println!(),
+            _ => println!(" ({wall_time:?} wall-clock with {jobs} jobs)"),
+        }
+    }
+
+    let failed_days: Vec<usize> = failed.iter().map(|(day, _)| *day).collect();
+    webhook::notify(config, &webhook::run_summary(&runs, &failed_days));
+
+    match failed.as_slice() {
+        [] => Ok(()),
+        [(_, first), ..] => {
+            let days = failed_days;
+            let error = anyhow!("failed to solve days {days:?}");
+            // exit with the code of the first failure
+            Err(match first {
+                Some(kind) => kind.tag(error),
+                None => error,
+            })
+        }
+    }
+}
+
+/// solve one day for each of several inputs, printing a table of their
+/// answers. An input that fails is reported without stopping the others.
+fn run_batch(
+    day: usize,
+    inputs: &[String],
+    mode: Mode,
+    algo: Option<&str>,
+    format: Format,
+    config: &Config,
+) -> Result<()> {
+    let mut rows = vec![];
+    let mut first_failure = None;
+    for input in inputs {
+        let run = solve(day, Some(input), mode, algo, config);
+        if let Err(e) = &run {
+            if format != Format::Text {
+                eprintln!("{input}: {e}");
+            }
+            first_failure = first_failure.or(Some(exit::failure(e)));
+        }
+        rows.push((input.clone(), run.map_err(|e| e.to_string())));
+    }
+
+    output::print(&batch::render(&rows, format)?)?;
+    let runs: Vec<Run> = rows.into_iter().filter_map(|(_, run)| run.ok()).collect();
+    record_history(&runs, config);
+
+    match first_failure {
+        None => Ok(()),
+        Some(kind) => {
+            let failed = inputs.len() - runs.len();
+            let error = anyhow!("failed to solve {failed} of {} inputs", inputs.len());
+            Err(match kind {
+                Some(kind) => kind.tag(error),
+                None => error,
+            })
+        }
+    }
+}
+
+/// How many times `bench` runs each part, how it steadies the machine
+/// first, and where it compares and saves its medians
+struct BenchOptions<'a> {
+    warmup: usize,
+    iterations: usize,
+    baseline: Option<&'a Path>,
+    max_regression: f64,
+    save_baseline: Option<&'a Path>,
+    pin_cpu: Option<Option<usize>>,
+    quiesce: bool,
+}
+
+fn run_bench(
+    day: usize,
+    input: Option<&str>,
+    mode: Mode,
+    algo: Option<&str>,
+    options: BenchOptions,
+    config: &Config,
+) -> Result<()> {
+    let BenchOptions {
+        warmup,
+        iterations,
+        quiesce,
+        ..
+    } = options;
+    // read before measuring, so a missing baseline doesn't waste a run
+    let baseline = options.baseline.map(Baseline::load).transpose()?;
+    if let (Some(baseline), Some(path)) = (&baseline, options.baseline) {
+        baseline.day(day, path)?;
+    }
+
+    let solvers = lookup(day, mode, algo)?;
+    let text = read_input(day, input, config)?;
+    if let Some(core) = options.pin_cpu {
+        let core = bench::pin_cpu(core)?;
+        println!("pinned to cpu {core}");
+    }
+    let (part_one, part_two) = bench::run(&solvers, &text, warmup, iterations, quiesce)?;
+
+    println!("day {day}: {warmup} warmup, {iterations} measured iterations");
+    for (name, stats) in [("part one", &part_one), ("part two", &part_two)] {
+        println!(
+            "{name}: min {:?} | median {:?} | mean {:?} | stddev {:?}",
+            stats.min, stats.median, stats.mean, stats.stddev
+        );
+    }
+
+    let medians = Medians::from_stats(&part_one, &part_two);
+    if let Some(path) = options.save_baseline {
+        Baseline::save(path, day, medians)?;
+        println!("saved the medians to {}", path.display());
+    }
+    let (Some(baseline), Some(path)) = (baseline, options.baseline) else {
+        return Ok(());
+    };
+    let max = options.max_regression;
+    let comparisons = baseline::compare(&medians, baseline.day(day, path)?);
+    let mut regressed = 0;
+    for comparison in &comparisons {
+        let part = if comparison.part == 1 { "one" } else { "two" };
+        let verdict = match comparison.regressed(max) {
+            true => {
+                regressed += 1;
+                "REGRESSED"
+            }
+            false => "ok",
+        };
+        println!(
+            "part {part}: median {:?} vs baseline {:?}, {:+.1}%, {verdict}",
+            comparison.current,
+            comparison.baseline,
+            comparison.change()
+        );
+    }
+    match regressed {
+        0 => Ok(()),
+        _ => Err(anyhow!(
+            "{regressed} of {} parts regressed by more than {max}%",
+            comparisons.len()
+        )),
+    }
+}
+
+fn run_fetch(day: usize, dir: Option<PathBuf>, force: bool, config: &Config) -> Result<()> {
+    let dir = dir.unwrap_or_else(|| config.inputs_dir().to_path_buf());
+    let path = dir.join(format!("day{day}.txt"));
+    if path.exists() && !force {
+        println!("{} already exists, skipping download", path.display());
+        return Ok(());
+    }
+
+    if force {
+        let _ = fs::remove_file(cache::path(day)?);
+    }
+    let text = cache::load_or_fetch(day, config)?;
+    fs::create_dir_all(&dir)?;
+    fs::write(&path, text)?;
+    println!("saved input for day {day} to {}", path.display());
     Ok(())
 }
+
+/// how long to keep trying to download an input that should have unlocked,
+/// in case our clock is ahead of adventofcode.com's
+const UNLOCK_RETRIES: usize = 3;
+const UNLOCK_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+fn run_countdown(fetch: bool, dir: Option<PathBuf>, config: &Config) -> Result<()> {
+    let unlock = calendar::next_unlock(SystemTime::now());
+    let remaining = || {
+        unlock
+            .at
+            .duration_since(SystemTime::now())
+            .unwrap_or_default()
+    };
+    if !fetch {
+        println!(
+            "day {} unlocks in {}",
+            unlock.day,
+            calendar::countdown(remaining())
+        );
+        return Ok(());
+    }
+
+    // fail now rather than after the wait if there's no session to download with
+    client::Client::from_config(config)?;
+    let live = io::stdout().is_terminal();
+    while !remaining().is_zero() {
+        if live {
+            print!(
+                "\rday {} unlocks in {} ",
+                unlock.day,
+                calendar::countdown(remaining())
+            );
+            io::stdout().flush()?;
+        }
+        thread::sleep(remaining().min(Duration::from_secs(1)));
+    }
+    if live {
+        println!();
+    }
+
+    let mut attempt = 1;
+    loop {
+        match run_fetch(unlock.day, dir.clone(), false, config) {
+            Err(e) if attempt < UNLOCK_RETRIES => {
+                warn!("day {} isn't available yet: {e}", unlock.day);
+                thread::sleep(UNLOCK_RETRY_DELAY);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn run_submit(
+    day: usize,
+    part: u8,
+    input: Option<&str>,
+    algo: Option<&str>,
+    retries: usize,
+    config: &Config,
+) -> Result<()> {
+    let run = solve(day, input, config.mode.unwrap_or_default(), algo, config)?;
+    let answer = match part {
+        1 => run.part1,
+        _ => run.part2,
+    };
+
+    let client = client::Client::from_config(config)?;
+    let mut attempt = 0;
+    loop {
+        println!("submitting {answer} for day {day} part {part}");
+        match client.submit(day, part, &answer.to_string())? {
+            Verdict::Wait(wait) if attempt < retries => {
+                attempt += 1;
+                println!("throttled, retrying in {wait:?} ({attempt}/{retries})");
+                thread::sleep(wait);
+            }
+            Verdict::Correct => {
+                println!("correct!");
+                return Ok(());
+            }
+            verdict => return Err(anyhow!("answer not accepted: {verdict:?}")),
+        }
+    }
+}
+
+fn run_report(
+    selection: Option<Selection>,
+    html: bool,
+    output: Option<PathBuf>,
+    mode: Mode,
+    config: &Config,
+) -> Result<()> {
+    let days = match selection {
+        Some(Selection(days)) => days,
+        None => (1..=25)
+            .filter(|&day| days::lookup(day, mode).is_some())
+            .collect(),
+    };
+    let runs: Vec<report::Outcome> = days
+        .into_iter()
+        .map(|day| {
+            let run = solve(day, None, mode, None, config).map_err(|e| e.to_string());
+            (day, run)
+        })
+        .collect();
+
+    let report = match html {
+        true => report::html(&runs),
+        false => report::markdown(&runs),
+    };
+    match output {
+        Some(path) => {
+            fs::write(&path, report)?;
+            println!("wrote report to {}", path.display());
+        }
+        None => print!("{report}"),
+    }
+    Ok(())
+}
+
+fn run_list(format: Format) -> Result<()> {
+    for registration in aoc_core::solver::registered() {
+        let metadata = registration.solver.metadata();
+        let algorithms = days::algorithms(metadata.day);
+        // the metadata, with the algorithms `--algo` picks between
+        let described = || -> Result<serde_json::Value> {
+            let mut described = serde_json::to_value(metadata)?;
+            described["algorithms"] = serde_json::json!(algorithms);
+            Ok(described)
+        };
+        match format {
+            Format::Text => println!(
+                "day {}: {} [{}] ({}){}",
+                metadata.day,
+                metadata.title,
+                metadata.tags.join(", "),
+                metadata.runtime,
+                match algorithms.as_slice() {
+                    [] => String::new(),
+                    names => format!(", algorithms: {}", names.join(", ")),
+                }
+            ),
+            Format::Json => println!("{}", described()?),
+            Format::Csv => println!(
+                "{},{:?},{:?},{},{:?}",
+                metadata.day,
+                metadata.title,
+                metadata.tags.join(";"),
+                metadata.runtime,
+                algorithms.join(";")
+            ),
+            Format::Raw => println!("{}", metadata.day),
+            Format::Msgpack => output::print(&output::msgpack(&described()?)?)?,
+        }
+    }
+    Ok(())
+}
+
+fn run_validate(day: usize, input: Option<&str>, format: Format, config: &Config) -> Result<()> {
+    let validate =
+        days::validator(day).ok_or_else(|| anyhow!("Validator not implemented for day {day}"))?;
+    let text = read_input(day, input, config)?;
+    let diagnostics = validate(text.text()?);
+
+    let name = input.map_or_else(|| format!("day{day}.txt"), str::to_string);
+    for diagnostic in &diagnostics {
+        match format {
+            Format::Text => println!("{name}:{diagnostic}"),
+            Format::Json => println!("{}", output::diagnostic_json(diagnostic)),
+            Format::Csv => println!(
+                "{},{},{},{:?}",
+                diagnostic.severity, diagnostic.line, diagnostic.column, diagnostic.message
+            ),
+            Format::Raw => println!("{diagnostic}"),
+            Format::Msgpack => {
+                output::print(&output::msgpack(&output::diagnostic_json(diagnostic))?)?
+            }
+        }
+    }
+
+    // warnings are reported but only errors fail the check
+    let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+    let warnings = diagnostics.len() - errors;
+    match (errors, warnings) {
+        (0, 0) => {
+            if format == Format::Text {
+                println!("{name}: no problems found");
+            }
+            Ok(())
+        }
+        (0, warnings) => {
+            if format == Format::Text {
+                println!("{name}: found {warnings} warnings");
+            }
+            Ok(())
+        }
+        (errors, warnings) => Err(anyhow!(
+            "found {errors} errors and {warnings} warnings in {name}"
+        )),
+    }
+}
+
+fn run_parse(day: usize, input: Option<&str>, format: Format, config: &Config) -> Result<()> {
+    let exporter = days::exporter(day)
+        .ok_or_else(|| anyhow!("day {day} can't show what its input parses into"))?;
+    let text = read_input(day, input, config)?;
+    let model = exporter.export(
+        text.text()?,
+        aoc_core::strategy::ExecutionStrategy::Sequential,
+    )?;
+    match format {
+        Format::Text => println!("{}", serde_json::to_string_pretty(&model)?),
+        Format::Json => println!("{model}"),
+        Format::Msgpack => output::print(&output::msgpack(&model)?)?,
+        Format::Csv | Format::Raw => {
+            return Err(anyhow!(
+                "a parsed input can only be printed as text, json or msgpack"
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// write the timings and each day's parsed default input as Parquet. A day
+/// without an input is skipped rather than failing the others.
+#[cfg(feature = "parquet")]
+fn run_parquet(selection: Option<Selection>, output: &Path, config: &Config) -> Result<()> {
+    fs::create_dir_all(output).with_context(|| format!("unable to create {}", output.display()))?;
+    let selected = selection.map(|Selection(days)| days);
+    let (path, rows) = columnar::write_timings(&trend::open(config)?, selected.as_deref(), output)?;
+    println!("wrote {rows} timings to {}", path.display());
+
+    let days = selected.unwrap_or_else(|| {
+        aoc_core::solver::registered()
+            .into_iter()
+            .map(|registration| registration.solver.day())
+            .collect()
+    });
+    for day in days {
+        let Some(exporter) = days::exporter(day) else {
+            continue;
+        };
+        let text = match read_input(day, None, config) {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("day {day}: skipped, {e}");
+                continue;
+            }
+        };
+        let model = exporter.export(
+            text.text()?,
+            aoc_core::strategy::ExecutionStrategy::Sequential,
+        )?;
+        for (path, rows) in columnar::write_parsed(day, model, output)? {
+            println!("wrote {rows} rows to {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn run_scramble(
+    day: usize,
+    input: Option<&str>,
+    output: Option<&Path>,
+    seed: Option<u64>,
+    config: &Config,
+) -> Result<()> {
+    let text = read_input(day, input, config)?;
+    let scrambled = scramble::scramble(
+        day,
+        text.text()?,
+        seed.unwrap_or_else(scramble::random_seed),
+    )?;
+    match output {
+        Some(path) => {
+            fs::write(path, scrambled)?;
+            println!("wrote a scrambled day {day} input to {}", path.display());
+        }
+        None => print!("{scrambled}"),
+    }
+    Ok(())
+}
+
+fn run_verify(
+    answers: &Path,
+    selection: Option<Selection>,
+    mode: Mode,
+    algo: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    let text = fs::read_to_string(answers)
+        .with_context(|| format!("unable to read answers from {}", answers.display()))?;
+    let answers =
+        verify::Answers::parse(&text).with_context(|| format!("invalid {}", answers.display()))?;
+
+    let mut passed = 0;
+    let mut regressions = vec![];
+    for (&day, expected) in &answers.0 {
+        if let Some(Selection(days)) = &selection {
+            if !days.contains(&day) {
+                continue;
+            }
+        }
+        let run = match solve(day, None, mode, algo, config) {
+            Ok(run) => run,
+            Err(e) => {
+                println!("day {day}: FAIL, {e}");
+                regressions.push(format!("day {day}: {e}"));
+                continue;
+            }
+        };
+        for check in verify::check(&run, expected) {
+            let part = if check.part == 1 { "one" } else { "two" };
+            if check.passed() {
+                println!("day {day} part {part}: ok");
+                passed += 1;
+            } else {
+                let mismatch = format!("expected {} but got {}", check.expected, check.actual);
+                println!("day {day} part {part}: FAIL, {mismatch}");
+                regressions.push(format!("day {day} part {part}: {mismatch}"));
+            }
+        }
+    }
+
+    let failed = regressions.len();
+    println!("{passed} passed, {failed} failed");
+    match failed {
+        0 => Ok(()),
+        _ => {
+            webhook::notify(
+                config,
+                &webhook::regression_summary(&regressions, passed + failed),
+            );
+            Err(anyhow!("{failed} answers did not match"))
+        }
+    }
+}
+
+fn run_watch(
+    day: usize,
+    input: Option<PathBuf>,
+    source: bool,
+    mut resolve: watch::Resolve,
+    time: bool,
+    algo: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    let input = input.unwrap_or_else(|| config.inputs_dir().join(format!("day{day}.txt")));
+    let source = match source {
+        true => Some(watch::source_dir(day)?),
+        false => None,
+    };
+    if let watch::Resolve::Full(mode) = resolve {
+        // fail fast on an unknown day or algorithm rather than on every change
+        lookup(day, mode, algo)?;
+    }
+
+    let path = input.to_string_lossy();
+    let color = config.color.unwrap_or_default().enabled();
+    println!("watching {path}, press ctrl-c to stop");
+    watch::watch(&input, source.as_deref(), || {
+        let run = match &mut resolve {
+            watch::Resolve::Incremental(updating) => read_input(day, Some(&path), config)
+                .and_then(|text| updating.update(day, text.text()?)),
+            watch::Resolve::Full(mode) => solve(day, Some(&path), *mode, algo, config),
+        };
+        let result = run.and_then(|run| {
+            let rendered = output::render(std::slice::from_ref(&run), Format::Text, time, color)?;
+            record_history(&[run], config);
+            Ok(rendered)
+        });
+        match result {
+            Ok(rendered) => print!("{}", String::from_utf8_lossy(&rendered)),
+            Err(e) => eprintln!("day {day}: {e}"),
+        }
+    })
+}
+
+/// solve a day sequentially, keeping the frames its solver emits, and play
+/// them back or render them to `output`
+fn run_animate(
+    day: usize,
+    input: Input,
+    part: Option<u8>,
+    fps: u32,
+    output: Option<&Path>,
+    algo: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    // frames from rayon workers would arrive out of order
+    let solvers = lookup(day, Mode::Sequential, algo)?;
+    let read = |path| -> Result<[InputText; 2]> {
+        let text = read_input(day, path, config)?;
+        Ok([text.to_vec().into(), text])
+    };
+    let [part_one, part_two] = match input {
+        Input::Default => read(None)?,
+        Input::File(path) => read(Some(path))?,
+        Input::Example => days::examples(day)
+            .ok_or_else(|| anyhow!("no examples bundled for day {day}"))?
+            .map(|text| text.to_string().into()),
+    };
+
+    let mut frames = vec![];
+    if part != Some(2) {
+        let (answer, emitted) = animate::record(|| (solvers.part_one)(&part_one));
+        answer?;
+        frames.extend(emitted);
+    }
+    if part != Some(1) {
+        let (answer, emitted) = animate::record(|| (solvers.part_two)(&part_two));
+        answer?;
+        frames.extend(emitted);
+    }
+    if frames.is_empty() {
+        return Err(anyhow!("day {day} doesn't visualize its solve"));
+    }
+
+    if let Some(output) = output {
+        let written = render::write(&frames, output, fps)?;
+        match written.as_slice() {
+            [path] => println!("wrote {} frames to {}", frames.len(), path.display()),
+            [first, .., last] => println!(
+                "wrote {} SVGs, {} to {}",
+                written.len(),
+                first.display(),
+                last.display()
+            ),
+            [] => {}
+        }
+        return Ok(());
+    }
+
+    let color = config.color.unwrap_or_default().enabled();
+    let live = io::stdout().is_terminal();
+    animate::play(&frames, fps, live, color, &mut io::stdout().lock())
+}
+
+#[cfg(feature = "sqlite")]
+fn run_stats(selection: Option<Selection>, format: Format, config: &Config) -> Result<()> {
+    let days = selection.map(|Selection(days)| days);
+    let trends = trend::trends(&trend::open(config)?, days.as_deref())?;
+    if trends.is_empty() {
+        println!("no timings recorded yet, they're added with every run");
+        return Ok(());
+    }
+    output::print(&trend::render(&trends, format)?)?;
+    Ok(())
+}
+
+fn run_plugins(config: &Config) -> Result<()> {
+    let dir = plugin::dir(config)?;
+    let plugins = plugin::discover(&dir)?;
+    if plugins.is_empty() {
+        println!("no plugins in {}", dir.display());
+    }
+    for plugin in plugins {
+        let days: Vec<String> = plugin.days.iter().map(usize::to_string).collect();
+        println!(
+            "{}: days {} ({})",
+            plugin.name,
+            days.join(", "),
+            plugin.path.display()
+        );
+    }
+    Ok(())
+}
+
+/// send logs to stderr, at a level picked by the number of `-v` flags unless
+/// overridden with `RUST_LOG`. Span closes are logged so each phase's timing
+/// shows up alongside its events.
+fn init_tracing(verbose: u8) {
+    let level = match verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .with_span_events(FmtSpan::CLOSE)
+        .init();
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            ExitCode::from(exit::code(&e))
+        }
+    }
+}
+
+fn run(args: Args) -> Result<()> {
+    init_tracing(args.verbose);
+    let mut config = Config::load()?;
+    if args.no_history {
+        config.history = Some(false);
+    }
+    if args.parse_cache {
+        config.parse_cache = Some(true);
+    }
+    if config.parse_cache == Some(true) {
+        parsed::enable();
+    }
+
+    if let Some(threads) = args.threads {
+        config.threads = Some(threads as usize);
+    }
+    if let Some(jobs) = args.jobs {
+        config.jobs = Some(jobs as usize);
+    }
+    if cfg!(target_os = "wasi") {
+        // WASI has no threads, so days are solved one at a time and rayon
+        // runs parallel solvers on the main thread
+        config.threads = None;
+        config.jobs = Some(1);
+    }
+    if let Some(color) = args.color {
+        config.color = Some(color);
+    }
+    if let Some(overflow) = args.overflow {
+        config.overflow = Some(overflow);
+    }
+    if let Some(seed) = args.seed {
+        config.seed = Some(seed);
+    }
+    if let Some(budget) = args.memory_budget {
+        config.memory_budget = Some(budget);
+    }
+    if let Some(url) = args.webhook {
+        config.webhook = Some(url);
+    }
+    #[cfg(feature = "stats")]
+    if args.stats {
+        // allocations are counted for the whole process, so days solved side
+        // by side would be charged for each other's memory
+        config.jobs = Some(1);
+        stats::enable();
+    }
+
+    if let Some(name) = &args.plugin {
+        let dir = plugin::dir(&config)?;
+        let found = plugin::discover(&dir)?
+            .into_iter()
+            .find(|p| &p.name == name);
+        plugin::select(
+            found.ok_or_else(|| anyhow!("no plugin named {name} in {}", dir.display()))?,
+        );
+    }
+
+    if let Some(threads) = config.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()?;
+    }
+
+    let mut solve_context = SolveContext::default()
+        .with_overflow(config.overflow.unwrap_or_default().into())
+        .with_seed(config.seed.unwrap_or(aoc_core::random::DEFAULT_SEED));
+    if let Some(budget) = config.memory_budget {
+        solve_context = solve_context.with_memory_budget(budget);
+    }
+    let command = || match args.command {
+        Some(Command::Bench {
+            day,
+            input,
+            warmup,
+            iterations,
+            mode,
+            baseline,
+            max_regression,
+            save_baseline,
+            pin_cpu,
+            quiesce,
+        }) => {
+            let mode = mode.or(config.mode).unwrap_or_default();
+            run_bench(
+                day,
+                input.as_deref(),
+                mode,
+                args.algo.as_deref(),
+                BenchOptions {
+                    warmup,
+                    iterations: iterations as usize,
+                    baseline: baseline.as_deref(),
+                    max_regression,
+                    save_baseline: save_baseline.as_deref(),
+                    pin_cpu,
+                    quiesce,
+                },
+                &config,
+            )
+        }
+        Some(Command::Fetch { day, dir, force }) => run_fetch(day, dir, force, &config),
+        Some(Command::Countdown { fetch, dir }) => run_countdown(fetch, dir, &config),
+        Some(Command::Leaderboard { id }) => {
+            let board = Leaderboard::parse(&cache::load_or_fetch_leaderboard(id, &config)?)?;
+            println!("Advent of Code {} private leaderboard {id}\n", board.event);
+            println!("{}", leaderboard::render(&board));
+            Ok(())
+        }
+        Some(Command::Submit {
+            day,
+            part,
+            input,
+            retries,
+        }) => run_submit(
+            day,
+            part,
+            input.as_deref(),
+            args.algo.as_deref(),
+            retries,
+            &config,
+        ),
+        Some(Command::Completions { shell }) => {
+            clap_complete::generate(shell, &mut Args::command(), "aoc", &mut io::stdout());
+            Ok(())
+        }
+        Some(Command::Report {
+            day,
+            html,
+            output,
+            mode,
+        }) => run_report(
+            day,
+            html,
+            output,
+            mode.or(config.mode).unwrap_or_default(),
+            &config,
+        ),
+        Some(Command::Validate { day, input, format }) => run_validate(
+            day,
+            input.as_deref(),
+            format.or(config.format).unwrap_or_default(),
+            &config,
+        ),
+        Some(Command::Parse { day, input, format }) => run_parse(
+            day,
+            input.as_deref(),
+            format.or(config.format).unwrap_or_default(),
+            &config,
+        ),
+        Some(Command::Scramble {
+            day,
+            input,
+            output,
+            seed,
+        }) => run_scramble(day, input.as_deref(), output.as_deref(), seed, &config),
+        Some(Command::Verify { answers, day, mode }) => run_verify(
+            &answers,
+            day,
+            mode.or(config.mode).unwrap_or_default(),
+            args.algo.as_deref(),
+            &config,
+        ),
+        Some(Command::Serve {
+            address,
+            mode,
+            timeout,
+        }) => serve::run(
+            &address,
+            mode.or(config.mode).unwrap_or_default(),
+            args.algo.as_deref(),
+            timeout.map(Duration::from_secs),
+            solve_context.clone(),
+        ),
+        Some(Command::Rpc { mode, timeout }) => rpc::run(
+            mode.or(config.mode).unwrap_or_default(),
+            args.algo.as_deref(),
+            timeout.map(Duration::from_secs),
+            solve_context.clone(),
+        ),
+        #[cfg(feature = "grpc")]
+        Some(Command::Grpc {
+            address,
+            mode,
+            timeout,
+        }) => grpc::run(
+            &address,
+            mode.or(config.mode).unwrap_or_default(),
+            args.algo.as_deref(),
+            timeout.map(Duration::from_secs),
+            solve_context.clone(),
+        ),
+        Some(Command::NewDay { day }) => {
+            let dir = scaffold::new_day(day)?;
+            println!(
+                "created {}, fill in the example inputs and get solving",
+                dir.display()
+            );
+            Ok(())
+        }
+        Some(Command::Watch {
+            day,
+            input,
+            source,
+            full,
+            time,
+            mode,
+        }) => {
+            let mode = mode.or(config.mode).unwrap_or_default();
+            // only the built in solvers update incrementally
+            let incremental = match full || args.algo.is_some() || args.plugin.is_some() {
+                true => None,
+                false => days::incremental(day),
+            };
+            let resolve = match incremental {
+                Some(start) => watch::Resolve::Incremental(watch::Updating::new(start)),
+                None => watch::Resolve::Full(mode),
+            };
+            run_watch(
+                day,
+                input,
+                source,
+                resolve,
+                time,
+                args.algo.as_deref(),
+                &config,
+            )
+        }
+        #[cfg(feature = "profile")]
+        Some(Command::Profile {
+            day,
+            input,
+            part,
+            output,
+            seconds,
+            frequency,
+            mode,
+        }) => {
+            let solvers = lookup(
+                day,
+                mode.or(config.mode).unwrap_or_default(),
+                args.algo.as_deref(),
+            )?;
+            let text = read_input(day, input.as_deref(), &config)?;
+            let output = output.unwrap_or_else(|| format!("day{day}-flamegraph.svg").into());
+            let runs = profile::run(
+                &profile::parts(&solvers, part),
+                &text,
+                Duration::from_secs(seconds),
+                frequency,
+                &output,
+            )?;
+            println!(
+                "profiled {runs} runs of day {day}, wrote {}",
+                output.display()
+            );
+            Ok(())
+        }
+        Some(Command::List { format }) => run_list(format.or(config.format).unwrap_or_default()),
+        Some(Command::Animate {
+            day,
+            input,
+            example,
+            part,
+            fps,
+            output,
+        }) => run_animate(
+            day,
+            match (input.as_deref(), example) {
+                (Some(path), _) => Input::File(path),
+                (None, true) => Input::Example,
+                (None, false) => Input::Default,
+            },
+            part,
+            fps,
+            output.as_deref(),
+            args.algo.as_deref(),
+            &config,
+        ),
+        #[cfg(feature = "sqlite")]
+        Some(Command::Stats { day, format }) => {
+            run_stats(day, format.or(config.format).unwrap_or_default(), &config)
+        }
+        Some(Command::Plugins) => run_plugins(&config),
+        #[cfg(feature = "parquet")]
+        Some(Command::Parquet { day, output }) => run_parquet(day, &output, &config),
+        #[cfg(feature = "tui")]
+        Some(Command::Tui { mode }) => tui::run(mode.or(config.mode).unwrap_or_default(), &config),
+        None => {
+            let days = match args.day {
+                Some(days) => days,
+                None => calendar::puzzle_day(SystemTime::now())
+                    .map(|day| Selection(vec![day]))
+                    .ok_or_else(|| anyhow!("--day is required outside of December 1st-25th"))?,
+            };
+            let mode = match args.explain {
+                true => {
+                    explain::enable();
+                    config.jobs = Some(1);
+                    Mode::Sequential
+                }
+                false => args.mode.or(config.mode).unwrap_or_default(),
+            };
+            let format = match args.quiet {
+                true => Format::Raw,
+                false => args.format.or(config.format).unwrap_or_default(),
+            };
+            let inputs = batch::expand(&args.input)?;
+            let input = match (inputs.as_slice(), args.example) {
+                ([path], _) => Input::File(path),
+                ([], true) => Input::Example,
+                ([], false) => Input::Default,
+                (_, _) => {
+                    let [day] = days.0[..] else {
+                        return Err(anyhow!("--input can only be used with a single day"));
+                    };
+                    return run_batch(day, &inputs, mode, args.algo.as_deref(), format, &config);
+                }
+            };
+            run_days(
+                &days.0,
+                input,
+                mode,
+                args.algo.as_deref(),
+                format,
+                args.time,
+                &config,
+            )
+        }
+    };
+    context::scoped(&solve_context, command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_have_consistent_arguments() {
+        Args::command().debug_assert();
+    }
+}
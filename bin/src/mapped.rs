@@ -0,0 +1,100 @@
+use std::{fs::File, ops::Deref, path::Path};
+
+use anyhow::{Context, Result};
+use memmap2::Mmap;
+
+use crate::compress::Compression;
+
+/// uncompressed input files at least this big are mapped rather than read,
+/// smaller ones are cheaper to just read
+const MAP_AT_BYTES: u64 = 1 << 20;
+
+/// A puzzle input, either read into memory or, for large uncompressed
+/// files, mapped straight from disk so it's never copied onto the heap.
+/// Either way it derefs to the bytes, which the solvers take as they are;
+/// only what needs the input as text checks it's UTF-8.
+pub enum InputText {
+    Owned(Vec<u8>),
+    Mapped(Mmap),
+}
+
+impl Deref for InputText {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            InputText::Owned(bytes) => bytes,
+            InputText::Mapped(map) => map,
+        }
+    }
+}
+
+impl InputText {
+    /// the input as text, failing if it isn't UTF-8
+    pub fn text(&self) -> Result<&str> {
+        std::str::from_utf8(self).context("the input is not UTF-8")
+    }
+}
+
+impl From<Vec<u8>> for InputText {
+    fn from(bytes: Vec<u8>) -> InputText {
+        InputText::Owned(bytes)
+    }
+}
+
+impl From<String> for InputText {
+    fn from(text: String) -> InputText {
+        InputText::Owned(text.into_bytes())
+    }
+}
+
+/// map the input file at `path`, or `None` if it's small or compressed and
+/// is better read, or can't be mapped at all
+pub fn map(path: &Path) -> Result<Option<InputText>> {
+    // WASI has no mmap, so every input is read there
+    if cfg!(target_os = "wasi") {
+        return Ok(None);
+    }
+    let file = File::open(path).with_context(|| format!("unable to open {}", path.display()))?;
+    if file.metadata()?.len() < MAP_AT_BYTES {
+        return Ok(None);
+    }
+    // SAFETY: the map is only ever read, and inputs aren't written to while
+    // they're being solved. One that was would give the solvers a torn
+    // input, which is the risk taken for not copying large inputs.
+    let map =
+        unsafe { Mmap::map(&file) }.with_context(|| format!("unable to map {}", path.display()))?;
+    if Compression::detect(path, &map) != Compression::None {
+        return Ok(None);
+    }
+    Ok(Some(InputText::Mapped(map)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs, io::Write, process};
+
+    #[test]
+    fn should_map_only_large_plain_files() -> Result<()> {
+        let dir = env::temp_dir().join(format!("aoc-mapped-{}", process::id()));
+        fs::create_dir_all(&dir)?;
+
+        let small = dir.join("small.txt");
+        fs::write(&small, "1abc2\n")?;
+        assert!(map(&small)?.is_none());
+
+        let large = dir.join("large.txt");
+        let mut file = File::create(&large)?;
+        for _ in 0..(MAP_AT_BYTES / 6 + 1) {
+            file.write_all(b"1abc2\n")?;
+        }
+        drop(file);
+        let text = map(&large)?.expect("large files are mapped");
+        assert!(matches!(text, InputText::Mapped(_)));
+        assert!(text.starts_with(b"1abc2\n1abc2\n"));
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+use crate::output::Run;
+
+/// upper bounds of the solve duration histogram's buckets, in seconds, from
+/// the instant days up to ones that take a minute
+const BUCKETS: [f64; 8] = [0.0001, 0.001, 0.01, 0.1, 0.5, 1.0, 10.0, 60.0];
+
+/// How long a part has taken each time it was solved
+#[derive(Default)]
+struct Histogram {
+    /// how many solves fell in each bucket or a smaller one
+    buckets: [u64; BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        for (bucket, bound) in self.buckets.iter_mut().zip(BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+}
+
+#[derive(Default)]
+struct Recorded {
+    solves: BTreeMap<usize, u64>,
+    /// failed solves by day, or `unknown` for days without a solver, and why
+    errors: BTreeMap<(String, &'static str), u64>,
+    durations: BTreeMap<(usize, u8), Histogram>,
+    /// requests by endpoint and the status they were answered with
+    requests: BTreeMap<(&'static str, u16), u64>,
+}
+
+/// Counts of what a long running `serve` has done, for Prometheus to scrape
+#[derive(Default)]
+pub struct Metrics {
+    recorded: Mutex<Recorded>,
+}
+
+impl Metrics {
+    fn recorded(&self) -> MutexGuard<'_, Recorded> {
+        self.recorded
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// count a solve and how long each part took
+    pub fn solved(&self, run: &Run) {
+        let mut recorded = self.recorded();
+        *recorded.solves.entry(run.day).or_default() += 1;
+        for (part, duration) in [(1, run.timings.part1), (2, run.timings.part2)] {
+            recorded
+                .durations
+                .entry((run.day, part))
+                .or_default()
+                .observe(duration);
+        }
+    }
+
+    /// count a solve of `day` that failed for `reason`. Days without a
+    /// solver are counted together, so made up days can't add labels.
+    pub fn failed(&self, day: Option<usize>, reason: &'static str) {
+        let day = day.map_or_else(|| "unknown".to_string(), |day| day.to_string());
+        *self.recorded().errors.entry((day, reason)).or_default() += 1;
+    }
+
+    /// count a request to `endpoint` answered with `status`
+    pub fn request(&self, endpoint: &'static str, status: u16) {
+        *self
+            .recorded()
+            .requests
+            .entry((endpoint, status))
+            .or_default() += 1;
+    }
+
+    /// everything counted so far, in Prometheus' text format
+    pub fn render(&self) -> String {
+        let recorded = self.recorded();
+        let mut text = String::new();
+
+        header(
+            &mut text,
+            "aoc_solves_total",
+            "counter",
+            "Inputs solved, by day.",
+        );
+        for (day, count) in &recorded.solves {
+            let _ = writeln!(text, "aoc_solves_total{{day=\"{day}\"}} {count}");
+        }
+
+        header(
+            &mut text,
+            "aoc_solve_errors_total",
+            "counter",
+            "Solves that failed, by day and reason.",
+        );
+        for ((day, reason), count) in &recorded.errors {
+            let _ = writeln!(
+                text,
+                "aoc_solve_errors_total{{day=\"{day}\",reason=\"{reason}\"}} {count}"
+            );
+        }
+
+        header(
+            &mut text,
+            "aoc_solve_duration_seconds",
+            "histogram",
+            "How long each part took to solve.",
+        );
+        for ((day, part), histogram) in &recorded.durations {
+            let labels = format!("day=\"{day}\",part=\"{part}\"");
+            for (bound, count) in BUCKETS.iter().zip(histogram.buckets) {
+                let _ = writeln!(
+                    text,
+                    "aoc_solve_duration_seconds_bucket{{{labels},le=\"{bound}\"}} {count}"
+                );
+            }
+            let _ = writeln!(
+                text,
+                "aoc_solve_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {}\n\
+                 aoc_solve_duration_seconds_sum{{{labels}}} {}\n\
+                 aoc_solve_duration_seconds_count{{{labels}}} {}",
+                histogram.count, histogram.sum, histogram.count
+            );
+        }
+
+        header(
+            &mut text,
+            "aoc_http_requests_total",
+            "counter",
+            "Requests answered, by endpoint and status.",
+        );
+        for ((endpoint, status), count) in &recorded.requests {
+            let _ = writeln!(
+                text,
+                "aoc_http_requests_total{{endpoint=\"{endpoint}\",status=\"{status}\"}} {count}"
+            );
+        }
+        text
+    }
+}
+
+/// the lines describing a metric, which its samples follow
+fn header(text: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(text, "# HELP {name} {help}\n# TYPE {name} {kind}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Timings;
+    use aoc_core::answer::Answer;
+
+    #[test]
+    fn should_render_what_was_counted() {
+        let metrics = Metrics::default();
+        let run = Run {
+            day: 4,
+            part1: Answer::U64(13),
+            part2: Answer::U64(30),
+            timings: Timings {
+                input: Duration::ZERO,
+                parse: None,
+                part1: Duration::from_micros(50),
+                part2: Duration::from_millis(200),
+            },
+            memory: None,
+            input_hash: String::new(),
+        };
+        metrics.solved(&run);
+        metrics.solved(&run);
+        metrics.failed(Some(4), "failed");
+        metrics.failed(None, "no_solver");
+        metrics.request("solve", 200);
+
+        let text = metrics.render();
+        for line in [
+            "# TYPE aoc_solves_total counter",
+            "aoc_solves_total{day=\"4\"} 2",
+            "aoc_solve_errors_total{day=\"4\",reason=\"failed\"} 1",
+            "aoc_solve_errors_total{day=\"unknown\",reason=\"no_solver\"} 1",
+            "aoc_solve_duration_seconds_bucket{day=\"4\",part=\"1\",le=\"0.0001\"} 2",
+            "aoc_solve_duration_seconds_bucket{day=\"4\",part=\"2\",le=\"0.1\"} 0",
+            "aoc_solve_duration_seconds_bucket{day=\"4\",part=\"2\",le=\"0.5\"} 2",
+            "aoc_solve_duration_seconds_bucket{day=\"4\",part=\"2\",le=\"+Inf\"} 2",
+            "aoc_solve_duration_seconds_count{day=\"4\",part=\"2\"} 2",
+            "aoc_http_requests_total{endpoint=\"solve\",status=\"200\"} 1",
+        ] {
+            assert!(
+                text.lines().any(|l| l == line),
+                "{line} missing from\n{text}"
+            );
+        }
+    }
+}
@@ -0,0 +1,425 @@
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+use anyhow::Result;
+use aoc_core::{answer::Answer, diagnostic::Diagnostic, error::AocError};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::{
+    color::{self, paint},
+    exit,
+    stats::{self, Memory},
+};
+
+/// How the results of a run should be written to stdout
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// free-form, human readable lines
+    #[default]
+    Text,
+    /// a single JSON object per day
+    Json,
+    /// comma separated values, one row per day and part
+    Csv,
+    /// only the answers, one per line
+    Raw,
+    /// MessagePack, a map per day with the JSON format's fields, written one
+    /// after another for compact output from large batches
+    Msgpack,
+}
+
+/// Wall-clock time spent in each phase of a run
+#[derive(Serialize, Debug, Default, Clone, Copy)]
+pub struct Timings {
+    #[serde(rename = "input_ns", serialize_with = "as_nanos")]
+    pub input: Duration,
+    /// how long parsing took, when both parts were solved from one parse
+    #[serde(
+        rename = "parse_ns",
+        serialize_with = "some_as_nanos",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub parse: Option<Duration>,
+    #[serde(rename = "part1_ns", serialize_with = "as_nanos")]
+    pub part1: Duration,
+    #[serde(rename = "part2_ns", serialize_with = "as_nanos")]
+    pub part2: Duration,
+}
+
+impl Timings {
+    /// the time spent on every phase together
+    pub fn total(&self) -> Duration {
+        self.input + self.parse.unwrap_or_default() + self.part1 + self.part2
+    }
+}
+
+/// The answers and timings produced by solving a single day
+#[derive(Serialize, Debug, Clone)]
+pub struct Run {
+    pub day: usize,
+    pub part1: Answer,
+    pub part2: Answer,
+    pub timings: Timings,
+    /// peak heap of each phase, only measured with `--stats`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Memory>,
+    /// sha256 of the input the answers were computed from, as hex
+    #[serde(skip)]
+    pub input_hash: String,
+}
+
+/// an answer as a CSV field. Strings are quoted, since some answers are lists
+/// with commas in them.
+pub fn csv_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::String(answer) => format!("{answer:?}"),
+        answer => answer.to_string(),
+    }
+}
+
+/// a diagnostic as the JSON object `validate --format json` prints for it
+pub fn diagnostic_json(diagnostic: &Diagnostic) -> serde_json::Value {
+    serde_json::json!({
+        "severity": diagnostic.severity.to_string(),
+        "line": diagnostic.line,
+        "column": diagnostic.column,
+        "message": diagnostic.message,
+    })
+}
+
+/// `value` encoded as MessagePack, with maps keyed by field name like the
+/// JSON format rather than positional arrays
+pub fn msgpack<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    Ok(rmp_serde::to_vec_named(value)?)
+}
+
+/// write what a render produced to stdout as is, since MessagePack can't go
+/// through `println!`
+pub fn print(rendered: &[u8]) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(rendered)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn as_nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_nanos() as u64)
+}
+
+/// [`as_nanos`] for a duration that's only sometimes measured, and skipped
+/// when it isn't
+fn some_as_nanos<S: Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    as_nanos(&duration.unwrap_or_default(), serializer)
+}
+
+/// render a run in the requested format. Timings are only included in the
+/// text format when `time` is set, machine formats always carry them, and
+/// the parse time only when it was measured apart from the parts. Peak
+/// memory is included whenever it was measured.
+fn render_one(run: &Run, format: Format, time: bool, color: bool) -> Result<Vec<u8>> {
+    let answer = |answer: &Answer| paint(answer, color::ANSWER, color);
+    let badge = |elapsed: Duration| paint(format!("{elapsed:?}"), color::duration(elapsed), color);
+    let parse = match run.timings.parse {
+        Some(parse) => format!("parse: {}\n", badge(parse)),
+        None => String::new(),
+    };
+    let mut rendered = match format {
+        Format::Text if time => format!(
+            "input: {}\n{parse}part one: {} ({})\npart two: {} ({})",
+            badge(run.timings.input),
+            answer(&run.part1),
+            badge(run.timings.part1),
+            answer(&run.part2),
+            badge(run.timings.part2)
+        ),
+        Format::Text => format!(
+            "part one: {}\npart two: {}",
+            answer(&run.part1),
+            answer(&run.part2)
+        ),
+        Format::Json => serde_json::to_string(run)?,
+        Format::Csv => format!(
+            "{},1,{},{}\n{},2,{},{}",
+            run.day,
+            csv_answer(&run.part1),
+            run.timings.part1.as_nanos(),
+            run.day,
+            csv_answer(&run.part2),
+            run.timings.part2.as_nanos()
+        ),
+        Format::Raw => format!("{}\n{}", run.part1, run.part2),
+        Format::Msgpack => return msgpack(run),
+    };
+    match (format, run.memory) {
+        (Format::Text, Some(memory)) => {
+            let line = format!(
+                "peak heap: input {}, solve {}",
+                stats::human(memory.input),
+                stats::human(memory.solve)
+            );
+            rendered.push('\n');
+            rendered.push_str(&paint(line, color::DIM, color));
+        }
+        (Format::Csv, Some(memory)) => {
+            // both parts come from one solve, so their rows share its peak
+            let lines: Vec<&str> = rendered.lines().collect();
+            rendered = format!(
+                "{},{}\n{},{}",
+                lines[0], memory.solve, lines[1], memory.solve
+            );
+        }
+        _ => {}
+    }
+    Ok(rendered.into_bytes())
+}
+
+/// A day that failed to solve, as written in the JSON format
+#[derive(Serialize)]
+struct Failed {
+    day: usize,
+    error: AocError,
+}
+
+/// describe a day that failed to solve. The JSON format gets an object with
+/// the day, part and line of the input where it failed, when known. Errors
+/// from the day crates already say which day failed, other errors are
+/// prefixed with it.
+pub fn render_failure(day: usize, error: &anyhow::Error, format: Format) -> Result<String> {
+    let located = exit::aoc_error(error);
+    match format {
+        Format::Json => {
+            let error = match located {
+                Some(located) => located.clone(),
+                None => AocError::new(format!("{error:#}")).with_day(day),
+            };
+            Ok(serde_json::to_string(&Failed { day, error })?)
+        }
+        _ => match located.and_then(|located| located.day) {
+            Some(_) => Ok(error.to_string()),
+            None => Ok(format!("day {day}: {error}")),
+        },
+    }
+}
+
+/// render a collection of runs in the requested format, adding any header
+/// the format needs exactly once. `color` only affects the text format.
+/// Text formats end each line with a newline, MessagePack values follow each
+/// other directly.
+pub fn render(runs: &[Run], format: Format, time: bool, color: bool) -> Result<Vec<u8>> {
+    let mut out = vec![];
+    if format == Format::Csv {
+        match runs.iter().any(|run| run.memory.is_some()) {
+            true => writeln!(out, "day,part,answer,duration_ns,peak_bytes")?,
+            false => writeln!(out, "day,part,answer,duration_ns")?,
+        }
+    }
+    for run in runs {
+        // label each day's answers when there is more than one to tell apart
+        if format == Format::Text && runs.len() > 1 {
+            let label = paint(format!("day {}:", run.day), color::HEADER, color);
+            writeln!(out, "{label}")?;
+        }
+        out.extend(render_one(run, format, time, color)?);
+        if format != Format::Msgpack {
+            out.push(b'\n');
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a text format's render as a string
+    fn render(runs: &[Run], format: Format, time: bool, color: bool) -> Result<String> {
+        Ok(String::from_utf8(super::render(
+            runs, format, time, color,
+        )?)?)
+    }
+
+    fn run() -> Run {
+        Run {
+            day: 3,
+            part1: Answer::U64(4361),
+            part2: Answer::U64(467835),
+            timings: Timings {
+                input: Duration::from_nanos(10),
+                parse: None,
+                part1: Duration::from_nanos(200),
+                part2: Duration::from_nanos(300),
+            },
+            memory: None,
+            input_hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn should_render_json() -> Result<()> {
+        let result = render(&[run()], Format::Json, false, false)?;
+        let expected = r#"{"day":3,"part1":4361,"part2":467835,"timings":{"input_ns":10,"part1_ns":200,"part2_ns":300}}"#;
+        assert_eq!(result, format!("{expected}\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_the_parse_time_when_measured() -> Result<()> {
+        let mut parsed = run();
+        parsed.timings.parse = Some(Duration::from_nanos(50));
+        let text = render(&[parsed.clone()], Format::Text, true, false)?;
+        assert_eq!(
+            text,
+            "input: 10ns\nparse: 50ns\npart one: 4361 (200ns)\npart two: 467835 (300ns)\n"
+        );
+        let json = render(&[parsed], Format::Json, false, false)?;
+        assert!(json.contains(r#""timings":{"input_ns":10,"parse_ns":50,"part1_ns":200"#));
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_text() -> Result<()> {
+        let result = render(&[run()], Format::Text, false, false)?;
+        assert_eq!(result, "part one: 4361\npart two: 467835\n");
+        Ok(())
+    }
+
+    #[test]
+    fn should_label_days_in_text() -> Result<()> {
+        let mut other = run();
+        other.day = 4;
+        let result = render(&[run(), other], Format::Text, false, false)?;
+        let expected = "day 3:\npart one: 4361\npart two: 467835\n\
+                        day 4:\npart one: 4361\npart two: 467835\n";
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_csv_with_single_header() -> Result<()> {
+        let mut other = run();
+        other.day = 4;
+        let result = render(&[run(), other], Format::Csv, false, false)?;
+        let expected = "day,part,answer,duration_ns\n\
+                        3,1,4361,200\n\
+                        3,2,467835,300\n\
+                        4,1,4361,200\n\
+                        4,2,467835,300\n";
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn should_quote_string_answers_in_csv() -> Result<()> {
+        let mut words = run();
+        words.part1 = Answer::from("4,1,7");
+        words.part2 = Answer::I64(-2);
+        let result = render(&[words], Format::Csv, false, false)?;
+        let expected = "day,part,answer,duration_ns\n\
+                        3,1,\"4,1,7\",200\n\
+                        3,2,-2,300\n";
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_peak_memory_when_measured() -> Result<()> {
+        let mut measured = run();
+        measured.memory = Some(Memory {
+            input: 100,
+            solve: 3 << 20,
+        });
+        let text = render(&[measured.clone()], Format::Text, false, false)?;
+        assert!(text.ends_with("\npeak heap: input 100 B, solve 3.0 MiB\n"));
+
+        let csv = render(&[measured.clone()], Format::Csv, false, false)?;
+        assert_eq!(
+            csv,
+            "day,part,answer,duration_ns,peak_bytes\n3,1,4361,200,3145728\n3,2,467835,300,3145728\n"
+        );
+
+        let json = render(&[measured], Format::Json, false, false)?;
+        assert!(json.ends_with("\"memory\":{\"input_bytes\":100,\"solve_bytes\":3145728}}\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_color_text_only() -> Result<()> {
+        let text = render(&[run(), run()], Format::Text, true, true)?;
+        assert!(
+            text.starts_with("\u{1b}[1m\u{1b}[36mday 3:\u{1b}[0m\ninput: \u{1b}[32m10ns\u{1b}[0m")
+        );
+        assert!(text.contains("part one: \u{1b}[1m4361\u{1b}[0m (\u{1b}[32m200ns\u{1b}[0m)"));
+
+        let csv = render(&[run()], Format::Csv, true, true)?;
+        assert!(!csv.contains('\u{1b}'));
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_failures() -> Result<()> {
+        let located = AocError::new("malformatted card id")
+            .at_line(2)
+            .with_day(4)
+            .with_part(1);
+        let error = exit::Failure::Parse.tag(located.into());
+        assert_eq!(
+            render_failure(4, &error, Format::Text)?,
+            "day 4 part 1, line 2: malformatted card id"
+        );
+        assert_eq!(
+            render_failure(4, &error, Format::Json)?,
+            r#"{"day":4,"error":{"day":4,"part":1,"line":2,"message":"malformatted card id"}}"#
+        );
+
+        let error = anyhow::anyhow!("no input for day 9");
+        assert_eq!(
+            render_failure(9, &error, Format::Text)?,
+            "day 9: no input for day 9"
+        );
+        assert_eq!(
+            render_failure(9, &error, Format::Json)?,
+            r#"{"day":9,"error":{"day":9,"message":"no input for day 9"}}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_raw_answers_only() -> Result<()> {
+        let result = render(&[run(), run()], Format::Raw, true, false)?;
+        assert_eq!(result, "4361\n467835\n4361\n467835\n");
+        Ok(())
+    }
+
+    #[test]
+    fn should_render_msgpack_maps_one_after_another() -> Result<()> {
+        let mut other = run();
+        other.day = 4;
+        let rendered = super::render(&[run(), other], Format::Msgpack, true, true)?;
+
+        let mut reader = rendered.as_slice();
+        let mut days = vec![];
+        while !reader.is_empty() {
+            let value: serde_json::Value = rmp_serde::from_read(&mut reader)?;
+            days.push(value);
+        }
+        assert_eq!(days.len(), 2);
+        assert_eq!(
+            days[0],
+            serde_json::from_str::<serde_json::Value>(&render(
+                &[run()],
+                Format::Json,
+                false,
+                false
+            )?)?
+        );
+        assert_eq!(days[1]["day"], 4);
+        Ok(())
+    }
+}
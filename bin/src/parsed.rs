@@ -0,0 +1,168 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::Result;
+use aoc_core::{answer::Answer, persist::Persist, solver, strategy::ExecutionStrategy};
+
+use crate::{
+    cache,
+    days::{Day, Mode, Solver},
+    history,
+};
+
+/// set by `--parse-cache` or `parse_cache` in the config, and off otherwise
+/// so runs don't fill the cache directory unasked
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// solve from saved parses for the rest of the run
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// directory parsed inputs are saved in, next to the downloaded inputs
+fn dir() -> Result<PathBuf> {
+    Ok(cache::dir()?.join("parsed"))
+}
+
+/// what a build saves its parses under besides the input. A build from
+/// another revision, or a rebuild of this one, may parse into a different
+/// shape, so it doesn't read what this one saved.
+fn build() -> String {
+    let built = env::current_exe()
+        .and_then(fs::metadata)
+        .and_then(|meta| meta.modified())
+        .ok();
+    format!("{:?} {built:?}", history::REVISION)
+}
+
+/// where the parse of `text` for `day` is saved
+fn path(dir: &Path, day: usize, text: &str) -> PathBuf {
+    let key = history::hash(format!("{}\n{text}", build()));
+    dir.join(format!("day{day}-{key}.bin"))
+}
+
+/// `solvers`, changed to solve from the input's saved parse when the parse
+/// cache is on and the day can save one
+pub fn cached(day: usize, mode: Mode, solvers: Day) -> Day {
+    let persist = match ENABLED.load(Ordering::Relaxed) {
+        true => solver::find(day).and_then(|registration| registration.persist),
+        false => None,
+    };
+    let Some(persist) = persist else {
+        return solvers;
+    };
+    let strategy = ExecutionStrategy::from(mode);
+    Day {
+        part_one: part(day, 1, persist, strategy),
+        part_two: part(day, 2, persist, strategy),
+        both: None,
+    }
+}
+
+fn part(
+    day: usize,
+    part: u8,
+    persist: &'static dyn Persist,
+    strategy: ExecutionStrategy,
+) -> Solver {
+    Arc::new(move |input: &[u8]| {
+        let text = std::str::from_utf8(input)?;
+        solve(&dir()?, day, part, persist, text, strategy)
+    })
+}
+
+/// solve `part` from the saved parse of `text`, parsing and saving it first
+/// if it hasn't been. A saved parse that fails is parsed again, in case
+/// it's what was wrong, and the answer from that is the one given.
+fn solve(
+    dir: &Path,
+    day: usize,
+    part: u8,
+    persist: &dyn Persist,
+    text: &str,
+    strategy: ExecutionStrategy,
+) -> Result<Answer> {
+    let path = path(dir, day, text);
+    if let Ok(saved) = fs::read(&path) {
+        if let Ok(answer) = persist.solve_saved(&saved, part, strategy) {
+            return Ok(answer);
+        }
+        let _ = fs::remove_file(&path);
+    }
+    let saved = persist.save(text, strategy)?;
+    // a parse that can't be saved only costs the next run a parse
+    let _ = save(&path, &saved);
+    Ok(persist.solve_saved(&saved, part, strategy)?)
+}
+
+/// write `saved` to `path` all at once, so a run that's interrupted, or
+/// another one solving the same input, never reads half of it
+fn save(path: &Path, saved: &[u8]) -> Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+    let partial = path.with_extension(format!("{}.partial", process::id()));
+    fs::write(&partial, saved)?;
+    fs::rename(&partial, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_solve_every_example_from_its_saved_parse() -> Result<()> {
+        let strategy = ExecutionStrategy::Sequential;
+        for registration in solver::registered() {
+            let Some(persist) = registration.persist else {
+                continue;
+            };
+            let expected = (registration.expected)();
+            for (i, part) in [1, 2].into_iter().enumerate() {
+                let Some(expected) = &expected[i] else {
+                    continue;
+                };
+                let saved = persist.save(registration.examples[i], strategy)?;
+                assert_eq!(&persist.solve_saved(&saved, part, strategy)?, expected);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_solve_from_a_saved_parse() -> Result<()> {
+        let dir = env::temp_dir().join(format!("aoc-parsed-{}", process::id()));
+        let persist = solver::find(4)
+            .and_then(|r| r.persist)
+            .expect("day 4 persists");
+        let strategy = ExecutionStrategy::Sequential;
+
+        let answer = solve(&dir, 4, 1, persist, day4::PART_ONE_EXAMPLE, strategy)?;
+        assert_eq!(answer, Answer::U64(13));
+        let path = path(&dir, 4, day4::PART_ONE_EXAMPLE);
+        assert!(path.exists());
+
+        let answer = solve(&dir, 4, 2, persist, day4::PART_ONE_EXAMPLE, strategy)?;
+        assert_eq!(answer, Answer::U64(30));
+        assert_eq!(
+            persist.solve_saved(&fs::read(&path)?, 2, strategy)?,
+            Answer::U64(30)
+        );
+
+        // an unreadable parse is parsed again and replaced
+        fs::write(&path, b"not a parse")?;
+        let answer = solve(&dir, 4, 1, persist, day4::PART_ONE_EXAMPLE, strategy)?;
+        assert_eq!(answer, Answer::U64(13));
+        assert_ne!(fs::read(&path)?, b"not a parse");
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+//! External solvers, run as subprocesses that speak JSON.
+//!
+//! A plugin is any executable in the plugins directory. Version 1 of the
+//! protocol has two commands:
+//!
+//! - `<plugin> describe` prints `{"protocol": 1, "name": "...", "days": [4, ...]}`
+//! - `<plugin> solve` reads `{"day": 4, "part": 1, "input": "..."}` from stdin
+//!   and prints `{"answer": 13}` or `{"error": "..."}`. Answers may also be
+//!   negative numbers or strings
+//!
+//! Anything the plugin writes to stderr is passed through to the user.
+
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::{Arc, OnceLock},
+};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::answer::Answer;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::{
+    config::{self, Config},
+    days::Day,
+};
+
+/// the only protocol version there is so far
+pub const PROTOCOL: u32 = 1;
+
+/// What a plugin says about itself in answer to `describe`
+#[derive(Deserialize, Debug, PartialEq)]
+struct Description {
+    protocol: u32,
+    name: String,
+    days: Vec<usize>,
+}
+
+#[derive(Serialize)]
+struct Request<'a> {
+    day: usize,
+    part: u8,
+    input: &'a str,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Response {
+    Answer { answer: Answer },
+    Error { error: String },
+}
+
+/// An external solver discovered in the plugins directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plugin {
+    pub name: String,
+    pub days: Vec<usize>,
+    pub path: PathBuf,
+}
+
+impl Plugin {
+    /// ask the executable at `path` to describe itself
+    pub fn load(path: &Path) -> Result<Plugin> {
+        let output = Command::new(path)
+            .arg("describe")
+            .stderr(Stdio::inherit())
+            .output()
+            .with_context(|| format!("unable to run plugin {}", path.display()))?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "{} describe exited with {}",
+                path.display(),
+                output.status
+            ));
+        }
+        let description: Description = serde_json::from_slice(&output.stdout)
+            .with_context(|| format!("{} describe printed invalid json", path.display()))?;
+        if description.protocol != PROTOCOL {
+            return Err(anyhow!(
+                "{} speaks protocol {}, only {PROTOCOL} is supported",
+                path.display(),
+                description.protocol
+            ));
+        }
+        Ok(Plugin {
+            name: description.name,
+            days: description.days,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// solve one part of a day by running the plugin
+    pub fn solve(&self, day: usize, part: u8, input: &str) -> Result<Answer> {
+        let mut child = Command::new(&self.path)
+            .arg("solve")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("unable to run plugin {}", self.name))?;
+        let request = serde_json::to_vec(&Request { day, part, input })?;
+        // dropping stdin once it's written closes it, so the plugin sees the
+        // end of the request
+        child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("plugin {} has no stdin", self.name))?
+            .write_all(&request)?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "plugin {} exited with {}",
+                self.name,
+                output.status
+            ));
+        }
+        match serde_json::from_slice(&output.stdout)
+            .with_context(|| format!("plugin {} printed invalid json", self.name))?
+        {
+            Response::Answer { answer } => Ok(answer),
+            Response::Error { error } => Err(anyhow!(error)),
+        }
+    }
+
+    /// the plugin's solvers for `day`, if it has them
+    pub fn day(&self, day: usize) -> Option<Day> {
+        if !self.days.contains(&day) {
+            return None;
+        }
+        let part = |part| {
+            let plugin = self.clone();
+            // the input goes to the plugin as a JSON string, so it has to be text
+            Arc::new(move |input: &[u8]| plugin.solve(day, part, std::str::from_utf8(input)?))
+        };
+        Some(Day {
+            part_one: part(1),
+            part_two: part(2),
+            both: None,
+        })
+    }
+}
+
+/// the configured plugins directory, defaulting to `$XDG_DATA_HOME/aoc2023/plugins`
+pub fn dir(config: &Config) -> Result<PathBuf> {
+    match &config.plugins_dir {
+        Some(dir) => Ok(dir.clone()),
+        None => Ok(config::data_dir()
+            .context("unable to locate plugins directory")?
+            .join("plugins")),
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// every plugin in `dir`, sorted by name. A missing directory has no
+/// plugins, and executables that fail to describe themselves are skipped
+/// with a warning rather than hiding the rest.
+pub fn discover(dir: &Path) -> Result<Vec<Plugin>> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut plugins = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !is_executable(&path) {
+            continue;
+        }
+        match Plugin::load(&path) {
+            Ok(plugin) => plugins.push(plugin),
+            Err(e) => warn!("skipping plugin: {e:#}"),
+        }
+    }
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(plugins)
+}
+
+static SELECTED: OnceLock<Plugin> = OnceLock::new();
+
+/// dispatch every solve for the rest of the run to `plugin`
+pub fn select(plugin: Plugin) {
+    let _ = SELECTED.set(plugin);
+}
+
+/// the plugin picked with `--plugin`, if any
+pub fn selected() -> Option<&'static Plugin> {
+    SELECTED.get()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::{env, os::unix::fs::PermissionsExt, process};
+
+    const SCRIPT: &str = r#"#!/bin/sh
+case "$1" in
+    describe) echo '{"protocol": 1, "name": "counter", "days": [4]}' ;;
+    solve) wc -c | tr -d ' ' | sed 's/.*/{"answer": &}/' ;;
+esac
+"#;
+
+    #[test]
+    fn should_discover_and_run_plugins() -> Result<()> {
+        let dir = env::temp_dir().join(format!("aoc-plugins-{}", process::id()));
+        fs::create_dir_all(&dir)?;
+        let script = dir.join("counter");
+        fs::write(&script, SCRIPT)?;
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755))?;
+        // not executable, so not a plugin
+        fs::write(dir.join("README"), "")?;
+
+        let plugins = discover(&dir)?;
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].name, "counter");
+        assert_eq!(plugins[0].days, vec![4]);
+
+        assert!(plugins[0].day(5).is_none());
+        let day = plugins[0].day(4).ok_or(anyhow!("day 4 missing"))?;
+        // the request is {"day":4,"part":1,"input":"ab"}
+        assert_eq!((day.part_one)(b"ab")?, 31);
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
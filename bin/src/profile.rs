@@ -0,0 +1,54 @@
+use std::{
+    fs::File,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use pprof::ProfilerGuardBuilder;
+
+use crate::days::{Day, Solver};
+
+/// frames from these libraries are noise in the flamegraph and sampling
+/// inside some of them can deadlock
+const BLOCKLIST: [&str; 4] = ["libc", "libgcc", "pthread", "vdso"];
+
+/// the solvers to profile, both parts unless a single part was asked for
+pub fn parts(solvers: &Day, part: Option<u8>) -> Vec<Solver> {
+    match part {
+        Some(1) => vec![solvers.part_one.clone()],
+        Some(_) => vec![solvers.part_two.clone()],
+        None => vec![solvers.part_one.clone(), solvers.part_two.clone()],
+    }
+}
+
+/// Run the solvers over `input` again and again for at least `duration`
+/// while sampling the stack `frequency` times a second, then write the
+/// samples to `output` as a flamegraph SVG. Fast days finish in well under a
+/// sample interval, so looping is what gives the profile enough samples.
+/// Returns how many times the solvers ran.
+pub fn run(
+    solvers: &[Solver],
+    input: &[u8],
+    duration: Duration,
+    frequency: i32,
+    output: &Path,
+) -> Result<usize> {
+    let guard = ProfilerGuardBuilder::default()
+        .frequency(frequency)
+        .blocklist(&BLOCKLIST)
+        .build()?;
+
+    let start = Instant::now();
+    let mut runs = 0;
+    while runs == 0 || start.elapsed() < duration {
+        for solver in solvers {
+            solver(input)?;
+        }
+        runs += 1;
+    }
+
+    let report = guard.report().build()?;
+    report.flamegraph(File::create(output)?)?;
+    Ok(runs)
+}
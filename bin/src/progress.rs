@@ -0,0 +1,88 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use aoc_core::progress::{self, Progress};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+/// how long a part may run before we start drawing a progress bar for it
+const SHOW_AFTER: Duration = Duration::from_secs(1);
+
+/// cleared while several days are solved at once, since there is only one
+/// progress reporter for the whole process
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Draws an indicatif progress bar for a solver, but only once it has been
+/// running for longer than `SHOW_AFTER` so fast days don't flicker
+struct Bar {
+    bar: ProgressBar,
+    started: Instant,
+}
+
+impl Bar {
+    fn new(label: String) -> Bar {
+        let bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::hidden());
+        bar.set_style(
+            ProgressStyle::with_template("{prefix} {msg} [{bar:40}] {pos}/{len} ({eta})")
+                .unwrap_or_else(|_| ProgressStyle::default_bar())
+                .progress_chars("=> "),
+        );
+        bar.set_prefix(label);
+        Bar {
+            bar,
+            started: Instant::now(),
+        }
+    }
+
+    fn reveal_if_slow(&self) {
+        if self.bar.is_hidden() && self.started.elapsed() > SHOW_AFTER {
+            self.bar.set_draw_target(ProgressDrawTarget::stderr());
+        }
+    }
+}
+
+impl Progress for Bar {
+    fn start(&self, phase: &str, total: u64) {
+        self.bar.set_message(phase.to_string());
+        self.bar.set_length(total);
+        self.bar.set_position(0);
+        self.reveal_if_slow();
+    }
+
+    fn advance(&self, delta: u64) {
+        self.bar.inc(delta);
+        self.reveal_if_slow();
+    }
+
+    fn finish(&self) {
+        if !self.bar.is_hidden() {
+            self.bar
+                .finish_with_message(format!("done in {:?}", self.started.elapsed()));
+        }
+    }
+}
+
+impl Drop for Bar {
+    fn drop(&mut self) {
+        if !self.bar.is_finished() {
+            self.bar.finish_and_clear();
+        }
+    }
+}
+
+/// run a solver with a progress bar labelled `label`
+pub fn with_bar<T>(label: String, f: impl FnOnce() -> T) -> T {
+    match ENABLED.load(Ordering::Relaxed) {
+        true => progress::scoped(Arc::new(Bar::new(label)), f),
+        false => f(),
+    }
+}
+
+/// stop drawing progress bars for the rest of the run
+pub fn disable() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
@@ -0,0 +1,235 @@
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    fmt::Write as _,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::visualize::{Frame, Mark};
+use gif::{Encoder, Repeat};
+
+/// how big a cell of the grid is drawn in an SVG, in pixels
+const SVG_CELL_WIDTH: usize = 10;
+const SVG_CELL_HEIGHT: usize = 18;
+
+/// how many pixels square a cell of the grid is drawn in a GIF. GIFs have no
+/// text, so each cell is a block colored by what's in it.
+const GIF_CELL: usize = 6;
+
+/// the GIF's palette, indexed by [`Paint`]
+const PALETTE: [[u8; 3]; 6] = [
+    [0x0f, 0x0f, 0x23],
+    [0xcc, 0xcc, 0xcc],
+    [0x6b, 0x8c, 0xff],
+    [0xff, 0xd7, 0x00],
+    [0x00, 0xcc, 0x00],
+    [0xcc, 0x33, 0x33],
+];
+
+/// The color a cell is drawn in
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Paint {
+    Background,
+    Digit,
+    Symbol,
+    Current,
+    Accepted,
+    Rejected,
+}
+
+impl Paint {
+    fn of(frame: &Frame, row: usize, column: usize, cell: char) -> Paint {
+        match frame.mark(row, column) {
+            Some(Mark::Current) => Paint::Current,
+            Some(Mark::Accepted) => Paint::Accepted,
+            Some(Mark::Rejected) => Paint::Rejected,
+            None if cell == '.' || cell.is_whitespace() => Paint::Background,
+            None if cell.is_ascii_alphanumeric() => Paint::Digit,
+            None => Paint::Symbol,
+        }
+    }
+
+    fn hex(self) -> String {
+        let [r, g, b] = PALETTE[self as usize];
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+/// the width and height of the widest frame, in cells
+fn size(frames: &[Frame]) -> (usize, usize) {
+    frames.iter().fold((0, 0), |(width, height), frame| {
+        let columns = frame.rows.iter().map(|row| row.chars().count()).max();
+        (
+            width.max(columns.unwrap_or(0)),
+            height.max(frame.rows.len()),
+        )
+    })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// a frame as an SVG image: the grid in a monospace font over a background
+/// of its highlighted cells, with the annotations underneath
+pub fn svg(frame: &Frame) -> String {
+    let (columns, rows) = size(std::slice::from_ref(frame));
+    let lines = rows + frame.annotations.len();
+    let width = (columns * SVG_CELL_WIDTH).max(1);
+    let height = (lines * SVG_CELL_HEIGHT).max(1);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="{}">"#,
+        SVG_CELL_HEIGHT - 4
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        Paint::Background.hex()
+    );
+    // a cell highlighted more than once is drawn once, in its last mark
+    let mut drawn = HashSet::new();
+    for highlight in &frame.highlights {
+        if !drawn.insert((highlight.row, highlight.column)) {
+            continue;
+        }
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{SVG_CELL_WIDTH}" height="{SVG_CELL_HEIGHT}" fill="{}" fill-opacity="0.6"/>"#,
+            highlight.column * SVG_CELL_WIDTH,
+            highlight.row * SVG_CELL_HEIGHT,
+            Paint::of(frame, highlight.row, highlight.column, ' ').hex()
+        );
+    }
+    let text_lines = frame.rows.iter().chain(&frame.annotations);
+    for (line, text) in text_lines.enumerate() {
+        let length = text.chars().count() * SVG_CELL_WIDTH;
+        let _ = writeln!(
+            svg,
+            r#"<text x="0" y="{}" fill="{}" xml:space="preserve" textLength="{length}" lengthAdjust="spacingAndGlyphs">{}</text>"#,
+            (line + 1) * SVG_CELL_HEIGHT - 4,
+            Paint::Digit.hex(),
+            escape(text)
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// write `frames` as an animated GIF, `fps` a second, looping forever
+pub fn gif(frames: &[Frame], fps: u32, out: impl Write) -> Result<()> {
+    let (columns, rows) = size(frames);
+    let too_big = || anyhow!("a {columns}x{rows} grid is too big for a GIF");
+    let width = u16::try_from((columns * GIF_CELL).max(1)).map_err(|_| too_big())?;
+    let height = u16::try_from((rows * GIF_CELL).max(1)).map_err(|_| too_big())?;
+
+    let palette: Vec<u8> = PALETTE.concat();
+    let mut encoder = Encoder::new(out, width, height, &palette)?;
+    encoder.set_repeat(Repeat::Infinite)?;
+    // GIF delays are in hundredths of a second
+    let delay = (100 / fps.max(1)).max(1) as u16;
+
+    for frame in frames {
+        let mut pixels = vec![Paint::Background as u8; usize::from(width) * usize::from(height)];
+        for (row, cells) in frame.rows.iter().enumerate() {
+            for (column, cell) in cells.chars().enumerate() {
+                let paint = Paint::of(frame, row, column, cell) as u8;
+                for y in row * GIF_CELL..(row + 1) * GIF_CELL - 1 {
+                    let start = y * usize::from(width) + column * GIF_CELL;
+                    pixels[start..start + GIF_CELL - 1].fill(paint);
+                }
+            }
+        }
+        encoder.write_frame(&gif::Frame {
+            width,
+            height,
+            delay,
+            buffer: Cow::Owned(pixels),
+            ..gif::Frame::default()
+        })?;
+    }
+    Ok(())
+}
+
+/// the path of the `index`th of `count` SVGs written for `path`: `path`
+/// itself for a single frame, or numbered after its stem for several
+fn numbered(path: &Path, index: usize, count: usize) -> PathBuf {
+    if count == 1 {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let digits = count.to_string().len();
+    path.with_file_name(format!("{stem}-{:0digits$}.svg", index + 1))
+}
+
+/// write `frames` to `path`, as an animated GIF when it ends in `.gif` or a
+/// sequence of SVGs when it ends in `.svg`, returning the files written
+pub fn write(frames: &[Frame], path: &Path, fps: u32) -> Result<Vec<PathBuf>> {
+    let extension = path.extension().and_then(|e| e.to_str());
+    match extension {
+        Some("gif") => {
+            let file = File::create(path)
+                .with_context(|| format!("unable to create {}", path.display()))?;
+            gif(frames, fps, BufWriter::new(file))?;
+            Ok(vec![path.to_path_buf()])
+        }
+        Some("svg") => frames
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                let path = numbered(path, i, frames.len());
+                fs::write(&path, svg(frame))
+                    .with_context(|| format!("unable to write {}", path.display()))?;
+                Ok(path)
+            })
+            .collect(),
+        _ => Err(anyhow!(
+            "don't know how to render {}, use a .gif or .svg file",
+            path.display()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        Frame::new(vec!["12.".to_string(), "..*".to_string()])
+            .highlight(0, 0, Mark::Accepted)
+            .highlight(0, 1, Mark::Accepted)
+            .annotate("1 < 2 & 3")
+    }
+
+    #[test]
+    fn should_draw_highlights_and_escape_text_in_svg() {
+        let svg = svg(&frame());
+        assert!(svg.starts_with("<svg "));
+        assert_eq!(svg.matches(&Paint::Accepted.hex()).count(), 2);
+        assert!(svg.contains(">1 &lt; 2 &amp; 3</text>"));
+    }
+
+    #[test]
+    fn should_encode_a_looping_gif() -> Result<()> {
+        let mut out = vec![];
+        gif(&[frame(), frame()], 10, &mut out)?;
+        assert!(out.starts_with(b"GIF89a"));
+        // width and height, little endian, after the signature
+        assert_eq!(out[6..10], [18, 0, 12, 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_number_svgs_only_when_there_are_several() {
+        let path = Path::new("out/day3.svg");
+        assert_eq!(numbered(path, 0, 1), Path::new("out/day3.svg"));
+        assert_eq!(numbered(path, 8, 12), Path::new("out/day3-09.svg"));
+    }
+}
@@ -0,0 +1,161 @@
+use std::{fmt::Write, time::Duration};
+
+use aoc_core::solver;
+
+use crate::output::Run;
+
+/// The result of solving one day for the report, the error is kept as text
+pub type Outcome = (usize, Result<Run, String>);
+
+fn total(runs: &[Outcome]) -> Duration {
+    runs.iter()
+        .filter_map(|(_, run)| run.as_ref().ok())
+        .map(|run| run.timings.total())
+        .sum()
+}
+
+/// the puzzle's title, if the day's solver is registered
+fn title(day: usize) -> String {
+    solver::find(day)
+        .map(|registration| registration.solver.metadata().title.to_string())
+        .unwrap_or_default()
+}
+
+/// the cells of a row in the answers table, in the order of the header
+fn cells(outcome: &Outcome) -> Vec<String> {
+    match outcome {
+        (day, Ok(run)) => vec![
+            day.to_string(),
+            title(*day),
+            run.part1.to_string(),
+            run.part2.to_string(),
+            format!("{:?}", run.timings.input),
+            format!("{:?}", run.timings.part1),
+            format!("{:?}", run.timings.part2),
+        ],
+        (day, Err(e)) => vec![
+            day.to_string(),
+            title(*day),
+            format!("error: {e}"),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ],
+    }
+}
+
+const HEADER: [&str; 7] = [
+    "day",
+    "title",
+    "part one",
+    "part two",
+    "input",
+    "part one time",
+    "part two time",
+];
+
+fn summary(runs: &[Outcome]) -> String {
+    let solved = runs.iter().filter(|(_, run)| run.is_ok()).count();
+    format!(
+        "Solved {solved} of {} days in {:?}.",
+        runs.len(),
+        total(runs)
+    )
+}
+
+/// a markdown document with a table of each day's answers and timings
+pub fn markdown(runs: &[Outcome]) -> String {
+    let mut report = String::from("# Advent of Code 2023\n\n");
+    let _ = writeln!(report, "| {} |", HEADER.join(" | "));
+    let _ = writeln!(report, "|{}", "---:|".repeat(HEADER.len()));
+    for outcome in runs {
+        // pipes would end the cell early
+        let row: Vec<String> = cells(outcome)
+            .iter()
+            .map(|cell| cell.replace('|', "\\|"))
+            .collect();
+        let _ = writeln!(report, "| {} |", row.join(" | "));
+    }
+    let _ = writeln!(report, "\n{}", summary(runs));
+    report
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// a standalone html page with the same table as [`markdown`]
+pub fn html(runs: &[Outcome]) -> String {
+    let mut report = String::from(concat!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n",
+        "<title>Advent of Code 2023</title>\n",
+        "<style>table { border-collapse: collapse; } ",
+        "th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: right; } ",
+        ".error { color: #c00; }</style>\n",
+        "</head>\n<body>\n<h1>Advent of Code 2023</h1>\n<table>\n<tr>",
+    ));
+    for name in HEADER {
+        let _ = write!(report, "<th>{name}</th>");
+    }
+    report.push_str("</tr>\n");
+    for outcome in runs {
+        let class = match outcome.1 {
+            Ok(_) => "",
+            Err(_) => " class=\"error\"",
+        };
+        let _ = write!(report, "<tr{class}>");
+        for cell in cells(outcome) {
+            let _ = write!(report, "<td>{}</td>", escape(&cell));
+        }
+        report.push_str("</tr>\n");
+    }
+    let _ = write!(
+        report,
+        "</table>\n<p>{}</p>\n</body>\n</html>\n",
+        escape(&summary(runs))
+    );
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Timings;
+    use aoc_core::answer::Answer;
+
+    fn runs() -> Vec<Outcome> {
+        let run = Run {
+            day: 4,
+            part1: Answer::U64(13),
+            part2: Answer::U64(30),
+            timings: Timings {
+                input: Duration::from_micros(1),
+                parse: None,
+                part1: Duration::from_micros(2),
+                part2: Duration::from_micros(3),
+            },
+            memory: None,
+            input_hash: String::new(),
+        };
+        vec![(4, Ok(run)), (5, Err("no <input> | here".to_string()))]
+    }
+
+    #[test]
+    fn should_render_markdown_table() {
+        let report = markdown(&runs());
+        assert!(report.contains("| 4 | Scratchcards | 13 | 30 | 1µs | 2µs | 3µs |\n"));
+        assert!(report.contains("| 5 |  | error: no <input> \\| here |  |  |  |  |\n"));
+        assert!(report.ends_with("Solved 1 of 2 days in 6µs.\n"));
+    }
+
+    #[test]
+    fn should_escape_html() {
+        let report = html(&runs());
+        assert!(report.contains("<td>error: no &lt;input&gt; | here</td>"));
+        assert!(report.contains("<tr class=\"error\">"));
+    }
+}
@@ -0,0 +1,336 @@
+use std::{
+    io::{self, BufRead, Write},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use aoc_core::{context::SolveContext, solver};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{days, days::Mode, output, progress, serve::Solving};
+
+/// the request wasn't JSON
+const PARSE_ERROR: i64 = -32700;
+/// the JSON wasn't a JSON-RPC request
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// the day's solver failed, usually on an input it couldn't parse
+const SOLVE_FAILED: i64 = -32000;
+/// there's no solver or validator for the day
+const NO_SOLVER: i64 = -32001;
+/// the solve was abandoned once it took longer than `--timeout`
+const TIMED_OUT: i64 = -32002;
+
+/// What `solve` and `validate` are called with
+#[derive(Deserialize)]
+struct Puzzle {
+    day: usize,
+    input: String,
+}
+
+/// A JSON-RPC 2.0 server for editors, which keep one running and send it
+/// inputs as they're edited instead of starting the cli for each
+pub struct Rpc {
+    solving: Solving,
+}
+
+impl Rpc {
+    pub fn new(
+        mode: Mode,
+        algo: Option<&str>,
+        timeout: Option<Duration>,
+        context: SolveContext,
+    ) -> Rpc {
+        Rpc {
+            solving: Solving::new(mode, algo, timeout, context),
+        }
+    }
+
+    /// the response to a message, which is a request or a batch of them, or
+    /// `None` when nothing needs to be sent back because it was only
+    /// notifications
+    pub fn handle(&self, message: &str) -> Option<Value> {
+        let message: Value = match serde_json::from_str(message) {
+            Ok(message) => message,
+            Err(e) => {
+                return Some(error(
+                    Value::Null,
+                    PARSE_ERROR,
+                    format!("invalid JSON: {e}"),
+                ))
+            }
+        };
+        match message {
+            Value::Array(batch) if batch.is_empty() => {
+                Some(error(Value::Null, INVALID_REQUEST, "empty batch"))
+            }
+            Value::Array(batch) => {
+                let responses: Vec<Value> = batch
+                    .into_iter()
+                    .filter_map(|request| self.request(request))
+                    .collect();
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            request => self.request(request),
+        }
+    }
+
+    /// the response to one request, or `None` for a notification, which has
+    /// no id to answer to
+    fn request(&self, request: Value) -> Option<Value> {
+        let id = request.get("id").cloned();
+        let (Some("2.0"), Some(method)) = (
+            request.get("jsonrpc").and_then(Value::as_str),
+            request.get("method").and_then(Value::as_str),
+        ) else {
+            return Some(error(
+                id.unwrap_or_default(),
+                INVALID_REQUEST,
+                "expected a JSON-RPC 2.0 request",
+            ));
+        };
+        let params = request.get("params").cloned().unwrap_or_default();
+        let result = self.call(method, params);
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error(id, code, message),
+        })
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value, (i64, String)> {
+        match method {
+            "days" => Ok(solver::registered()
+                .into_iter()
+                .map(|registration| json!(registration.solver.metadata()))
+                .collect()),
+            "solve" => {
+                let puzzle = puzzle(params)?;
+                match self.solving.solve(puzzle.day, &puzzle.input) {
+                    Ok(run) => Ok(json!(run)),
+                    Err(reply) => {
+                        let code = match reply.status {
+                            404 => NO_SOLVER,
+                            503 => TIMED_OUT,
+                            _ => SOLVE_FAILED,
+                        };
+                        Err((code, reply.message().to_string()))
+                    }
+                }
+            }
+            "validate" => {
+                let puzzle = puzzle(params)?;
+                let validate = days::validator(puzzle.day).ok_or_else(|| {
+                    (
+                        NO_SOLVER,
+                        format!("Validator not implemented for day {}", puzzle.day),
+                    )
+                })?;
+                Ok(validate(&puzzle.input)
+                    .iter()
+                    .map(output::diagnostic_json)
+                    .collect())
+            }
+            "shutdown" => Ok(Value::Null),
+            _ => Err((METHOD_NOT_FOUND, format!("no method named {method:?}"))),
+        }
+    }
+}
+
+fn puzzle(params: Value) -> Result<Puzzle, (i64, String)> {
+    serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))
+}
+
+fn error(id: Value, code: i64, message: impl ToString) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.to_string() },
+    })
+}
+
+/// How a message was framed, which its response is framed the same way as
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Framing {
+    /// one message per line
+    Line,
+    /// a `Content-Length` header, a blank line and then the message, the way
+    /// the language server protocol sends them
+    Header,
+}
+
+/// the next message from `reader` and how it was framed, or `None` once the
+/// input has ended
+fn read_message(reader: &mut impl BufRead) -> Result<Option<(String, Framing)>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            break;
+        }
+    }
+    let Some(length) = header_length(&line)? else {
+        return Ok(Some((line.trim().to_string(), Framing::Line)));
+    };
+
+    // any other headers run up to the blank line before the message
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(anyhow!("input ended in the middle of a message's headers"));
+        }
+        if line.trim().is_empty() {
+            break;
+        }
+    }
+    let mut message = vec![0; length];
+    reader.read_exact(&mut message)?;
+    Ok(Some((String::from_utf8(message)?, Framing::Header)))
+}
+
+/// the length a `Content-Length` header gives, or `None` if `line` isn't one
+fn header_length(line: &str) -> Result<Option<usize>> {
+    let Some((name, value)) = line.split_once(':') else {
+        return Ok(None);
+    };
+    if !name.trim().eq_ignore_ascii_case("content-length") {
+        return Ok(None);
+    }
+    let length = value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("invalid Content-Length {:?}", value.trim()))?;
+    Ok(Some(length))
+}
+
+fn write_message(writer: &mut impl Write, message: &Value, framing: Framing) -> Result<()> {
+    let message = message.to_string();
+    match framing {
+        Framing::Line => writeln!(writer, "{message}")?,
+        Framing::Header => write!(writer, "Content-Length: {}\r\n\r\n{message}", message.len())?,
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// answer the messages read from `reader` on `writer` until the input ends or
+/// a `shutdown` request has been answered
+pub fn serve(rpc: &Rpc, reader: &mut impl BufRead, writer: &mut impl Write) -> Result<()> {
+    while let Some((message, framing)) = read_message(reader)? {
+        let shutdown = serde_json::from_str::<Value>(&message)
+            .is_ok_and(|message| message["method"] == "shutdown");
+        if let Some(response) = rpc.handle(&message) {
+            write_message(writer, &response, framing)?;
+        }
+        if shutdown {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// answer JSON-RPC requests on stdin and stdout until stdin is closed.
+/// Progress bars are turned off, since nobody is watching the terminal.
+pub fn run(
+    mode: Mode,
+    algo: Option<&str>,
+    timeout: Option<Duration>,
+    context: SolveContext,
+) -> Result<()> {
+    progress::disable();
+    let rpc = Rpc::new(mode, algo, timeout, context);
+    serve(&rpc, &mut io::stdin().lock(), &mut io::stdout().lock())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../day4/src/part1_example.txt");
+
+    fn rpc() -> Rpc {
+        Rpc::new(Mode::Sequential, None, None, SolveContext::default())
+    }
+
+    fn call(method: &str, params: Value) -> Value {
+        let request = json!({ "jsonrpc": "2.0", "id": 7, "method": method, "params": params });
+        rpc()
+            .handle(&request.to_string())
+            .expect("a request is answered")
+    }
+
+    #[test]
+    fn should_answer_requests() {
+        let solved = call("solve", json!({ "day": 4, "input": EXAMPLE }));
+        assert_eq!(solved["id"], 7);
+        assert_eq!(solved["result"]["part1"], 13);
+        assert_eq!(solved["result"]["part2"], 30);
+        assert!(solved["result"]["timings"]["part1_ns"].is_u64());
+
+        let validated = call("validate", json!({ "day": 4, "input": "Card 1: 1 | x\n" }));
+        assert_eq!(validated["result"][0]["line"], 1);
+        assert_eq!(validated["result"][0]["severity"], "error");
+
+        let days = call("days", Value::Null);
+        assert_eq!(days["result"][3]["title"], "Scratchcards");
+    }
+
+    #[test]
+    fn should_report_errors() {
+        let code = |response: Value| response["error"]["code"].as_i64();
+        assert_eq!(
+            code(call("solve", json!({ "day": 4, "input": "not a card" }))),
+            Some(SOLVE_FAILED)
+        );
+        assert_eq!(
+            code(call("solve", json!({ "day": 99, "input": "" }))),
+            Some(NO_SOLVER)
+        );
+        assert_eq!(
+            code(call("solve", json!({ "day": 4 }))),
+            Some(INVALID_PARAMS)
+        );
+        assert_eq!(code(call("answer", Value::Null)), Some(METHOD_NOT_FOUND));
+        assert_eq!(
+            code(rpc().handle("{").unwrap_or_default()),
+            Some(PARSE_ERROR)
+        );
+        assert_eq!(
+            code(rpc().handle(r#"{"id": 1}"#).unwrap_or_default()),
+            Some(INVALID_REQUEST)
+        );
+        // notifications aren't answered, even when they fail
+        assert_eq!(
+            rpc().handle(r#"{"jsonrpc": "2.0", "method": "answer"}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn should_answer_in_the_framing_asked_in() -> Result<()> {
+        let days = r#"{"jsonrpc":"2.0","id":1,"method":"days"}"#;
+        let shutdown = r#"{"jsonrpc":"2.0","id":2,"method":"shutdown"}"#;
+        let input = format!(
+            "{days}\n\nContent-Length: {}\r\nContent-Type: application/json\r\n\r\n{shutdown}{days}\n",
+            shutdown.len()
+        );
+        let mut output = vec![];
+        serve(&rpc(), &mut input.as_bytes(), &mut output)?;
+
+        let output = String::from_utf8(output)?;
+        let (line, framed) = output.split_once('\n').unwrap_or_default();
+        assert_eq!(serde_json::from_str::<Value>(line)?["id"], 1);
+        let response = r#"{"id":2,"jsonrpc":"2.0","result":null}"#;
+        // nothing after the shutdown is answered
+        assert_eq!(
+            framed,
+            format!("Content-Length: {}\r\n\r\n{response}", response.len())
+        );
+        Ok(())
+    }
+}
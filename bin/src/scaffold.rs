@@ -0,0 +1,109 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.tmpl");
+
+/// the workspace this binary was built from
+fn workspace_root() -> Result<PathBuf> {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| anyhow!("unable to locate the workspace root"))
+}
+
+fn render(template: &str, day: usize) -> String {
+    template.replace("{{day}}", &day.to_string())
+}
+
+/// add the new crate to the workspace members and shared dependencies
+fn add_to_workspace(manifest: &str, day: usize) -> Result<String> {
+    let start = manifest
+        .find("members = [")
+        .ok_or_else(|| anyhow!("workspace manifest has no members list"))?;
+    let end = start
+        + manifest[start..]
+            .find(']')
+            .ok_or_else(|| anyhow!("workspace members list is not closed"))?;
+    Ok(format!(
+        "{}, \"day{day}\"{}\n[workspace.dependencies.day{day}]\npath = \"day{day}\"\n",
+        &manifest[..end],
+        &manifest[end..],
+    ))
+}
+
+/// add the new crate as a dependency of the cli, after the last day, with
+/// the `serde` feature the parse cache needs
+fn add_to_bin(manifest: &str, day: usize) -> Result<String> {
+    let last = manifest
+        .lines()
+        .rfind(|line| {
+            line.strip_prefix("day")
+                .and_then(|rest| rest.split_once([' ', '.']))
+                .is_some_and(|(number, _)| number.parse::<usize>().is_ok())
+                && line.contains("workspace = true")
+        })
+        .ok_or_else(|| anyhow!("bin manifest has no day dependencies"))?;
+    let end = manifest.find(last).unwrap_or_default() + last.len();
+    Ok(format!(
+        "{}\nday{day} = {{ workspace = true, features = [\"serde\"] }}{}",
+        &manifest[..end],
+        &manifest[end..]
+    ))
+}
+
+fn edit(path: &Path, f: impl FnOnce(&str) -> Result<String>) -> Result<()> {
+    let text = fs::read_to_string(path)?;
+    fs::write(path, f(&text)?)?;
+    Ok(())
+}
+
+/// generate a `day<N>` crate from the template and add it to the workspace
+/// and the cli's dependencies, which is all it takes for the cli to find it
+pub fn new_day(day: usize) -> Result<PathBuf> {
+    let root = workspace_root()?;
+    let dir = root.join(format!("day{day}"));
+    if dir.exists() {
+        return Err(anyhow!("{} already exists", dir.display()));
+    }
+
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), render(CARGO_TEMPLATE, day))?;
+    fs::write(dir.join("src").join("lib.rs"), render(LIB_TEMPLATE, day))?;
+    fs::write(dir.join("src").join("part1_example.txt"), "")?;
+    fs::write(dir.join("src").join("part2_example.txt"), "")?;
+
+    edit(&root.join("Cargo.toml"), |text| add_to_workspace(text, day))?;
+    edit(&root.join("bin").join("Cargo.toml"), |text| {
+        add_to_bin(text, day)
+    })?;
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_add_workspace_member_and_dependency() -> Result<()> {
+        let manifest = "[workspace]\nmembers = [\"bin\", \"day1\"]\n\n[workspace.dependencies.day1]\npath = \"day1\"\n";
+        let updated = add_to_workspace(manifest, 2)?;
+        assert!(updated.contains("members = [\"bin\", \"day1\", \"day2\"]"));
+        assert!(updated.ends_with("[workspace.dependencies.day2]\npath = \"day2\"\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_wire_the_current_tree() -> Result<()> {
+        let bin = add_to_bin(include_str!("../Cargo.toml"), 26)?;
+        assert!(bin.contains(
+            "day4 = { workspace = true, features = [\"serde\"] }\n\
+             day26 = { workspace = true, features = [\"serde\"] }\n"
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,217 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use aoc_core::random::Rng;
+
+/// a seed that's different every run
+pub fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+/// `text`, a puzzle input for `day`, with its values scrambled but its shape
+/// kept, so it can be shared without sharing the input itself. The same seed
+/// always scrambles an input the same way.
+pub fn scramble(day: usize, text: &str, seed: u64) -> Result<String> {
+    let mut rng = Rng::new(seed);
+    let scrambled = match day {
+        1 => day1(text, &mut rng),
+        2 => day2(text, &mut rng)?,
+        3 => day3(text, &mut rng),
+        4 => day4(text, &mut rng),
+        _ => return Err(anyhow!("day {day} has no scrambler")),
+    };
+    Ok(scrambled)
+}
+
+/// keep `text`'s trailing newline, or lack of one
+fn lines(text: &str, scrambled: Vec<String>) -> String {
+    let mut joined = scrambled.join("\n");
+    if text.ends_with('\n') {
+        joined.push('\n');
+    }
+    joined
+}
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// letters that aren't in any digit word, so filler can't spell one out
+const FILLER: &[u8] = b"abcdjklmpqy";
+
+/// Every digit becomes another digit, every spelled out digit another
+/// spelled out digit and every other letter a letter that can't spell one,
+/// so each line keeps its calibration value's shape. Spelled out digits that
+/// overlap, as in `twone`, only keep the first.
+fn day1(text: &str, rng: &mut Rng) -> String {
+    let scrambled = text
+        .lines()
+        .map(|line| {
+            let mut scrambled = String::new();
+            let mut rest = line;
+            while let Some(c) = rest.chars().next() {
+                if let Some(word) = DIGIT_WORDS.iter().find(|word| rest.starts_with(*word)) {
+                    scrambled.push_str(rng.pick(&DIGIT_WORDS));
+                    rest = &rest[word.len()..];
+                    continue;
+                }
+                match c {
+                    '0'..='9' => scrambled.push(char::from(b'0' + rng.between(1, 9) as u8)),
+                    c if c.is_ascii_alphabetic() => scrambled.push(char::from(rng.pick(FILLER))),
+                    c => scrambled.push(c),
+                }
+                rest = &rest[c.len_utf8()..];
+            }
+            scrambled
+        })
+        .collect();
+    lines(text, scrambled)
+}
+
+/// the most cubes of each color part one allows
+fn day2_limit(color: &str) -> Option<u64> {
+    match color {
+        "red" => Some(12),
+        "green" => Some(13),
+        "blue" => Some(14),
+        _ => None,
+    }
+}
+
+/// Games keep their ids and how many draws each round has, but every count
+/// is redrawn on the same side of its color's part one limit and the draws in
+/// a round are shuffled, so the same games are possible.
+fn day2(text: &str, rng: &mut Rng) -> Result<String> {
+    let mut scrambled = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let (game, rounds) = line
+            .split_once(": ")
+            .ok_or_else(|| anyhow!("line {}: expected \"Game <id>: ...\"", i + 1))?;
+        let mut scrambled_rounds = vec![];
+        for round in rounds.split("; ") {
+            let mut draws = vec![];
+            for draw in round.split(", ") {
+                let (count, color) = draw
+                    .split_once(' ')
+                    .ok_or_else(|| anyhow!("line {}: expected \"<count> <color>\"", i + 1))?;
+                let count: u64 = count.parse()?;
+                let count = match day2_limit(color) {
+                    Some(limit) if count <= limit => rng.between(1, limit),
+                    Some(limit) => rng.between(limit + 1, count.max(limit + 6)),
+                    None => rng.between(1, count.max(1)),
+                };
+                draws.push(format!("{count} {color}"));
+            }
+            rng.shuffle(&mut draws);
+            scrambled_rounds.push(draws.join(", "));
+        }
+        scrambled.push(format!("{game}: {}", scrambled_rounds.join("; ")));
+    }
+    Ok(lines(text, scrambled))
+}
+
+const SYMBOLS: &[u8] = b"#+$/@=%&-";
+
+/// The schematic keeps its layout: digits stay digits, without new leading
+/// zeros, and symbols stay symbols, with `*`s left alone so the same cells
+/// can be gears.
+fn day3(text: &str, rng: &mut Rng) -> String {
+    let scrambled = text
+        .lines()
+        .map(|line| {
+            let mut previous = '.';
+            line.chars()
+                .map(|c| {
+                    let scrambled = match c {
+                        '0'..='9' if previous.is_ascii_digit() => {
+                            char::from(b'0' + rng.between(0, 9) as u8)
+                        }
+                        '1'..='9' => char::from(b'0' + rng.between(1, 9) as u8),
+                        '.' | '*' | '0' => c,
+                        c if c.is_ascii_punctuation() => char::from(rng.pick(SYMBOLS)),
+                        c => c,
+                    };
+                    previous = c;
+                    scrambled
+                })
+                .collect()
+        })
+        .collect();
+    lines(text, scrambled)
+}
+
+/// Every number on the cards is swapped for another with as many digits, the
+/// same swap throughout, so the same numbers match on every card and the
+/// columns still line up.
+fn day4(text: &str, rng: &mut Rng) -> String {
+    let mut swaps: [Vec<u64>; 2] = [(1..10).collect(), (10..100).collect()];
+    for swap in &mut swaps {
+        rng.shuffle(swap);
+    }
+    let swap = |token: &str| match token.parse::<u64>() {
+        Ok(n @ 1..=9) => swaps[0][n as usize - 1].to_string(),
+        Ok(n @ 10..=99) => swaps[1][n as usize - 10].to_string(),
+        _ => token.to_string(),
+    };
+
+    let scrambled = text
+        .lines()
+        .map(|line| match line.split_once(':') {
+            // splitting on single spaces keeps the runs of them that line
+            // the columns up
+            Some((card, numbers)) => {
+                let numbers: Vec<String> = numbers.split(' ').map(swap).collect();
+                format!("{card}:{}", numbers.join(" "))
+            }
+            None => line.to_string(),
+        })
+        .collect();
+    lines(text, scrambled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{self, Mode};
+
+    fn solve(day: usize, text: &str) -> [String; 2] {
+        let solvers = days::lookup(day, Mode::Sequential).expect("the day is registered");
+        [&solvers.part_one, &solvers.part_two].map(|part| {
+            part(text.as_bytes())
+                .expect("scrambled input solves")
+                .to_string()
+        })
+    }
+
+    #[test]
+    fn should_scramble_into_valid_inputs() -> Result<()> {
+        for day in 1..=4 {
+            let [example, _] = days::examples(day).expect("the day has examples");
+            let scrambled = scramble(day, example, 2023)?;
+            assert_ne!(scrambled, example, "day {day}");
+            assert_eq!(scrambled.lines().count(), example.lines().count());
+            assert_eq!(scramble(day, example, 2023)?, scrambled, "day {day}");
+            let validate = days::validator(day).expect("the day has a validator");
+            assert!(validate(&scrambled).is_empty(), "day {day}:\n{scrambled}");
+            solve(day, &scrambled);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_keep_the_answers_that_depend_only_on_structure() -> Result<()> {
+        let [example, _] = days::examples(2).expect("day 2 has examples");
+        let scrambled = scramble(2, example, 7)?;
+        assert_eq!(solve(2, &scrambled)[0], solve(2, example)[0]);
+
+        let [example, _] = days::examples(4).expect("day 4 has examples");
+        let scrambled = scramble(4, example, 7)?;
+        assert_eq!(solve(4, &scrambled), solve(4, example));
+        for (line, original) in scrambled.lines().zip(example.lines()) {
+            assert_eq!(line.len(), original.len());
+        }
+        Ok(())
+    }
+}
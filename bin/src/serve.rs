@@ -0,0 +1,379 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use aoc_core::context::{self, SolveContext};
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::{info, warn};
+
+use crate::{
+    days::Mode,
+    graphql::{self, AocSchema},
+    lookup,
+    metrics::Metrics,
+    output::Run,
+    solve_text,
+};
+
+/// largest puzzle input accepted, well above any real input
+const MAX_BODY_BYTES: u64 = 1 << 20;
+
+/// What a response carries
+#[derive(Debug, PartialEq)]
+pub enum Body {
+    Json(serde_json::Value),
+    /// metrics in Prometheus' text format
+    Metrics(String),
+}
+
+/// A response ready to send: a status code and a body
+#[derive(Debug, PartialEq)]
+pub struct Reply {
+    pub status: u16,
+    pub body: Body,
+}
+
+impl Reply {
+    fn json(status: u16, body: serde_json::Value) -> Reply {
+        Reply {
+            status,
+            body: Body::Json(body),
+        }
+    }
+
+    fn error(status: u16, message: impl ToString) -> Reply {
+        Reply::json(status, json!({ "error": message.to_string() }))
+    }
+
+    /// the message of an error reply
+    pub fn message(&self) -> &str {
+        match &self.body {
+            Body::Json(body) => body["error"].as_str().unwrap_or_default(),
+            Body::Metrics(_) => "",
+        }
+    }
+}
+
+/// How requests are solved, and the latest run of each day solved so far,
+/// which GraphQL clients can query after submitting its input
+pub struct Solving {
+    mode: Mode,
+    algo: Option<String>,
+    timeout: Option<Duration>,
+    context: SolveContext,
+    runs: Mutex<BTreeMap<usize, Run>>,
+    metrics: Metrics,
+}
+
+impl Solving {
+    /// solve with `mode` and `algo` under `context`, giving up on a solve
+    /// once it has taken longer than `timeout` or more than the context's
+    /// memory budget
+    pub fn new(
+        mode: Mode,
+        algo: Option<&str>,
+        timeout: Option<Duration>,
+        context: SolveContext,
+    ) -> Solving {
+        Solving {
+            mode,
+            algo: algo.map(str::to_string),
+            timeout,
+            context,
+            runs: Mutex::default(),
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// solve `text` as `day`'s input, giving up once it has taken longer than
+    /// the timeout, and keep the run as the day's latest
+    pub fn solve(&self, day: usize, text: &str) -> Result<Run, Reply> {
+        let solvers = lookup(day, self.mode, self.algo.as_deref()).map_err(|e| {
+            self.metrics.failed(None, "no_solver");
+            Reply::error(404, e)
+        })?;
+        let mut solve_context = SolveContext {
+            strategy: self.mode.into(),
+            ..self.context.clone()
+        };
+        if let Some(timeout) = self.timeout {
+            solve_context = solve_context.with_deadline(Instant::now() + timeout);
+        }
+        let run = match context::scoped(&solve_context, || {
+            solve_text(day, text.as_bytes(), &solvers)
+        }) {
+            Ok(run) => run,
+            Err(_) if solve_context.check().is_err() => {
+                self.metrics.failed(Some(day), "timeout");
+                return Err(Reply::error(
+                    503,
+                    format!("solving took longer than {:?}", self.timeout),
+                ));
+            }
+            Err(e) => {
+                self.metrics.failed(Some(day), "failed");
+                return Err(Reply::error(422, e));
+            }
+        };
+        self.metrics.solved(&run);
+        self.runs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(day, run.clone());
+        Ok(run)
+    }
+
+    /// the latest run of `day`, if its input has been solved
+    pub fn latest(&self, day: usize) -> Option<Run> {
+        self.runs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&day)
+            .cloned()
+    }
+}
+
+/// The endpoints, and the state they share between requests
+pub struct Service {
+    solving: Arc<Solving>,
+    schema: AocSchema,
+}
+
+impl Service {
+    /// solve with `mode` and `algo` under `context`, giving up on a solve
+    /// once it has taken longer than `timeout` or more than the context's
+    /// memory budget
+    pub fn new(
+        mode: Mode,
+        algo: Option<&str>,
+        timeout: Option<Duration>,
+        context: SolveContext,
+    ) -> Service {
+        let solving = Arc::new(Solving::new(mode, algo, timeout, context));
+        Service {
+            schema: graphql::schema(solving.clone()),
+            solving,
+        }
+    }
+
+    /// route a request to the matching endpoint: `POST /solve/{day}` solves
+    /// the puzzle text in the body, `POST /graphql` runs a GraphQL query and
+    /// `GET /metrics` gives what's been counted for Prometheus
+    pub fn handle(&self, method: &Method, url: &str, body: &str) -> Reply {
+        let path = url.split('?').next().unwrap_or_default();
+        let (endpoint, reply) = match path {
+            "/graphql" => ("graphql", self.graphql(method, body)),
+            "/metrics" => ("metrics", self.metrics(method)),
+            _ if path.starts_with("/solve/") => ("solve", self.solve(method, path, body)),
+            _ => (
+                "other",
+                Reply::error(404, format!("no such endpoint {path}")),
+            ),
+        };
+        self.solving.metrics.request(endpoint, reply.status);
+        reply
+    }
+
+    fn solve(&self, method: &Method, path: &str, body: &str) -> Reply {
+        let day = path.trim_start_matches("/solve/");
+        if *method != Method::Post {
+            return Reply::error(405, "use POST with the puzzle input as the body");
+        }
+        let Ok(day) = day.parse::<usize>() else {
+            return Reply::error(404, format!("{day:?} is not a day"));
+        };
+        match self.solving.solve(day, body) {
+            Ok(run) => Reply::json(200, json!(run)),
+            Err(reply) => reply,
+        }
+    }
+
+    fn metrics(&self, method: &Method) -> Reply {
+        if *method != Method::Get {
+            return Reply::error(405, "use GET to scrape metrics");
+        }
+        Reply {
+            status: 200,
+            body: Body::Metrics(self.solving.metrics.render()),
+        }
+    }
+
+    /// run the GraphQL request in the body. Errors in the query itself are
+    /// reported in the response's `errors`, as GraphQL clients expect.
+    fn graphql(&self, method: &Method, body: &str) -> Reply {
+        if *method != Method::Post {
+            return Reply::error(405, "use POST with a GraphQL request as the body");
+        }
+        let request: async_graphql::Request = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(e) => return Reply::error(400, format!("invalid GraphQL request: {e}")),
+        };
+        let response = pollster::block_on(self.schema.execute(request));
+        match serde_json::to_value(response) {
+            Ok(body) => Reply::json(200, body),
+            Err(e) => Reply::error(500, format!("unable to encode the response: {e}")),
+        }
+    }
+}
+
+fn respond(mut request: Request, service: &Service) -> Result<()> {
+    let mut body = String::new();
+    let reply = match request.body_length() {
+        Some(length) if length as u64 > MAX_BODY_BYTES => {
+            Reply::error(413, "puzzle input is too large")
+        }
+        _ => match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => service.handle(request.method(), request.url(), &body),
+            Err(e) => Reply::error(400, format!("unable to read body: {e}")),
+        },
+    };
+    info!(method = %request.method(), url = request.url(), status = reply.status, "handled request");
+
+    let (content_type, body) = match reply.body {
+        Body::Json(body) => ("application/json", body.to_string()),
+        Body::Metrics(text) => ("text/plain; version=0.0.4", text),
+    };
+    let content_type = Header::from_bytes("Content-Type", content_type)
+        .map_err(|_| anyhow!("invalid content type header"))?;
+    let response = Response::from_string(body)
+        .with_status_code(reply.status)
+        .with_header(content_type);
+    request.respond(response)?;
+    Ok(())
+}
+
+/// serve the solvers over HTTP on `address` until the process is stopped.
+/// Requests are handled one at a time, in the order they arrive, and each
+/// may take up to `timeout` to solve and as much memory as `context`'s
+/// budget allows.
+pub fn run(
+    address: &str,
+    mode: Mode,
+    algo: Option<&str>,
+    timeout: Option<Duration>,
+    context: SolveContext,
+) -> Result<()> {
+    let server =
+        Server::http(address).map_err(|e| anyhow!("unable to listen on {address}: {e}"))?;
+    println!("listening on http://{}", server.server_addr());
+
+    let service = Service::new(mode, algo, timeout, context);
+    for request in server.incoming_requests() {
+        if let Err(e) = respond(request, &service) {
+            warn!("unable to respond to request: {e}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../day4/src/part1_example.txt");
+
+    fn service() -> Service {
+        Service::new(Mode::Sequential, None, None, SolveContext::default())
+    }
+
+    fn json(reply: Reply) -> serde_json::Value {
+        match reply.body {
+            Body::Json(body) => body,
+            Body::Metrics(text) => panic!("expected JSON, got {text}"),
+        }
+    }
+
+    #[test]
+    fn should_solve_posted_input() {
+        let reply = service().handle(&Method::Post, "/solve/4", EXAMPLE);
+        assert_eq!(reply.status, 200);
+        let body = json(reply);
+        assert_eq!(body["part1"], 13);
+        assert_eq!(body["part2"], 30);
+    }
+
+    #[test]
+    fn should_reject_bad_requests() {
+        let service = service();
+        let status = |method, url, body| service.handle(&method, url, body).status;
+        assert_eq!(status(Method::Get, "/solve/4", EXAMPLE), 405);
+        assert_eq!(status(Method::Post, "/answers", EXAMPLE), 404);
+        assert_eq!(status(Method::Post, "/solve/99", EXAMPLE), 404);
+        assert_eq!(status(Method::Post, "/solve/4", "not a card"), 422);
+        assert_eq!(status(Method::Get, "/graphql", "{}"), 405);
+        assert_eq!(status(Method::Post, "/graphql", "not json"), 400);
+        assert_eq!(status(Method::Post, "/metrics", ""), 405);
+    }
+
+    #[test]
+    fn should_count_requests_in_metrics() {
+        let service = service();
+        service.handle(&Method::Post, "/solve/4", EXAMPLE);
+        service.handle(&Method::Post, "/solve/4", "not a card");
+        service.handle(&Method::Post, "/solve/99", EXAMPLE);
+
+        let reply = service.handle(&Method::Get, "/metrics", "");
+        assert_eq!(reply.status, 200);
+        let Body::Metrics(text) = reply.body else {
+            panic!("expected metrics, got {:?}", reply.body);
+        };
+        for line in [
+            "aoc_solves_total{day=\"4\"} 1",
+            "aoc_solve_errors_total{day=\"4\",reason=\"failed\"} 1",
+            "aoc_solve_errors_total{day=\"unknown\",reason=\"no_solver\"} 1",
+            "aoc_solve_duration_seconds_count{day=\"4\",part=\"1\"} 1",
+            "aoc_http_requests_total{endpoint=\"solve\",status=\"200\"} 1",
+            "aoc_http_requests_total{endpoint=\"solve\",status=\"404\"} 1",
+        ] {
+            assert!(
+                text.lines().any(|l| l == line),
+                "{line} missing from\n{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_answer_graphql_queries() {
+        let service = service();
+        let graphql = |query: &str, variables: serde_json::Value| {
+            let body = json!({ "query": query, "variables": variables }).to_string();
+            json(service.handle(&Method::Post, "/graphql", &body))
+        };
+
+        let submitted = graphql(
+            "mutation($input: String!) { submitInput(day: 4, input: $input) { part1 part2 } }",
+            json!({ "input": EXAMPLE }),
+        );
+        assert_eq!(
+            submitted["data"]["submitInput"],
+            json!({ "part1": "13", "part2": "30" })
+        );
+
+        let day = graphql(
+            "{ day(day: 4) { title run { part2 timings { part1Ns } } } }",
+            json!({}),
+        );
+        assert_eq!(day["data"]["day"]["title"], "Scratchcards");
+        assert_eq!(day["data"]["day"]["run"]["part2"], "30");
+        assert!(day["data"]["day"]["run"]["timings"]["part1Ns"].is_u64());
+
+        // solves over REST are the day's latest run too
+        service.handle(&Method::Post, "/solve/4", "Card 1: 1 | 1\n");
+        let day = graphql("{ days { day run { part1 } } }", json!({}));
+        let days = day["data"]["days"].as_array().cloned().unwrap_or_default();
+        assert!(days.contains(&json!({ "day": 4, "run": { "part1": "1" } })));
+        assert!(days.contains(&json!({ "day": 1, "run": null })));
+
+        let failed = graphql(
+            "mutation { submitInput(day: 4, input: \"not a card\") { part1 } }",
+            json!({}),
+        );
+        assert!(failed["errors"][0]["message"]
+            .as_str()
+            .is_some_and(|message| message.contains("day 4")));
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(feature = "stats")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
+
+use serde::Serialize;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// bytes allocated since counting was enabled, which goes negative when
+/// memory allocated before then is freed
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+/// The system allocator, counting live heap bytes once [`enable`] has been
+/// called so [`measure`] can report the peak of each phase of a run. Every
+/// allocation and free pays for a relaxed load checking whether counting is
+/// enabled, and two atomic updates once it is, so it's only installed when
+/// the cli is built with the `stats` feature.
+#[cfg(feature = "stats")]
+pub struct Counting;
+
+#[cfg(feature = "stats")]
+fn record(delta: isize) {
+    if ENABLED.load(Ordering::Relaxed) {
+        let now = CURRENT.fetch_add(delta, Ordering::Relaxed) + delta;
+        PEAK.fetch_max(now, Ordering::Relaxed);
+    }
+}
+
+#[cfg(feature = "stats")]
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            record(new_size as isize - layout.size() as isize);
+        }
+        new
+    }
+}
+
+/// start counting allocations, for the rest of the process
+#[cfg(feature = "stats")]
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// run a closure, returning its result along with the most heap it had
+/// allocated at any one time, or `None` when counting isn't enabled, which
+/// it never is without the `stats` feature.
+/// Allocations from every thread count, so phases mustn't overlap.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<usize>) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return (f(), None);
+    }
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let result = f();
+    let peak = PEAK.load(Ordering::Relaxed) - baseline;
+    (result, Some(peak.max(0) as usize))
+}
+
+/// Peak heap allocated in each phase of a run, in bytes
+#[derive(Serialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Memory {
+    #[serde(rename = "input_bytes")]
+    pub input: usize,
+    /// parsing and solving both parts, which share what was parsed
+    #[serde(rename = "solve_bytes")]
+    pub solve: usize,
+}
+
+/// a byte count in binary units, e.g. `1.5 MiB`
+pub fn human(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "stats")]
+    #[test]
+    fn should_measure_peak_heap() {
+        enable();
+        let (len, peak) = measure(|| {
+            let big = vec![0u8; 1 << 20];
+            drop(big);
+            vec![0u8; 1024].len()
+        });
+        assert_eq!(len, 1024);
+        // other tests allocate and free concurrently, so allow some slack
+        assert!(peak.is_some_and(|peak| peak >= 1 << 19));
+    }
+
+    #[test]
+    fn should_format_bytes() {
+        assert_eq!(human(512), "512 B");
+        assert_eq!(human(1536), "1.5 KiB");
+        assert_eq!(human(3 << 20), "3.0 MiB");
+    }
+}
@@ -0,0 +1,271 @@
+use std::{fs, io::Write as _, path::PathBuf, time::Duration};
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use crate::{
+    config::{self, Config},
+    history,
+    output::{self, Format, Run},
+};
+
+/// the configured timings database, defaulting to
+/// `$XDG_DATA_HOME/aoc2023/timings.sqlite3` (or `~/.local/share/aoc2023/timings.sqlite3`)
+pub fn path(config: &Config) -> Result<PathBuf> {
+    if let Some(path) = &config.timings_file {
+        return Ok(path.clone());
+    }
+    Ok(config::data_dir()
+        .context("unable to locate timings database")?
+        .join("timings.sqlite3"))
+}
+
+/// open the timings database, creating it if it doesn't exist yet
+pub fn open(config: &Config) -> Result<Connection> {
+    let path = path(config)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let connection =
+        Connection::open(&path).with_context(|| format!("unable to open {}", path.display()))?;
+    create(&connection)?;
+    Ok(connection)
+}
+
+pub fn create(connection: &Connection) -> Result<()> {
+    connection.execute_batch(
+        "CREATE TABLE IF NOT EXISTS timings (
+            timestamp INTEGER NOT NULL,
+            day INTEGER NOT NULL,
+            part INTEGER NOT NULL,
+            duration_ns INTEGER NOT NULL,
+            input_sha256 TEXT NOT NULL,
+            revision TEXT
+        );
+        CREATE INDEX IF NOT EXISTS timings_by_day ON timings (day, part, timestamp);",
+    )?;
+    Ok(())
+}
+
+/// insert a row per part of each run, all in one transaction
+pub fn record(connection: &mut Connection, runs: &[Run], timestamp: u64) -> Result<()> {
+    let transaction = connection.transaction()?;
+    {
+        let mut insert = transaction.prepare(
+            "INSERT INTO timings (timestamp, day, part, duration_ns, input_sha256, revision)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for run in runs {
+            for entry in history::entries(run, timestamp) {
+                insert.execute(params![
+                    entry.timestamp,
+                    entry.day,
+                    entry.part,
+                    entry.duration_ns,
+                    entry.input_sha256,
+                    entry.revision,
+                ])?;
+            }
+        }
+    }
+    transaction.commit()?;
+    Ok(())
+}
+
+/// How one part's runtime looked at one revision of the code
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Trend {
+    pub day: usize,
+    pub part: u8,
+    /// `None` for builds from outside a git checkout
+    pub revision: Option<String>,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    /// how much the median changed from the previous revision's, in percent
+    pub change: Option<f64>,
+}
+
+/// every part's runtimes grouped by revision, oldest revision first. Only
+/// runs against each day's most recently timed input count, since a
+/// different input takes a different time.
+pub fn trends(connection: &Connection, days: Option<&[usize]>) -> Result<Vec<Trend>> {
+    let mut select = connection.prepare(
+        "SELECT day, part, revision, duration_ns FROM timings AS t
+         WHERE input_sha256 = (
+             SELECT input_sha256 FROM timings WHERE day = t.day
+             ORDER BY timestamp DESC, rowid DESC LIMIT 1
+         )
+         ORDER BY day, part, timestamp, rowid",
+    )?;
+    let rows = select.query_map([], |row| {
+        Ok((
+            row.get::<_, usize>(0)?,
+            row.get::<_, u8>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, u64>(3)?,
+        ))
+    })?;
+
+    let mut trends: Vec<Trend> = vec![];
+    let mut durations: Vec<Vec<u64>> = vec![];
+    for row in rows {
+        let (day, part, revision, duration) = row?;
+        if days.is_some_and(|days| !days.contains(&day)) {
+            continue;
+        }
+        // a revision that comes back after another is a new entry, so going
+        // back and forth between two shows up
+        match trends.last() {
+            Some(last) if (last.day, last.part, &last.revision) == (day, part, &revision) => {}
+            _ => {
+                trends.push(Trend {
+                    day,
+                    part,
+                    revision,
+                    runs: 0,
+                    min_ns: 0,
+                    median_ns: 0,
+                    change: None,
+                });
+                durations.push(vec![]);
+            }
+        }
+        if let Some(last) = durations.last_mut() {
+            last.push(duration);
+        }
+    }
+
+    for i in 0..trends.len() {
+        let samples = &mut durations[i];
+        samples.sort_unstable();
+        trends[i].runs = samples.len();
+        trends[i].min_ns = samples[0];
+        trends[i].median_ns = samples[samples.len() / 2];
+        if i > 0 && (trends[i - 1].day, trends[i - 1].part) == (trends[i].day, trends[i].part) {
+            let previous = trends[i - 1].median_ns as f64;
+            trends[i].change = Some((trends[i].median_ns as f64 - previous) / previous * 100.0);
+        }
+    }
+    Ok(trends)
+}
+
+/// the trends as text, a block per part, or as machine readable rows
+pub fn render(trends: &[Trend], format: Format) -> Result<Vec<u8>> {
+    let mut out = vec![];
+    let mut part = None;
+    for trend in trends {
+        let revision = trend.revision.as_deref().unwrap_or("unknown");
+        let median = Duration::from_nanos(trend.median_ns);
+        match format {
+            Format::Text => {
+                if part != Some((trend.day, trend.part)) {
+                    writeln!(out, "day {} part {}", trend.day, trend.part)?;
+                    part = Some((trend.day, trend.part));
+                }
+                write!(
+                    out,
+                    "  {revision:<12} {:>4} runs  min {:?}  median {median:?}",
+                    trend.runs,
+                    Duration::from_nanos(trend.min_ns)
+                )?;
+                match trend.change {
+                    Some(change) => writeln!(out, "  {change:+.1}%")?,
+                    None => writeln!(out)?,
+                }
+            }
+            Format::Json => writeln!(out, "{}", serde_json::to_string(trend)?)?,
+            Format::Csv => writeln!(
+                out,
+                "{},{},{revision},{},{},{},{}",
+                trend.day,
+                trend.part,
+                trend.runs,
+                trend.min_ns,
+                trend.median_ns,
+                trend
+                    .change
+                    .map_or(String::new(), |change| format!("{change:.1}"))
+            )?,
+            Format::Raw => writeln!(out, "{}", trend.median_ns)?,
+            Format::Msgpack => out.extend(output::msgpack(trend)?),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::answer::Answer;
+    use rusqlite::params;
+
+    fn insert(connection: &Connection, day: usize, part: u8, revision: &str, durations: &[u64]) {
+        for duration in durations {
+            connection
+                .execute(
+                    "INSERT INTO timings VALUES (0, ?1, ?2, ?3, 'abc', ?4)",
+                    params![day, part, duration, revision],
+                )
+                .expect("an insert");
+        }
+    }
+
+    #[test]
+    fn should_track_the_median_across_revisions() -> Result<()> {
+        let connection = Connection::open_in_memory()?;
+        create(&connection)?;
+        insert(&connection, 3, 1, "aaaaaaa", &[100, 300, 200]);
+        insert(&connection, 3, 1, "bbbbbbb", &[150, 160]);
+        insert(&connection, 4, 1, "bbbbbbb", &[10]);
+
+        let trends = trends(&connection, None)?;
+        let summary: Vec<_> = trends
+            .iter()
+            .map(|t| {
+                (
+                    t.day,
+                    t.revision.as_deref(),
+                    t.runs,
+                    t.min_ns,
+                    t.median_ns,
+                    t.change,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (3, Some("aaaaaaa"), 3, 100, 200, None),
+                (3, Some("bbbbbbb"), 2, 150, 160, Some(-20.0)),
+                (4, Some("bbbbbbb"), 1, 10, 10, None),
+            ]
+        );
+        assert_eq!(super::trends(&connection, Some(&[4]))?.len(), 1);
+        let text = String::from_utf8(render(&trends, Format::Text)?)?;
+        assert!(text.contains("day 3 part 1\n  aaaaaaa"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_record_a_row_per_part() -> Result<()> {
+        let mut connection = Connection::open_in_memory()?;
+        create(&connection)?;
+        let run = Run {
+            day: 1,
+            part1: Answer::U64(142),
+            part2: Answer::U64(281),
+            timings: Default::default(),
+            memory: None,
+            input_hash: history::hash("abc"),
+        };
+        record(&mut connection, &[run], 1_701_406_800)?;
+        let count: usize =
+            connection.query_row("SELECT COUNT(*) FROM timings WHERE day = 1", [], |row| {
+                row.get(0)
+            })?;
+        assert_eq!(count, 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use std::{io, time::Duration};
+
+use anyhow::Result;
+use ratatui::{
+    crossterm::{
+        event::{self, Event, KeyCode, KeyEventKind},
+        execute,
+        terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    },
+    prelude::*,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+};
+
+use crate::{config::Config, days, days::Mode, output::Run, solve};
+
+/// the last day of the event
+const LAST_DAY: usize = 25;
+
+/// What we know about a single day on the dashboard
+struct Entry {
+    day: usize,
+    implemented: bool,
+    result: Option<Result<Run, String>>,
+}
+
+struct Dashboard<'a> {
+    entries: Vec<Entry>,
+    state: TableState,
+    mode: Mode,
+    config: &'a Config,
+}
+
+impl<'a> Dashboard<'a> {
+    fn new(mode: Mode, config: &'a Config) -> Dashboard<'a> {
+        let entries = (1..=LAST_DAY)
+            .map(|day| Entry {
+                day,
+                implemented: days::lookup(day, mode).is_some(),
+                result: None,
+            })
+            .collect();
+        let mut state = TableState::default();
+        state.select(Some(0));
+        Dashboard {
+            entries,
+            state,
+            mode,
+            config,
+        }
+    }
+
+    fn selected(&self) -> usize {
+        self.state.selected().unwrap_or(0)
+    }
+
+    fn select(&mut self, offset: isize) {
+        let last = self.entries.len() as isize - 1;
+        let next = (self.selected() as isize + offset).clamp(0, last);
+        self.state.select(Some(next as usize));
+    }
+
+    /// solve a single entry, recording either its answers or the error
+    fn rerun(&mut self, index: usize) {
+        let entry = &mut self.entries[index];
+        if entry.implemented {
+            let result = solve(entry.day, None, self.mode, None, self.config);
+            entry.result = Some(result.map_err(|e| e.to_string()));
+        }
+    }
+
+    fn rerun_all(&mut self) {
+        for index in 0..self.entries.len() {
+            self.rerun(index);
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [table_area, help_area] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+
+        let rows = self.entries.iter().map(|entry| {
+            let (status, part1, part2, time) = match (&entry.result, entry.implemented) {
+                (_, false) => (
+                    "not implemented".to_string(),
+                    "".into(),
+                    "".into(),
+                    "".into(),
+                ),
+                (None, true) => ("not run".to_string(), "".into(), "".into(), "".into()),
+                (Some(Err(e)), true) => (format!("error: {e}"), "".into(), "".into(), "".into()),
+                (Some(Ok(run)), true) => {
+                    let total = run.timings.total();
+                    (
+                        "solved".to_string(),
+                        run.part1.to_string(),
+                        run.part2.to_string(),
+                        format!("{total:?}"),
+                    )
+                }
+            };
+            let style = match (&entry.result, entry.implemented) {
+                (_, false) => Style::default().fg(Color::DarkGray),
+                (Some(Err(_)), true) => Style::default().fg(Color::Red),
+                (Some(Ok(_)), true) => Style::default().fg(Color::Green),
+                (None, true) => Style::default(),
+            };
+            Row::new(vec![
+                Cell::from(entry.day.to_string()),
+                Cell::from(status),
+                Cell::from(part1),
+                Cell::from(part2),
+                Cell::from(time),
+            ])
+            .style(style)
+        });
+
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Percentage(40),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+            Constraint::Percentage(20),
+        ];
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(vec!["day", "status", "part one", "part two", "time"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(Block::default().borders(Borders::ALL).title("aoc2023"))
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, table_area, &mut self.state);
+
+        let help = Paragraph::new("↑/↓ select · enter/r re-run day · a re-run all · q quit");
+        frame.render_widget(help, help_area);
+    }
+}
+
+/// run the interactive dashboard until the user quits
+pub fn run(mode: Mode, config: &Config) -> Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    let result = event_loop(&mut terminal, Dashboard::new(mode, config));
+
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)?;
+    result
+}
+
+fn event_loop<B: Backend>(terminal: &mut Terminal<B>, mut dashboard: Dashboard) -> Result<()> {
+    dashboard.rerun_all();
+    loop {
+        terminal.draw(|frame| dashboard.draw(frame))?;
+
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => dashboard.select(-1),
+                KeyCode::Down | KeyCode::Char('j') => dashboard.select(1),
+                KeyCode::Enter | KeyCode::Char('r') => {
+                    let selected = dashboard.selected();
+                    dashboard.rerun(selected);
+                }
+                KeyCode::Char('a') => dashboard.rerun_all(),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn should_list_every_day() -> Result<()> {
+        let config = Config::default();
+        let mut dashboard = Dashboard::new(Mode::Sequential, &config);
+        let mut terminal = Terminal::new(TestBackend::new(80, 30))?;
+        terminal.draw(|frame| dashboard.draw(frame))?;
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("not run"));
+        assert!(screen.contains("not implemented"));
+        Ok(())
+    }
+}
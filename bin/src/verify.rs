@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use aoc_core::answer::Answer;
+use serde::Deserialize;
+
+use crate::output::Run;
+
+/// default location of the known-correct answers
+pub const FILE_NAME: &str = "answers.toml";
+
+/// The known-correct answers for a single day. Either part may be left out,
+/// e.g. while part two is still unsolved.
+#[derive(Deserialize, Default, Debug, PartialEq, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// Known-correct answers keyed by day, read from tables such as
+///
+/// ```toml
+/// [day1]
+/// part1 = 142
+/// part2 = 281
+/// ```
+///
+/// Answers that aren't numbers are written as strings.
+#[derive(Debug, PartialEq)]
+pub struct Answers(pub BTreeMap<usize, Expected>);
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers> {
+        let tables: BTreeMap<String, Expected> = toml::from_str(text)?;
+        let mut answers = BTreeMap::new();
+        for (name, expected) in tables {
+            let day = name
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| anyhow!("expected a table named day<N>, found [{name}]"))?;
+            answers.insert(day, expected);
+        }
+        Ok(Answers(answers))
+    }
+}
+
+/// The outcome of checking one part of a run against its expected answer
+#[derive(Debug, PartialEq)]
+pub struct Check {
+    pub day: usize,
+    pub part: u8,
+    pub expected: Answer,
+    pub actual: Answer,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+/// compare each part of a run that has a known answer
+pub fn check(run: &Run, expected: &Expected) -> Vec<Check> {
+    [
+        (1, &expected.part1, &run.part1),
+        (2, &expected.part2, &run.part2),
+    ]
+    .into_iter()
+    .filter_map(|(part, expected, actual)| {
+        expected.clone().map(|expected| Check {
+            day: run.day,
+            part,
+            expected,
+            actual: actual.clone(),
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Timings;
+    use std::time::Duration;
+
+    #[test]
+    fn should_parse_answers_by_day() -> Result<()> {
+        let answers = Answers::parse("[day1]\npart1 = 142\npart2 = 281\n\n[day4]\npart1 = 13\n")?;
+        let expected = Expected {
+            part1: Some(Answer::U64(13)),
+            part2: None,
+        };
+        assert_eq!(answers.0.len(), 2);
+        assert_eq!(answers.0[&4], expected);
+        assert!(Answers::parse("[one]\npart1 = 1").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn should_report_mismatches() {
+        let run = Run {
+            day: 4,
+            part1: Answer::U64(13),
+            part2: Answer::U64(31),
+            timings: Timings {
+                input: Duration::ZERO,
+                parse: None,
+                part1: Duration::ZERO,
+                part2: Duration::ZERO,
+            },
+            memory: None,
+            input_hash: String::new(),
+        };
+        let checks = check(
+            &run,
+            &Expected {
+                part1: Some(Answer::U64(13)),
+                part2: Some(Answer::U64(30)),
+            },
+        );
+        assert!(checks[0].passed());
+        assert!(!checks[1].passed());
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::{self, Command},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{anyhow, Result};
+use aoc_core::{
+    incremental::{self, Incremental},
+    timing::timed,
+};
+
+use crate::{
+    days::{Mode, Start},
+    history,
+    output::{Run, Timings},
+};
+
+/// how often the watched paths are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// the most recent modification time of anything at `path`, descending into
+/// directories. Missing paths count as never modified, so a file that is
+/// deleted and written again is still picked up.
+pub fn last_modified(path: &Path) -> Option<SystemTime> {
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_dir() {
+        return metadata.modified().ok();
+    }
+    fs::read_dir(path)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| last_modified(&entry.path()))
+        .max()
+}
+
+/// the source directory of a day's crate, when running from a cargo checkout
+pub fn source_dir(day: usize) -> Result<PathBuf> {
+    if env::var_os("CARGO").is_none() {
+        return Err(anyhow!("--source only works when run with `cargo run`"));
+    }
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .ok_or_else(|| anyhow!("unable to locate the workspace root"))?;
+    Ok(workspace.join(format!("day{day}")).join("src"))
+}
+
+/// rebuild and restart the cli with the same arguments via `cargo run`,
+/// exiting with the status of the new process
+fn restart() -> Result<()> {
+    let cargo = env::var_os("CARGO").ok_or_else(|| anyhow!("CARGO is not set"))?;
+    let mut command = Command::new(cargo);
+    command.args(["run", "--quiet", "--package", "bin"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    let status = command.arg("--").args(env::args_os().skip(1)).status()?;
+    process::exit(status.code().unwrap_or(1));
+}
+
+/// How watch mode gets the answers again after a change
+pub enum Resolve {
+    /// update the answers from only the lines that changed
+    Incremental(Updating),
+    /// solve from scratch with the sequential or parallel solvers
+    Full(Mode),
+}
+
+/// A day's answers kept up to date with the watched input, so a change only
+/// redoes the lines it touched instead of solving from scratch
+pub struct Updating {
+    solving: Box<dyn Incremental>,
+    /// the input as of the last update, to find what changed since
+    text: String,
+}
+
+impl Updating {
+    pub fn new(start: Start) -> Updating {
+        Updating {
+            solving: start(),
+            text: String::new(),
+        }
+    }
+
+    /// bring the answers up to date with `text`. The time spent taking in
+    /// the changed lines is reported as reading the input.
+    pub fn update(&mut self, day: usize, text: &str) -> Result<Run> {
+        let (changed, lines) = incremental::diff(&self.text, text);
+        let ((), input) = timed(|| self.solving.splice(changed, &lines));
+        self.text = text.to_string();
+        let (part1, part1_time) = timed(|| self.solving.part1());
+        let (part2, part2_time) = timed(|| self.solving.part2());
+        Ok(Run {
+            day,
+            part1: part1.map_err(|e| e.with_day(day))?,
+            part2: part2.map_err(|e| e.with_day(day))?,
+            timings: Timings {
+                input,
+                parse: None,
+                part1: part1_time,
+                part2: part2_time,
+            },
+            memory: None,
+            input_hash: history::hash(text),
+        })
+    }
+}
+
+/// call `on_change` once up front and then whenever `input` changes, until
+/// the process is interrupted. Changes to anything under `source` rebuild
+/// and restart the cli instead, so edits to the solver are picked up too.
+pub fn watch(input: &Path, source: Option<&Path>, mut on_change: impl FnMut()) -> Result<()> {
+    let mut input_modified = last_modified(input);
+    let mut source_modified = source.and_then(last_modified);
+    on_change();
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        if let Some(source) = source {
+            let modified = last_modified(source);
+            if modified != source_modified {
+                eprintln!("{} changed, rebuilding", source.display());
+                restart()?;
+            }
+            source_modified = modified;
+        }
+
+        let modified = last_modified(input);
+        if modified != input_modified {
+            input_modified = modified;
+            if modified.is_none() {
+                eprintln!("{} was removed, waiting for it to return", input.display());
+                continue;
+            }
+            // clear the screen so only the latest answers are shown
+            print!("\x1b[2J\x1b[H");
+            io::Write::flush(&mut io::stdout())?;
+            on_change();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_track_newest_file_in_directory() -> Result<()> {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        fs::create_dir_all(dir.join("nested"))?;
+        fs::write(dir.join("a.txt"), "a")?;
+        let before = last_modified(&dir);
+        assert!(before.is_some());
+
+        thread::sleep(Duration::from_millis(20));
+        fs::write(dir.join("nested").join("b.txt"), "b")?;
+        assert!(last_modified(&dir) > before);
+
+        fs::remove_dir_all(&dir)?;
+        assert_eq!(last_modified(&dir), None);
+        Ok(())
+    }
+
+    #[test]
+    fn should_update_only_the_changed_lines() -> Result<()> {
+        let start = crate::days::incremental(1).expect("day 1 updates incrementally");
+        let mut updating = Updating::new(start);
+        let run = updating.update(1, "1abc2\npqr3stu8vwx\n")?;
+        assert_eq!(run.part1.to_string(), "50");
+
+        let run = updating.update(1, "1abc2\ntreb7uchet\npqr3stu8vwx\n")?;
+        assert_eq!(run.part1.to_string(), "127");
+        let error = updating.update(1, "1abc2\nabc\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 1 part 1, line 2: no digits in string"
+        );
+        Ok(())
+    }
+}
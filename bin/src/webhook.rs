@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use tracing::warn;
+
+use crate::{client::USER_AGENT, config::Config, output::Run};
+
+/// the longest message Discord accepts; longer summaries are cut short
+const DISCORD_MAX_CHARS: usize = 2000;
+
+/// The message shape a webhook expects
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Flavor {
+    /// `{"text": ...}`, which Slack and the chat servers copying its
+    /// incoming webhooks (Mattermost, Rocket.Chat, ...) accept
+    Slack,
+    /// `{"content": ...}`
+    Discord,
+}
+
+impl Flavor {
+    fn of(url: &str) -> Flavor {
+        let host = url
+            .split_once("://")
+            .map_or(url, |(_, rest)| rest)
+            .split(['/', ':'])
+            .next()
+            .unwrap_or_default();
+        let discord = ["discord.com", "discordapp.com"]
+            .iter()
+            .any(|domain| host == *domain || host.ends_with(&format!(".{domain}")));
+        match discord {
+            true => Flavor::Discord,
+            false => Flavor::Slack,
+        }
+    }
+}
+
+/// what to post to `url` so its chat shows `summary`
+fn payload(url: &str, summary: &str) -> Value {
+    match Flavor::of(url) {
+        Flavor::Slack => json!({ "text": summary }),
+        Flavor::Discord => {
+            let content: String = summary.chars().take(DISCORD_MAX_CHARS).collect();
+            json!({ "content": content })
+        }
+    }
+}
+
+/// a summary of a run: each day's answers and how long they took, and which
+/// days failed
+pub fn run_summary(runs: &[Run], failed: &[usize]) -> String {
+    let total: Duration = runs.iter().map(|run| run.timings.total()).sum();
+    let mut lines = vec![format!(
+        "solved {} of {} days in {total:?}",
+        runs.len(),
+        runs.len() + failed.len()
+    )];
+    for run in runs {
+        lines.push(format!(
+            "day {}: part one {} ({:?}), part two {} ({:?})",
+            run.day, run.part1, run.timings.part1, run.part2, run.timings.part2
+        ));
+    }
+    lines.extend(failed.iter().map(|day| format!("day {day}: failed")));
+    lines.join("\n")
+}
+
+/// a summary of what `verify` found wrong, given a line per regression and
+/// how many answers were checked
+pub fn regression_summary(regressions: &[String], checked: usize) -> String {
+    let plural = if regressions.len() == 1 { "" } else { "s" };
+    let mut lines = vec![format!(
+        "verify found {} regression{plural} in {checked} answers",
+        regressions.len()
+    )];
+    lines.extend(regressions.iter().cloned());
+    lines.join("\n")
+}
+
+/// post `summary` to `url`
+pub fn post(url: &str, summary: &str) -> Result<()> {
+    ureq::AgentBuilder::new()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(10))
+        .build()
+        .post(url)
+        .set("Content-Type", "application/json")
+        .send_string(&payload(url, summary).to_string())
+        .with_context(|| format!("failed to post to webhook {url}"))?;
+    Ok(())
+}
+
+/// post `summary` to the configured webhook, if there is one. A webhook
+/// that can't be reached is only warned about, since the run itself went
+/// fine.
+pub fn notify(config: &Config, summary: &str) {
+    if let Some(url) = &config.webhook {
+        if let Err(e) = post(url, summary) {
+            warn!("unable to notify webhook: {e:#}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Timings;
+    use aoc_core::answer::Answer;
+    use std::thread;
+
+    #[test]
+    fn should_shape_payloads_for_each_chat() {
+        let slack = payload("https://hooks.slack.com/services/T0/B0/x", "day 4");
+        assert_eq!(slack, json!({ "text": "day 4" }));
+        let discord = payload("https://discord.com/api/webhooks/1/x", "day 4");
+        assert_eq!(discord, json!({ "content": "day 4" }));
+
+        let long = "x".repeat(DISCORD_MAX_CHARS + 10);
+        let discord = payload("https://discordapp.com/api/webhooks/1/x", &long);
+        assert_eq!(
+            discord["content"].as_str().map(str::len),
+            Some(DISCORD_MAX_CHARS)
+        );
+    }
+
+    #[test]
+    fn should_post_a_summary_of_the_run() -> Result<()> {
+        let server = tiny_http::Server::http("127.0.0.1:0").map_err(|e| anyhow::anyhow!(e))?;
+        let url = format!("http://{}/hook", server.server_addr());
+        let received = thread::spawn(move || -> Result<String> {
+            let mut request = server.recv()?;
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body)?;
+            request.respond(tiny_http::Response::empty(200))?;
+            Ok(body)
+        });
+
+        let run = Run {
+            day: 4,
+            part1: Answer::U64(13),
+            part2: Answer::U64(30),
+            timings: Timings {
+                input: Duration::ZERO,
+                parse: None,
+                part1: Duration::from_micros(50),
+                part2: Duration::from_millis(2),
+            },
+            memory: None,
+            input_hash: String::new(),
+        };
+        post(&url, &run_summary(&[run], &[5]))?;
+
+        let body: Value = serde_json::from_str(&received.join().expect("server thread")?)?;
+        assert_eq!(
+            body["text"],
+            "solved 1 of 2 days in 2.05ms\n\
+             day 4: part one 13 (50µs), part two 30 (2ms)\n\
+             day 5: failed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_summarize_regressions() {
+        let regressions = ["day 4 part two: expected 30 but got 31".to_string()];
+        assert_eq!(
+            regression_summary(&regressions, 8),
+            "verify found 1 regression in 8 answers\nday 4 part two: expected 30 but got 31"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use std::{env, fmt::Write, fs, path::Path};
+
+/// the spelled out digits, each at the index of the digit it spells
+const WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Generate `numeric_at`, a `match` on the bytes at the start of what's left
+/// of a line with an arm per digit word, which the compiler turns into a
+/// branch on each byte rather than the solver trying every word in turn
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let mut arms = String::new();
+    for (digit, word) in WORDS.iter().enumerate() {
+        let bytes: Vec<String> = word
+            .bytes()
+            .map(|byte| format!("b'{}'", byte as char))
+            .collect();
+        writeln!(arms, "        [{}, ..] => Some({digit}),", bytes.join(", ")).unwrap();
+    }
+    let code = format!(
+        "/// the digit written or spelled out at the start of `rest`, if any
+#[inline]
+fn numeric_at(rest: &[u8]) -> Option<u64> {{
+    match rest {{
+        [digit @ b'0'..=b'9', ..] => Some(u64::from(digit - b'0')),
+{arms}        _ => None,
+    }}
+}}
+"
+    );
+    let out_dir = env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    fs::write(Path::new(&out_dir).join("numerics.rs"), code).expect("OUT_DIR is writable");
+}
@@ -1,62 +1,64 @@
-use anyhow::{anyhow, Result};
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-const NUMERICS: [&str; 20] = [
-    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "zero", "one", "two", "three", "four",
-    "five", "six", "seven", "eight", "nine",
-];
+extern crate alloc;
 
-/// Trait for turning string types into numeric digits
-trait StringDigit {
-    fn to_u64(self) -> Result<u64>;
-}
+use alloc::{format, vec, vec::Vec};
+use core::ops::Range;
+
+#[cfg(feature = "serde")]
+use aoc_core::export::Model;
+use aoc_core::{
+    context,
+    diagnostic::Diagnostic,
+    error::{AocError, Result},
+    explain,
+    incremental::Incremental,
+    overflow, progress,
+    solver::{Answer, DynSolver, Solver},
+    strategy::ExecutionStrategy,
+    telemetry,
+};
+use aoc_utils::parse::lines;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use tracing::{debug, instrument, trace};
 
-impl StringDigit for &str {
-    fn to_u64(self) -> Result<u64> {
-        let result = match self {
-            "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" => self.parse()?,
-            "zero" => 0,
-            "one" => 1,
-            "two" => 2,
-            "three" => 3,
-            "four" => 4,
-            "five" => 5,
-            "six" => 6,
-            "seven" => 7,
-            "eight" => 8,
-            "nine" => 9,
-            _ => return Err(anyhow!("not a valid digit")),
-        };
+/// the example input from the puzzle description for each part
+pub const PART_ONE_EXAMPLE: &str = include_str!("part1_example.txt");
+pub const PART_TWO_EXAMPLE: &str = include_str!("part2_example.txt");
 
-        Ok(result)
+// `numeric_at`, generated by build.rs from the digit words
+include!(concat!(env!("OUT_DIR"), "/numerics.rs"));
+
+/// the two digit number made of the first and last of `digits`, found in a
+/// single pass without keeping the digits in between. A single digit is both
+/// the first and the last.
+fn first_and_last(digits: impl Iterator<Item = u64>) -> Result<u64> {
+    let found = digits.fold(None, |found, digit| {
+        Some((found.map_or(digit, |(first, _)| first), digit))
+    });
+    match found {
+        Some((first, last)) => Ok(first * 10 + last),
+        None => Err(AocError::new("no digits in string")),
     }
 }
 
-fn extract_first_and_last_digits(text: &str) -> Result<u64> {
-    let digits: Vec<char> = text.chars().filter(|x| x.is_numeric()).collect();
-    let value = match (digits.first(), digits.last()) {
-        (Some(first), Some(last)) => format!("{first}{last}").parse()?,
-        _ => return Err(anyhow!("no digits in string")),
-    };
-    Ok(value)
+fn extract_first_and_last_digits(line: &[u8]) -> Result<u64> {
+    first_and_last(
+        line.iter()
+            .filter(|byte| byte.is_ascii_digit())
+            .map(|byte| u64::from(byte - b'0')),
+    )
 }
 
-fn extract_first_and_last_digit_or_numeric_word(text: &str) -> Result<u64> {
-    let digits = filter_digits_and_numeric_words(text)?;
-    let value = match (digits.first(), digits.last()) {
-        (Some(first), Some(last)) => first * 10 + last,
-        _ => return Err(anyhow!("no digits in string")),
-    };
-    Ok(value)
+fn extract_first_and_last_digit_or_numeric_word(line: &[u8]) -> Result<u64> {
+    first_and_last(digits_and_numeric_words(line))
 }
 
-fn filter_digits_and_numeric_words(text: &str) -> Result<Vec<u64>> {
-    let mut digits: Vec<(usize, &str)> = vec![];
-    for digit in NUMERICS {
-        let mut matches: Vec<(usize, &str)> = text.match_indices(digit).collect();
-        digits.append(&mut matches)
-    }
-    digits.sort_by_key(|x| x.0);
-    digits.into_iter().map(|x| x.1.to_u64()).collect()
+/// every digit or spelled out digit in `line`, in order. Spelled out digits
+/// may overlap, as in `twone`.
+fn digits_and_numeric_words(line: &[u8]) -> impl Iterator<Item = u64> + '_ {
+    (0..line.len()).filter_map(|start| numeric_at(&line[start..]))
 }
 
 ///
@@ -64,23 +66,9 @@ fn filter_digits_and_numeric_words(text: &str) -> Result<Vec<u64>> {
 /// digit number using the first and last numeric characters found in
 /// each line, then summing the two digit numbers from all of the lines.
 ///
-/// ```
-/// use day1::solve_part_one;
-/// use std::fs;
-///
-/// let text = fs::read_to_string("src/part1_example.txt").unwrap();
-/// let result = solve_part_one(&text).unwrap();
-/// assert_eq!(result, 142)
-/// ```
-///
-pub fn solve_part_one(text: &str) -> Result<u64> {
-    // we'll solve this using a procedural approach since it's both fast
-    // and easy to read.
-    let mut total = 0;
-    for line in text.lines() {
-        total += extract_first_and_last_digits(line)?;
-    }
-    Ok(total)
+#[instrument(skip_all)]
+pub fn solve_part_one(text: &str) -> Result<Answer> {
+    DynSolver::solve_part_one(&Day1, text, ExecutionStrategy::Sequential)
 }
 
 ///
@@ -88,86 +76,289 @@ pub fn solve_part_one(text: &str) -> Result<u64> {
 /// We are still finding the first and last numbers, but this time
 /// words that represent numeric values *also* count as valid digits.
 ///
-/// ```
-/// use day1::solve_part_two;
-/// use std::fs;
-///
-/// let text = fs::read_to_string("src/part2_example.txt").unwrap();
-/// let result = solve_part_two(&text).unwrap();
-/// assert_eq!(result, 281)
-/// ```
-///
-pub fn solve_part_two(text: &str) -> Result<u64> {
-    // we'll solve this using a procedural approach since it's both fast
-    // and easy to read.
-    let mut total = 0;
-    for line in text.lines() {
-        total += extract_first_and_last_digit_or_numeric_word(line)?;
-    }
+#[instrument(skip_all)]
+pub fn solve_part_two(text: &str) -> Result<Answer> {
+    DynSolver::solve_part_two(&Day1, text, ExecutionStrategy::Sequential)
+}
+
+/// [`solve_part_one`] straight from the bytes of the input, such as a
+/// memory mapped file, without checking they're UTF-8 first
+#[instrument(skip_all)]
+pub fn solve_part_one_bytes(input: &[u8]) -> Result<Answer> {
+    DynSolver::solve_part_one_bytes(&Day1, input, ExecutionStrategy::Sequential)
+}
+
+/// [`solve_part_two`] straight from the bytes of the input
+#[instrument(skip_all)]
+pub fn solve_part_two_bytes(input: &[u8]) -> Result<Answer> {
+    DynSolver::solve_part_two_bytes(&Day1, input, ExecutionStrategy::Sequential)
+}
+
+/// sum the calibration value `extract` finds on each line
+fn sum_calibration_values(
+    lines: &[&[u8]],
+    extract: fn(&[u8]) -> Result<u64>,
+    strategy: ExecutionStrategy,
+) -> Result<u128> {
+    context::reserve_for::<u64>(lines.len(), "calibration values")?;
+    progress::start("lines", lines.len() as u64);
+    let values = strategy.try_map(lines, |i, line| {
+        context::checkpoint()?;
+        let value = extract(line).map_err(|e| e.at_line(i + 1))?;
+        trace!(line = %line.escape_ascii(), value, "extracted calibration value");
+        explain::step(|| format!("line {}: {} gives {value}", i + 1, line.escape_ascii()));
+        progress::advance(1);
+        Ok(value)
+    })?;
+    progress::finish();
+    let total = overflow::policy().sum(values)?;
+    telemetry::lines(lines.len());
+    debug!(lines = lines.len(), total, "summed calibration values");
     Ok(total)
 }
 
-pub mod mt {
-    use super::*;
-    use rayon::prelude::*;
+/// The calibration document is worked on a line at a time, so parsing only
+/// splits it into lines. Only ASCII digits and words count, so the lines are
+/// worked on as bytes.
+pub struct Day1;
 
-    pub fn solve_part_one(text: &str) -> Result<u64> {
-        let nums: Vec<u64> = text
-            .par_lines()
-            .map(extract_first_and_last_digits)
-            .collect::<Result<Vec<u64>>>()?;
-        let total: u64 = nums.par_iter().sum();
-        Ok(total)
+impl Solver for Day1 {
+    const DAY: usize = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+    const TAGS: &'static [&'static str] = &["strings"];
+    type Parsed<'a> = Vec<&'a [u8]>;
+
+    fn parse(text: &str, strategy: ExecutionStrategy) -> Result<Vec<&[u8]>> {
+        Self::parse_bytes(text.as_bytes(), strategy)
+    }
+
+    fn parse_bytes(input: &[u8], _strategy: ExecutionStrategy) -> Result<Vec<&[u8]>> {
+        let lines: Vec<&[u8]> = lines(input).collect();
+        context::reserve_for::<&[u8]>(lines.len(), "calibration lines")?;
+        telemetry::lines(lines.len());
+        Ok(lines)
+    }
+
+    fn part1(lines: &Vec<&[u8]>, strategy: ExecutionStrategy) -> Result<Answer> {
+        Ok(sum_calibration_values(lines, extract_first_and_last_digits, strategy)?.into())
     }
 
-    pub fn solve_part_two(text: &str) -> Result<u64> {
-        let nums: Vec<u64> = text
-            .par_lines()
-            .map(extract_first_and_last_digit_or_numeric_word)
+    fn part2(lines: &Vec<&[u8]>, strategy: ExecutionStrategy) -> Result<Answer> {
+        let extract = extract_first_and_last_digit_or_numeric_word;
+        Ok(sum_calibration_values(lines, extract, strategy)?.into())
+    }
+}
+
+/// Each line's calibration values only depend on the line, so they're kept
+/// for every line and an edit only extracts the lines it touches
+#[derive(Default)]
+pub struct Calibrations {
+    values: Vec<[Result<u64>; 2]>,
+}
+
+impl Calibrations {
+    fn sum(&self, part: usize) -> Result<Answer> {
+        let values = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, values)| values[part].clone().map_err(|e| e.at_line(i + 1)))
             .collect::<Result<Vec<u64>>>()?;
-        let total: u64 = nums.par_iter().sum();
-        Ok(total)
+        Ok(overflow::policy().sum(values)?.into())
+    }
+}
+
+impl Incremental for Calibrations {
+    fn splice(&mut self, changed: Range<usize>, lines: &[&str]) {
+        let values = lines.iter().map(|line| {
+            [
+                extract_first_and_last_digits(line.as_bytes()),
+                extract_first_and_last_digit_or_numeric_word(line.as_bytes()),
+            ]
+        });
+        self.values.splice(changed, values);
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        self.sum(0).map_err(|e| e.with_part(1))
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        self.sum(1).map_err(|e| e.with_part(2))
+    }
+}
+
+/// the lines as text rather than bytes
+#[cfg(feature = "serde")]
+impl Model for Day1 {
+    fn model<'a, 'b: 'a>(lines: &'a Vec<&'b [u8]>) -> impl Serialize + 'a {
+        lines
+            .iter()
+            .map(|line| alloc::string::String::from_utf8_lossy(line))
+            .collect::<Vec<_>>()
+    }
+}
+
+aoc_core::register! {
+    solver: Day1,
+    examples: [PART_ONE_EXAMPLE, PART_TWO_EXAMPLE],
+    expected: [Some(Answer::U64(142)), Some(Answer::U64(281))],
+    validate: validate,
+    incremental: || alloc::boxed::Box::new(Calibrations::default()),
+    persist: Day1,
+    export: Day1,
+}
+
+/// Slow but obviously correct versions of both parts, working on the text a
+/// character at a time, for tests to check the solvers against
+#[cfg(feature = "reference")]
+pub mod reference {
+    use super::*;
+
+    const WORDS: [&str; 10] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    /// the digit written, or spelled out when `words` count, at the start of
+    /// `rest`
+    fn digit_at(rest: &str, words: bool) -> Option<u128> {
+        if let Some(digit) = rest.chars().next()?.to_digit(10) {
+            return Some(digit.into());
+        }
+        let spelled = WORDS.iter().position(|word| rest.starts_with(word))?;
+        words.then_some(spelled as u128)
+    }
+
+    fn calibration_value(line: &str, words: bool) -> Result<u128> {
+        let digits: Vec<u128> = line
+            .char_indices()
+            .filter_map(|(i, _)| digit_at(&line[i..], words))
+            .collect();
+        match (digits.first(), digits.last()) {
+            (Some(first), Some(last)) => Ok(first * 10 + last),
+            _ => Err(AocError::new("no digits in string")),
+        }
+    }
+
+    pub fn part_one(text: &str) -> Result<u128> {
+        text.lines()
+            .map(|line| calibration_value(line, false))
+            .sum()
+    }
+
+    pub fn part_two(text: &str) -> Result<u128> {
+        text.lines().map(|line| calibration_value(line, true)).sum()
     }
 }
 
-pub fn print_answers(text: &str) -> Result<()> {
-    let part_one = solve_part_one(text)?;
-    let part_two = solve_part_two(text)?;
+/// check every line has the digits the solvers need, without solving
+pub fn validate(text: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        if let Some((column, c)) = line
+            .chars()
+            .enumerate()
+            .find(|(_, c)| c.is_numeric() && !c.is_ascii_digit())
+        {
+            diagnostics.push(Diagnostic::new(
+                number,
+                column + 1,
+                format!("{c:?} is not a digit from 0 to 9"),
+            ));
+        }
+        if !line.chars().any(|c| c.is_ascii_digit()) {
+            let message = match digits_and_numeric_words(line.as_bytes()).next() {
+                Some(_) => "no digits, part one needs at least one",
+                None => "no digits or spelled out digits",
+            };
+            diagnostics.push(Diagnostic::new(number, 1, message));
+        }
+    }
+    diagnostics
+}
 
-    println!("part one: {part_one}");
-    println!("part two: {part_two}");
-    Ok(())
+/// both parts' answers, parsing the input once, for the caller to present
+#[cfg(feature = "std")]
+pub fn answers(text: &str) -> Result<aoc_core::solver::Answers> {
+    Day1.solve(text, ExecutionStrategy::Sequential)
 }
 
 #[cfg(test)]
+// the tests hand back their assertion as `Ok(assert_eq!(..))`
+#[allow(clippy::unit_arg)]
 mod tests {
     use super::*;
 
     #[test]
     fn begins_and_ends_with_number() -> Result<()> {
-        let text = "1abc2";
+        let text = b"1abc2";
         let result = extract_first_and_last_digits(text)?;
         Ok(assert_eq!(result, 12))
     }
 
     #[test]
     fn begins_and_ends_with_letter() -> Result<()> {
-        let text = "pqr3stu8vwx";
+        let text = b"pqr3stu8vwx";
         let result = extract_first_and_last_digits(text)?;
         Ok(assert_eq!(result, 38))
     }
 
     #[test]
     fn has_multiple_numbers() -> Result<()> {
-        let text = "a1b2c3d4e5f";
+        let text = b"a1b2c3d4e5f";
         let result = extract_first_and_last_digits(text)?;
         Ok(assert_eq!(result, 15))
     }
 
     #[test]
     fn has_one_number() -> Result<()> {
-        let text = "treb7uchet";
+        let text = b"treb7uchet";
         let result = extract_first_and_last_digits(text)?;
         Ok(assert_eq!(result, 77))
     }
+
+    #[test]
+    fn has_overlapping_words() {
+        assert_eq!(
+            digits_and_numeric_words(b"xtwone3four").collect::<Vec<_>>(),
+            [2, 1, 3, 4]
+        );
+        assert_eq!(
+            extract_first_and_last_digit_or_numeric_word(b"xtwone3four"),
+            Ok(24)
+        );
+        assert!(extract_first_and_last_digit_or_numeric_word(b"xyz").is_err());
+    }
+
+    #[test]
+    fn should_look_up_every_digit_and_word() {
+        let words = [
+            "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        for (digit, word) in words.into_iter().enumerate() {
+            assert_eq!(numeric_at(word.as_bytes()), Some(digit as u64));
+            assert_eq!(
+                numeric_at(format!("{word}x").as_bytes()),
+                Some(digit as u64)
+            );
+            assert_eq!(numeric_at(&word.as_bytes()[1..]), None, "{word}");
+            assert_eq!(numeric_at(digit.to_string().as_bytes()), Some(digit as u64));
+        }
+        assert_eq!(numeric_at(b""), None);
+        assert_eq!(numeric_at(b"thre"), None);
+    }
+
+    #[test]
+    fn should_validate_lines() {
+        assert_eq!(validate(PART_ONE_EXAMPLE), vec![]);
+        let diagnostics = validate("1abc2\ntwone\nnothing");
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::new(2, 1, "no digits, part one needs at least one"),
+                Diagnostic::new(3, 1, "no digits or spelled out digits"),
+            ]
+        );
+    }
 }
@@ -1,9 +1,41 @@
-use std::collections::{
-    hash_map::Entry::{Occupied, Vacant},
-    HashMap,
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{mem, ops::Range};
+
+#[cfg(feature = "serde")]
+use aoc_core::export::Model;
+use aoc_core::{
+    collections::{
+        hash_map::Entry::{Occupied, Vacant},
+        FastMap,
+    },
+    context,
+    diagnostic::Diagnostic,
+    error::{AocError, Result},
+    explain,
+    incremental::Incremental,
+    overflow, progress,
+    solver::{Answer, DynSolver, Solver},
+    strategy::ExecutionStrategy,
+    telemetry,
 };
+use aoc_utils::parse::{lines, parse_u64};
+use bstr::ByteSlice;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use tracing::{debug, instrument, trace, warn};
 
-use anyhow::{anyhow, Result};
+/// the example input from the puzzle description for each part
+pub const PART_ONE_EXAMPLE: &str = include_str!("part1_example.txt");
+pub const PART_TWO_EXAMPLE: &str = include_str!("part1_example.txt");
 
 type GameData<'a> = (u64, Vec<Vec<(u64, &'a str)>>);
 
@@ -33,65 +65,99 @@ fn allowed_for_part_one(number: u64, color: &str) -> bool {
 /// parse each line (game) into the individual pieces of information
 /// needed to perform the calculations required for solving the puzzle.
 ///
-fn parse_line(text: &str) -> Result<GameData> {
+fn parse_line(line: &[u8]) -> Result<GameData<'_>> {
     // drop the "Game" prefix from the data
-    let (_, useful_text) = text
-        .split_once(' ')
-        .ok_or(anyhow!("malformatted line, no space separated data"))?;
+    let (_, useful_text) = line
+        .split_once_str(" ")
+        .ok_or(AocError::new("malformatted line, no space separated data"))?;
 
     // split the game id from the rest of the data
     let (id, draw_data) = useful_text
-        .split_once(':')
-        .ok_or(anyhow!("malformatted line, no colon separated data"))?;
+        .split_once_str(":")
+        .ok_or(AocError::new("malformatted line, no colon separated data"))?;
 
-    let parsed_id: u64 = id.parse()?;
+    let parsed_id = parse_u64(id)?;
+    reserve_rounds(draw_data)?;
 
     // break the remaining data into the subsets
     // ["3 blue, 4 red", "1 red, 2 green", ...]
-    let subsets = draw_data.split(';');
+    let subsets = draw_data.split_str(";");
 
     // this vec will hold the data representing the final format
     // [[("3", "blue"), ("4", "red")], [("1", "red"), ("2", "green")], ...]
     let mut parsed_subsets: Vec<Vec<(u64, &str)>> = vec![];
 
-    // Since the str::split we called above returned an iterator and not a Vec / slice,
+    // Since the split we called above returned an iterator and not a Vec / slice,
     // the actual split operation is being performed while we loop here, so we're not
-    // losing performance by iterating over the string data multiple times.
+    // losing performance by iterating over the line multiple times.
     for subset in subsets {
         // lets break the subset into strings indicating number and color
         // i.e. "3 blue, 4 red" => ["3 blue", "4 red"]
-        let cube_data = subset.split(',');
+        let cube_data = subset.split_str(",");
 
         // this vec will hold the
         let mut parsed_cube_data: Vec<(u64, &str)> = vec![];
 
-        // again, the str::split(',') we called a few lines ago didn't actually perform
-        // the split operation, but instead waited until we began iterating over the str,
+        // again, the split(",") we called a few lines ago didn't actually perform
+        // the split operation, but instead waited until we began iterating over the line,
         // gifting us additional performance.
         for data in cube_data {
             // lets break the number and color strings into tuples
             // i.e. "3 blue" =>  (3, "blue")
-            let (count, color) = data
-                .trim()
-                .split_once(' ')
-                .ok_or(anyhow!("malformatted line, dice data not space separated"))?;
+            let (count, color) = data.trim_ascii().split_once_str(" ").ok_or(AocError::new(
+                "malformatted line, dice data not space separated",
+            ))?;
 
-            let parsed_count: u64 = count.parse()?;
+            // the colors are the only part of the line kept as text, so
+            // they're the only part checked to be UTF-8
+            let color = core::str::from_utf8(color)
+                .map_err(|_| AocError::new("cube color is not valid UTF-8"))?;
+            let parsed_count = parse_u64(count)?;
+            if !matches!(color, "red" | "green" | "blue") {
+                warn!(game = parsed_id, color, "unexpected cube color");
+            }
             parsed_cube_data.push((parsed_count, color));
         }
 
         parsed_subsets.push(parsed_cube_data);
     }
+    trace!(
+        game = parsed_id,
+        subsets = parsed_subsets.len(),
+        "parsed game"
+    );
     Ok((parsed_id, parsed_subsets))
 }
 
-fn highest_count_seen(data: &GameData) -> HashMap<String, u64> {
-    let mut counts: HashMap<String, u64> = HashMap::new();
+/// reserve room for a game's rounds, a list per subset and a count and
+/// color per draw in it, counted up front so a giant game is refused before
+/// any of it is kept
+fn reserve_rounds(draw_data: &[u8]) -> Result<()> {
+    let rounds = draw_data.iter().filter(|&&byte| byte == b';').count() + 1;
+    let draws = draw_data.iter().filter(|&&byte| byte == b',').count() + rounds;
+    context::reserve(
+        (rounds * mem::size_of::<Vec<(u64, &str)>>())
+            .saturating_add(draws * mem::size_of::<(u64, &str)>()),
+        "game rounds",
+    )
+}
+
+fn highest_count_seen<'a>(data: &GameData<'a>) -> FastMap<&'a str, u64> {
     let (_, sets) = data;
+    highest_counts(sets.iter().map(Vec::as_slice))
+}
+
+/// the most cubes of each color drawn in any of a game's rounds, keyed by
+/// the color's name as it appears in the record so counting allocates
+/// nothing but the map
+fn highest_counts<'r, 'a: 'r>(
+    sets: impl IntoIterator<Item = &'r [(u64, &'a str)]>,
+) -> FastMap<&'a str, u64> {
+    let mut counts: FastMap<&str, u64> = FastMap::default();
 
     for set in sets {
         for (count, color) in set {
-            match counts.entry(color.to_string()) {
+            match counts.entry(*color) {
                 Occupied(mut entry) => {
                     // update logic
                     let value = entry.get_mut();
@@ -109,9 +175,19 @@ fn highest_count_seen(data: &GameData) -> HashMap<String, u64> {
     counts
 }
 
-fn possible_game(counts: HashMap<String, u64>, within_rules: fn(u64, &str) -> bool) -> bool {
+/// the highest counts, in color order so explanations read the same every run
+fn sorted<'a>(counts: &FastMap<&'a str, u64>) -> Vec<(&'a str, u64)> {
+    let mut sorted: Vec<(&str, u64)> = counts
+        .iter()
+        .map(|(color, count)| (*color, *count))
+        .collect();
+    sorted.sort_unstable();
+    sorted
+}
+
+fn possible_game(counts: FastMap<&str, u64>, within_rules: fn(u64, &str) -> bool) -> bool {
     for (color, count) in counts {
-        if !within_rules(count, &color) {
+        if !within_rules(count, color) {
             return false;
         }
     }
@@ -125,31 +201,9 @@ fn possible_game(counts: HashMap<String, u64>, within_rules: fn(u64, &str) -> bo
 /// What is the sum of the IDs of those games?
 /// ```
 ///
-/// ```
-/// use day2::solve_part_one;
-/// use std::fs;
-///
-/// let text = fs::read_to_string("src/part1_example.txt").unwrap();
-/// let result = solve_part_one(&text).unwrap();
-/// assert_eq!(result, 8)
-/// ```
-///
-pub fn solve_part_one(text: &str) -> Result<u64> {
-    let mut game_ids: Vec<u64> = vec![];
-    // for each line in game data
-    for line in text.lines() {
-        // parse game data
-        let data = parse_line(line)?;
-        // find highest counts seen
-        let counts = highest_count_seen(&data);
-        // record id if it is a valid game based on the rules
-        if possible_game(counts, allowed_for_part_one) {
-            game_ids.push(data.0);
-        }
-    }
-
-    // sum ids
-    Ok(game_ids.into_iter().sum())
+#[instrument(skip_all)]
+pub fn solve_part_one(text: &str) -> Result<Answer> {
+    DynSolver::solve_part_one(&Day2, text, ExecutionStrategy::Sequential)
 }
 
 ///
@@ -174,42 +228,434 @@ pub fn solve_part_one(text: &str) -> Result<u64> {
 /// What is the sum of the power of these sets?
 /// ```
 ///
-/// ```
-/// use day2::solve_part_two;
-/// use std::fs;
-///
-/// let text = fs::read_to_string("src/part1_example.txt").unwrap();
-/// let result = solve_part_two(&text).unwrap();
-/// assert_eq!(result, 2286)
-/// ```
-///
-pub fn solve_part_two(text: &str) -> Result<u64> {
-    let mut game_powers: Vec<u64> = vec![];
-    // for each line in game data
-    for line in text.lines() {
-        // parse game data
-        let data = parse_line(line)?;
-        // find highest counts seen
-        let counts = highest_count_seen(&data);
-        // calculate the powers
-        let power = counts.values().product::<u64>();
-        game_powers.push(power);
+#[instrument(skip_all)]
+pub fn solve_part_two(text: &str) -> Result<Answer> {
+    DynSolver::solve_part_two(&Day2, text, ExecutionStrategy::Sequential)
+}
+
+/// [`solve_part_one`] straight from the bytes of the input, such as a
+/// memory mapped file, checking only the cube colors are UTF-8
+#[instrument(skip_all)]
+pub fn solve_part_one_bytes(input: &[u8]) -> Result<Answer> {
+    DynSolver::solve_part_one_bytes(&Day2, input, ExecutionStrategy::Sequential)
+}
+
+/// [`solve_part_two`] straight from the bytes of the input
+#[instrument(skip_all)]
+pub fn solve_part_two_bytes(input: &[u8]) -> Result<Answer> {
+    DynSolver::solve_part_two_bytes(&Day2, input, ExecutionStrategy::Sequential)
+}
+
+/// Each line of the record is parsed into a game's id and the cubes drawn in
+/// each round
+pub struct Day2;
+
+impl Solver for Day2 {
+    const DAY: usize = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+    const TAGS: &'static [&'static str] = &["parsing"];
+    type Parsed<'a> = Vec<GameData<'a>>;
+
+    fn parse(text: &str, strategy: ExecutionStrategy) -> Result<Vec<GameData<'_>>> {
+        Self::parse_bytes(text.as_bytes(), strategy)
+    }
+
+    /// only the colors are kept as text, so the record is split up as bytes
+    /// and the colors alone are checked to be UTF-8
+    fn parse_bytes(input: &[u8], strategy: ExecutionStrategy) -> Result<Vec<GameData<'_>>> {
+        let lines: Vec<&[u8]> = lines(input).collect();
+        context::reserve_for::<(&[u8], GameData)>(lines.len(), "games")?;
+        progress::start("games", lines.len() as u64);
+        let games = strategy.try_map(&lines, |i, line| {
+            context::checkpoint()?;
+            progress::advance(1);
+            parse_line(line).map_err(|e| e.at_line(i + 1))
+        })?;
+        progress::finish();
+        telemetry::lines(games.len());
+        telemetry::entries(games.iter().map(|game| game.1.len()).sum());
+        Ok(games)
+    }
+
+    fn part1(games: &Vec<GameData<'_>>, strategy: ExecutionStrategy) -> Result<Answer> {
+        let game_ids: Vec<u64> = strategy
+            .map(games, |data| {
+                // find highest counts seen
+                let counts = highest_count_seen(data);
+                explain::step(|| {
+                    let too_many = sorted(&counts)
+                        .into_iter()
+                        .find(|(color, count)| !allowed_for_part_one(*count, color));
+                    match too_many {
+                        Some((color, count)) => {
+                            format!("game {} isn't possible, it draws {count} {color}", data.0)
+                        }
+                        None => format!("game {} is possible", data.0),
+                    }
+                });
+                // keep the id if it is a valid game based on the rules
+                possible_game(counts, allowed_for_part_one).then_some(data.0)
+            })
+            .into_iter()
+            .flatten()
+            .collect();
+
+        telemetry::entries(game_ids.len());
+        debug!(possible = game_ids.len(), "found possible games");
+
+        // sum ids
+        Ok(overflow::policy().sum(game_ids)?.into())
+    }
+
+    fn part2(games: &Vec<GameData<'_>>, strategy: ExecutionStrategy) -> Result<Answer> {
+        let policy = overflow::policy();
+        let game_powers: Vec<u128> = strategy.try_map(games, |_, data| {
+            // find highest counts seen
+            let counts = highest_count_seen(data);
+            // calculate the powers
+            let power = policy.product(counts.values().copied())?;
+            explain::step(|| {
+                let needed: Vec<String> = sorted(&counts)
+                    .into_iter()
+                    .map(|(color, count)| format!("{count} {color}"))
+                    .collect();
+                format!(
+                    "game {} needs at least {}, power {power}",
+                    data.0,
+                    needed.join(", ")
+                )
+            });
+            Ok(power)
+        })?;
+
+        telemetry::entries(game_powers.len());
+        debug!(games = game_powers.len(), "calculated game powers");
+
+        // sum powers
+        Ok(policy.sum(game_powers)?.into())
+    }
+}
+
+/// Both parts only need each game's id and highest counts, which only depend
+/// on its line, so they're kept for every game and an edit only parses the
+/// games it touches
+#[derive(Default)]
+pub struct Games {
+    games: Vec<Result<(u64, FastMap<String, u64>)>>,
+}
+
+impl Games {
+    /// every game's id and highest counts, or the first line that didn't parse
+    fn games(&self) -> Result<Vec<(u64, &FastMap<String, u64>)>> {
+        self.games
+            .iter()
+            .enumerate()
+            .map(|(i, game)| match game {
+                Ok((id, counts)) => Ok((*id, counts)),
+                Err(e) => Err(e.clone().at_line(i + 1)),
+            })
+            .collect()
+    }
+}
+
+impl Incremental for Games {
+    fn splice(&mut self, changed: Range<usize>, lines: &[&str]) {
+        let games = lines.iter().map(|line| {
+            parse_line(line.as_bytes()).and_then(|data| {
+                // the lines only last as long as the edit, so the colors are
+                // copied to be kept
+                let counts = highest_count_seen(&data);
+                context::reserve_for::<(String, u64)>(counts.len(), "game counts")?;
+                let counts = counts
+                    .into_iter()
+                    .map(|(color, count)| (color.to_string(), count))
+                    .collect();
+                Ok((data.0, counts))
+            })
+        });
+        self.games.splice(changed, games);
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        let games = self.games().map_err(|e| e.with_part(1))?;
+        let possible = games.into_iter().filter_map(|(id, counts)| {
+            let possible = counts
+                .iter()
+                .all(|(color, count)| allowed_for_part_one(*count, color));
+            possible.then_some(id)
+        });
+        let total = overflow::policy().sum(possible);
+        Ok(total.map_err(|e| e.with_part(1))?.into())
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        let games = self.games().map_err(|e| e.with_part(2))?;
+        let policy = overflow::policy();
+        let powers = games
+            .into_iter()
+            .map(|(_, counts)| policy.product(counts.values().copied()))
+            .collect::<Result<Vec<u128>>>()
+            .map_err(|e| e.with_part(2))?;
+        Ok(policy.sum(powers).map_err(|e| e.with_part(2))?.into())
+    }
+}
+
+/// A game as `aoc parse` shows it
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct Game<'a> {
+    id: u64,
+    /// the cubes drawn in each round
+    rounds: Vec<Vec<Cubes<'a>>>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct Cubes<'a> {
+    count: u64,
+    color: &'a str,
+}
+
+#[cfg(feature = "serde")]
+impl Model for Day2 {
+    fn model<'a, 'b: 'a>(games: &'a Vec<GameData<'b>>) -> impl Serialize + 'a {
+        games
+            .iter()
+            .map(|(id, rounds)| Game {
+                id: *id,
+                rounds: rounds
+                    .iter()
+                    .map(|round| {
+                        round
+                            .iter()
+                            .map(|&(count, color)| Cubes { count, color })
+                            .collect()
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<_>>()
     }
+}
 
-    // sum powers
-    Ok(game_powers.into_iter().sum())
+aoc_core::register! {
+    solver: Day2,
+    examples: [PART_ONE_EXAMPLE, PART_TWO_EXAMPLE],
+    expected: [Some(Answer::U64(8)), Some(Answer::U64(2286))],
+    validate: validate,
+    algorithms: &[
+        aoc_core::solver::Algorithm::solver("vec"),
+        #[cfg(feature = "arena")]
+        aoc_core::solver::Algorithm::new("arena", arena::solve_part_one, arena::solve_part_two),
+    ],
+    incremental: || alloc::boxed::Box::new(Games::default()),
+    persist: Day2,
+    export: Day2,
 }
 
-pub fn print_answers(text: &str) -> Result<()> {
-    let part_one = solve_part_one(text)?;
-    let part_two = solve_part_two(text)?;
+/// Variant that parses every game's rounds into one bumpalo arena rather than
+/// a `Vec` per round, so a large record costs a handful of allocations that
+/// are all freed together once the answer is found.
+#[cfg(feature = "arena")]
+pub mod arena {
+    use bumpalo::{collections::Vec as ArenaVec, Bump};
+
+    use super::*;
+
+    /// a game's id and the draws in each of its rounds
+    type Game<'a> = (u64, ArenaVec<'a, ArenaVec<'a, (u64, &'a str)>>);
+
+    fn parse_game<'a>(line: &'a str, arena: &'a Bump) -> Result<Game<'a>> {
+        let (_, useful_text) = line
+            .split_once(' ')
+            .ok_or(AocError::new("malformatted line, no space separated data"))?;
+        let (id, draw_data) = useful_text
+            .split_once(':')
+            .ok_or(AocError::new("malformatted line, no colon separated data"))?;
+        reserve_rounds(draw_data.as_bytes())?;
+
+        let mut rounds = ArenaVec::new_in(arena);
+        for subset in draw_data.split(';') {
+            let mut draws = ArenaVec::new_in(arena);
+            for data in subset.split(',') {
+                let (count, color) = data.trim().split_once(' ').ok_or(AocError::new(
+                    "malformatted line, dice data not space separated",
+                ))?;
+                draws.push((count.parse()?, color));
+            }
+            rounds.push(draws);
+        }
+        Ok((id.parse()?, rounds))
+    }
+
+    /// parse the record into `arena` and solve from it, freeing everything
+    /// parsed when the arena is dropped
+    fn solve(text: &str, solve: impl Fn(&[Game<'_>]) -> Result<u128>) -> Result<Answer> {
+        // it's a solve of its own, with a memory budget of its own
+        context::fresh(|| {
+            let arena = Bump::new();
+            let mut games = ArenaVec::new_in(&arena);
+            for (i, line) in text.lines().enumerate() {
+                context::checkpoint()?;
+                games.push(parse_game(line, &arena).map_err(|e| e.at_line(i + 1))?);
+            }
+            debug!(
+                games = games.len(),
+                bytes = arena.allocated_bytes(),
+                "parsed into arena"
+            );
+            Ok(solve(&games)?.into())
+        })
+    }
 
-    println!("part one: {part_one}");
-    println!("part two: {part_two}");
-    Ok(())
+    fn highest<'a>(game: &Game<'a>) -> FastMap<&'a str, u64> {
+        highest_counts(game.1.iter().map(|round| round.as_slice()))
+    }
+
+    #[instrument(name = "arena::solve_part_one", skip_all)]
+    pub fn solve_part_one(text: &str) -> Result<Answer> {
+        solve(text, |games| {
+            let possible = games
+                .iter()
+                .filter(|game| possible_game(highest(game), allowed_for_part_one))
+                .map(|game| game.0);
+            overflow::policy().sum(possible)
+        })
+    }
+
+    #[instrument(name = "arena::solve_part_two", skip_all)]
+    pub fn solve_part_two(text: &str) -> Result<Answer> {
+        solve(text, |games| {
+            let policy = overflow::policy();
+            let powers = games
+                .iter()
+                .map(|game| policy.product(highest(game).values().copied()))
+                .collect::<Result<Vec<u128>>>()?;
+            policy.sum(powers)
+        })
+    }
+}
+
+/// Slow but obviously correct versions of both parts, tallying the fewest
+/// cubes of each color a game needs as it reads the line, for tests to check
+/// the solvers against
+#[cfg(feature = "reference")]
+pub mod reference {
+    use super::*;
+
+    /// a game's id and the fewest red, green and blue cubes it could be
+    /// played with
+    fn fewest(line: &str) -> Result<(u128, [u128; 3])> {
+        let (game, draws) = line
+            .split_once(':')
+            .ok_or(AocError::new("malformatted line, no colon separated data"))?;
+        let id = game.trim_start_matches("Game ").parse()?;
+        let mut fewest = [0; 3];
+        for draw in draws.split([';', ',']) {
+            let (count, color) = draw.trim().split_once(' ').ok_or(AocError::new(
+                "malformatted line, dice data not space separated",
+            ))?;
+            let color = ["red", "green", "blue"]
+                .iter()
+                .position(|c| *c == color)
+                .ok_or(AocError::new(format!("unknown cube color {color:?}")))?;
+            fewest[color] = fewest[color].max(count.parse()?);
+        }
+        Ok((id, fewest))
+    }
+
+    pub fn part_one(text: &str) -> Result<u128> {
+        let mut total = 0;
+        for line in text.lines() {
+            let (id, [red, green, blue]) = fewest(line)?;
+            if red <= 12 && green <= 13 && blue <= 14 {
+                total += id;
+            }
+        }
+        Ok(total)
+    }
+
+    pub fn part_two(text: &str) -> Result<u128> {
+        let mut total = 0;
+        for line in text.lines() {
+            let (_, [red, green, blue]) = fewest(line)?;
+            total += red * green * blue;
+        }
+        Ok(total)
+    }
+}
+
+/// check each game is in the `Game <id>: <count> <color>, ...; ...` format,
+/// pointing at the first problem in each draw, without solving
+pub fn validate(text: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let Some(rest) = line.strip_prefix("Game ") else {
+            diagnostics.push(Diagnostic::new(
+                number,
+                1,
+                "expected line to start with \"Game \"",
+            ));
+            continue;
+        };
+        let Some((id, draws)) = rest.split_once(':') else {
+            diagnostics.push(Diagnostic::at(
+                number,
+                line,
+                rest,
+                "expected a colon after the game id",
+            ));
+            continue;
+        };
+        if id.parse::<u64>().is_err() {
+            diagnostics.push(Diagnostic::at(
+                number,
+                line,
+                id,
+                format!("game id {id:?} is not a number"),
+            ));
+        }
+        for data in draws.split([';', ',']) {
+            let data = data.trim();
+            let Some((count, color)) = data.split_once(' ') else {
+                diagnostics.push(Diagnostic::at(
+                    number,
+                    line,
+                    data,
+                    format!("expected \"<count> <color>\", found {data:?}"),
+                ));
+                continue;
+            };
+            if count.parse::<u64>().is_err() {
+                diagnostics.push(Diagnostic::at(
+                    number,
+                    line,
+                    count,
+                    format!("cube count {count:?} is not a number"),
+                ));
+            }
+            if !matches!(color, "red" | "green" | "blue") {
+                diagnostics.push(Diagnostic::at(
+                    number,
+                    line,
+                    color,
+                    format!("unknown cube color {color:?}"),
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+/// both parts' answers, parsing the input once, for the caller to present
+#[cfg(feature = "std")]
+pub fn answers(text: &str) -> Result<aoc_core::solver::Answers> {
+    Day2.solve(text, ExecutionStrategy::Sequential)
 }
 
 #[cfg(test)]
+// the tests hand back their assertion as `Ok(assert_eq!(..))`
+#[allow(clippy::unit_arg)]
 mod tests {
     use super::*;
 
@@ -228,22 +674,48 @@ mod tests {
     fn should_parse_line() -> Result<()> {
         let text = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
         let expected = game_data();
-        let result = parse_line(text)?;
+        let result = parse_line(text.as_bytes())?;
         Ok(assert_eq!(result, expected))
     }
 
+    #[test]
+    fn should_solve_from_bytes() -> Result<()> {
+        let crlf = PART_ONE_EXAMPLE.replace('\n', "\r\n");
+        assert_eq!(solve_part_one_bytes(crlf.as_bytes())?, 8);
+        assert_eq!(solve_part_two_bytes(PART_TWO_EXAMPLE.as_bytes())?, 2286);
+        let error = solve_part_one_bytes(b"Game 1: 3 blue\nGame 2: 4 r\xffd\n").unwrap_err();
+        assert_eq!(error.line, Some(2));
+        Ok(())
+    }
+
     #[test]
     fn should_find_highest_count_seen() {
         let data = game_data();
-        let expected = HashMap::from([
-            ("blue".to_string(), 6),
-            ("red".to_string(), 4),
-            ("green".to_string(), 2),
-        ]);
+        let expected: FastMap<&str, u64> = [("blue", 6), ("red", 4), ("green", 2)]
+            .into_iter()
+            .collect();
         let result = highest_count_seen(&data);
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn should_parse_the_example() -> Result<()> {
+        let games = Day2::parse(PART_ONE_EXAMPLE, ExecutionStrategy::Sequential)?;
+        insta::assert_debug_snapshot!(games);
+        Ok(())
+    }
+
+    #[cfg(feature = "arena")]
+    #[test]
+    fn arena_variant_should_match() -> Result<()> {
+        for text in [PART_ONE_EXAMPLE, PART_TWO_EXAMPLE] {
+            assert_eq!(arena::solve_part_one(text)?, solve_part_one(text)?);
+            assert_eq!(arena::solve_part_two(text)?, solve_part_two(text)?);
+        }
+        assert!(arena::solve_part_one("Game 1: x blue").is_err());
+        Ok(())
+    }
+
     #[test]
     fn should_find_possible_game() {
         let possible_game_data = game_data();
@@ -263,4 +735,18 @@ mod tests {
         let result = possible_game(bad_count, allowed_for_part_one);
         assert!(!result);
     }
+
+    #[test]
+    fn should_validate_games() {
+        assert_eq!(validate(PART_ONE_EXAMPLE), vec![]);
+        let diagnostics = validate("Game 1: 3 blue, x red; 2 purple\nGame 2 3 blue");
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::new(1, 17, "cube count \"x\" is not a number"),
+                Diagnostic::new(1, 26, "unknown cube color \"purple\""),
+                Diagnostic::new(2, 6, "expected a colon after the game id"),
+            ]
+        );
+    }
 }
@@ -1,11 +1,39 @@
-use std::collections::{
-    hash_map::Entry::{Occupied, Vacant},
-    HashMap,
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec, vec::Vec};
+use core::{iter, mem, ops::Range};
+
+#[cfg(feature = "serde")]
+use aoc_core::export::Model;
+use aoc_core::{
+    collections::FastMap,
+    context,
+    diagnostic::Diagnostic,
+    error::{AocError, Result},
+    explain,
+    incremental::Incremental,
+    overflow, progress,
+    solver::{Answer, DynSolver, Solver},
+    strategy::ExecutionStrategy,
+    telemetry,
+    visualize::{self, Frame, Mark},
 };
+use aoc_utils::{
+    grid::{Grid, Position},
+    parse::lines,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument, warn};
 
-use anyhow::Result;
+/// the example input from the puzzle description for each part
+pub const PART_ONE_EXAMPLE: &str = include_str!("part1_example.txt");
+pub const PART_TWO_EXAMPLE: &str = include_str!("part1_example.txt");
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct PartNumber {
     row: usize,
     begin: usize,
@@ -13,15 +41,13 @@ struct PartNumber {
     number: u64,
 }
 
-#[derive(Debug, Eq, Hash, PartialEq, Copy, Clone)]
-struct SchematicSymbol {
-    row: usize,
-    offset: usize,
-    symbol: char,
+impl PartNumber {
+    /// the cells the number's digits are in
+    fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        (self.begin..=self.end).map(|column| (self.row, column))
+    }
 }
 
-type LookupTable = HashMap<(usize, usize), SchematicSymbol>;
-
 trait Symbol {
     fn is_a_symbol(&self) -> bool;
 }
@@ -32,134 +58,40 @@ impl Symbol for char {
     }
 }
 
-enum ParserMode {
-    Scanning,
-    ParsingNumber,
-}
-
-/// returns a vector of possible part numbers and a hashmap of 3x3 regions mapped to their
-/// symbols
-fn parse(text: &str, row: usize) -> Result<(Vec<PartNumber>, LookupTable)> {
-    let mut chars = text.chars().enumerate().peekable();
-    let mut part_numbers: Vec<PartNumber> = vec![];
-    let mut valid_positions: HashMap<(usize, usize), SchematicSymbol> = HashMap::new();
-    let mut mode = ParserMode::Scanning;
-
-    let mut current_numeric_string = String::new();
-    let mut begin = 0;
-
-    while let Some((i, c)) = chars.next() {
-        match (c.is_ascii_digit(), c.is_a_symbol(), &mode) {
-            // happy path
-
-            // We are scanning and we have found the first digit of
-            // a number
-            (true, false, ParserMode::Scanning) => {
-                mode = ParserMode::ParsingNumber;
-                begin = i;
-                current_numeric_string.push(c);
-            }
-
-            // We are scanning and we have found a symbol
-            (false, true, ParserMode::Scanning) => {
-                update_positions(row, i, c, &mut valid_positions);
-            }
-
-            // We are scanning and we have found nothing interesting
-            (false, false, ParserMode::Scanning) => {
-                // do nothing
-            }
-
-            // We are parsing a number and have found an additional digit
-            (true, false, ParserMode::ParsingNumber) => {
-                current_numeric_string.push(c);
-                // finalize if we have reached the end of the line
-                if chars.peek().is_none() {
-                    finalize_part_number(
-                        &mut mode,
-                        row,
-                        begin,
-                        i,
-                        &mut current_numeric_string,
-                        &mut part_numbers,
-                    )?;
-                }
-            }
-
-            // We are parsing a number and have found a character that is a
-            // symbol, not a number
-            (false, true, ParserMode::ParsingNumber) => {
-                update_positions(row, i, c, &mut valid_positions);
-                finalize_part_number(
-                    &mut mode,
-                    row,
-                    begin,
-                    i - 1,
-                    &mut current_numeric_string,
-                    &mut part_numbers,
-                )?;
-            }
-
-            // We are parsing a number and have found no additional interesting
-            // characters
-            (false, false, ParserMode::ParsingNumber) => {
-                finalize_part_number(
-                    &mut mode,
-                    row,
-                    begin,
-                    i - 1,
-                    &mut current_numeric_string,
-                    &mut part_numbers,
-                )?;
-            }
-
-            // sad path
-            // it should not be possible for a character to be a symbol and a number
-            (true, true, _) => {
-                unreachable!()
-            }
-        }
+/// an error naming the first byte of `line` that isn't ASCII, if there is one
+fn check_ascii(line: &[u8]) -> Result<()> {
+    match line.iter().position(|byte| !byte.is_ascii()) {
+        Some(column) => Err(AocError::new(format!(
+            "unexpected byte {:#04x} in column {}, schematics are ASCII",
+            line[column],
+            column + 1
+        ))),
+        None => Ok(()),
     }
-    Ok((part_numbers, valid_positions))
-}
-
-fn finalize_part_number(
-    mode: &mut ParserMode,
-    row: usize,
-    begin: usize,
-    end: usize,
-    current_numeric_string: &mut String,
-    part_numbers: &mut Vec<PartNumber>,
-) -> Result<()> {
-    *mode = ParserMode::Scanning;
-    let part_number = PartNumber {
-        row,
-        begin,
-        end,
-        // this unwrap should be safe
-        number: current_numeric_string.parse()?,
-    };
-    part_numbers.push(part_number);
-    *current_numeric_string = String::new();
-    Ok(())
 }
 
-fn update_positions(
-    row: usize,
-    i: usize,
-    c: char,
-    valid_positions: &mut HashMap<(usize, usize), SchematicSymbol>,
-) {
-    let symbol = SchematicSymbol {
-        row,
-        offset: i,
-        symbol: c,
-    };
-    for y in (row.saturating_sub(1))..=(row + 1) {
-        for x in (i.saturating_sub(1))..=(i + 1) {
-            valid_positions.insert((x, y), symbol);
+/// find the runs of digits in a row of the schematic
+fn find_numbers(cells: &[char], row: usize) -> Result<Vec<PartNumber>> {
+    let mut part_numbers = vec![];
+    let mut column = 0;
+    while column < cells.len() {
+        if !cells[column].is_ascii_digit() {
+            column += 1;
+            continue;
+        }
+        let begin = column;
+        while column < cells.len() && cells[column].is_ascii_digit() {
+            column += 1;
         }
+        let digits: String = cells[begin..column].iter().collect();
+        part_numbers.push(PartNumber {
+            row,
+            begin,
+            end: column - 1,
+            number: digits.parse()?,
+        });
     }
+    Ok(part_numbers)
 }
 
 ///
@@ -192,45 +124,9 @@ fn update_positions(
 /// number is adjacent to a symbol and so is a part number; their sum is 4361.
 /// ```
 ///
-/// ```
-/// use day3::solve_part_one;
-/// use std::fs;
-///
-/// let text = fs::read_to_string("src/part1_example.txt").unwrap();
-/// let result = solve_part_one(&text).unwrap();
-/// assert_eq!(result, 4361)
-/// ```
-///
-pub fn solve_part_one(text: &str) -> Result<u64> {
-    // build a collection for the part numbers with their row number, start index,
-    // and end index.
-    let mut part_numbers = vec![];
-
-    // build a lookup table for valid positions for numbers, generated by the symbols
-    let mut valid_positions: HashMap<(usize, usize), SchematicSymbol> = HashMap::new();
-
-    for (i, line) in text.lines().enumerate() {
-        let (mut new_part_numbers, mut new_valid_positions) = parse(line, i)?;
-
-        part_numbers.append(&mut new_part_numbers);
-
-        new_valid_positions.drain().for_each(|(k, v)| {
-            valid_positions.insert(k, v);
-        });
-    }
-
-    // filter the collection of numbers using the lookup table for valid positions
-    let valid_parts = part_numbers.iter().filter(|pn| {
-        for x in pn.begin..=pn.end {
-            if valid_positions.contains_key(&(x, pn.row)) {
-                return true;
-            }
-        }
-        false
-    });
-
-    // sum the numbers
-    Ok(valid_parts.map(|pn| pn.number).sum())
+#[instrument(skip_all)]
+pub fn solve_part_one(text: &str) -> Result<Answer> {
+    DynSolver::solve_part_one(&Day3, text, ExecutionStrategy::Sequential)
 }
 
 ///
@@ -260,68 +156,749 @@ pub fn solve_part_one(text: &str) -> Result<u64> {
 /// adjacent to one part number.) Adding up all of the gear ratios produces 467835.
 /// ```
 ///
-/// ```
-/// use day3::solve_part_two;
-/// use std::fs;
-///
-/// let text = fs::read_to_string("src/part1_example.txt").unwrap();
-/// let result = solve_part_two(&text).unwrap();
-/// assert_eq!(result, 467835)
-/// ```
-///
-pub fn solve_part_two(text: &str) -> Result<u64> {
-    // build a collection for the part numbers with their row number, start index,
-    // and end index.
-    let mut part_numbers = vec![];
+#[instrument(skip_all)]
+pub fn solve_part_two(text: &str) -> Result<Answer> {
+    DynSolver::solve_part_two(&Day3, text, ExecutionStrategy::Sequential)
+}
+
+/// [`solve_part_one`] straight from the bytes of the input, such as a
+/// memory mapped file, without checking they're UTF-8 first
+#[instrument(skip_all)]
+pub fn solve_part_one_bytes(input: &[u8]) -> Result<Answer> {
+    DynSolver::solve_part_one_bytes(&Day3, input, ExecutionStrategy::Sequential)
+}
+
+/// [`solve_part_two`] straight from the bytes of the input
+#[instrument(skip_all)]
+pub fn solve_part_two_bytes(input: &[u8]) -> Result<Answer> {
+    DynSolver::solve_part_two_bytes(&Day3, input, ExecutionStrategy::Sequential)
+}
+
+/// The schematic, and the numbers found in it
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Schematic {
+    grid: Grid<char>,
+    part_numbers: Vec<PartNumber>,
+    /// whether each cell is next to a symbol, diagonals included, so finding
+    /// part numbers reads a cell per digit rather than looking around it
+    near_symbol: Grid<bool>,
+}
+
+impl Schematic {
+    /// whether a symbol is next to any of the number's digits
+    fn is_part_number(&self, part_number: &PartNumber) -> bool {
+        part_number
+            .positions()
+            .any(|position| self.near_symbol[position])
+    }
+
+    /// the symbols next to a number, diagonals included, with their positions
+    fn adjacent_symbols<'a>(
+        &'a self,
+        part_number: &'a PartNumber,
+    ) -> impl Iterator<Item = (Position, char)> + 'a {
+        adjacent_symbols(&self.grid, part_number)
+    }
+}
 
-    // build a lookup table for valid positions for numbers, generated by the symbols
-    let mut valid_positions: HashMap<(usize, usize), SchematicSymbol> = HashMap::new();
+/// the symbols in `grid` next to a number, diagonals included, with their
+/// positions. The cells around a number are the rectangle a cell bigger than
+/// it on every side, so each is looked at once.
+fn adjacent_symbols<'a>(
+    grid: &'a Grid<char>,
+    part_number: &'a PartNumber,
+) -> impl Iterator<Item = (Position, char)> + 'a {
+    let rows = part_number.row.saturating_sub(1)..(part_number.row + 2).min(grid.height());
+    let columns = part_number.begin.saturating_sub(1)..(part_number.end + 2).min(grid.width());
+    rows.flat_map(move |row| columns.clone().map(move |column| (row, column)))
+        .map(|position| (position, grid[position]))
+        .filter(|(_, c)| c.is_a_symbol())
+}
+
+/// which cells of `grid` are next to a symbol, diagonals included
+fn near_symbol(grid: &Grid<char>) -> Grid<bool> {
+    let mut near = Grid::new(grid.width(), grid.height(), false);
+    for (position, _) in grid.cells().filter(|(_, c)| c.is_a_symbol()) {
+        for neighbor in grid.neighbors8(position) {
+            near[neighbor] = true;
+        }
+    }
+    near
+}
 
-    for (i, line) in text.lines().enumerate() {
-        let (mut new_part_numbers, mut new_valid_positions) = parse(line, i)?;
+/// Both parts look for numbers next to symbols, so the schematic is parsed
+/// into a grid along with where the numbers are in it
+pub struct Day3;
 
-        part_numbers.append(&mut new_part_numbers);
+impl Solver for Day3 {
+    const DAY: usize = 3;
+    const TITLE: &'static str = "Gear Ratios";
+    const TAGS: &'static [&'static str] = &["grid"];
+    type Parsed<'a> = Schematic;
 
-        new_valid_positions.drain().for_each(|(k, v)| {
-            valid_positions.insert(k, v);
+    fn parse(text: &str, strategy: ExecutionStrategy) -> Result<Schematic> {
+        Self::parse_bytes(text.as_bytes(), strategy)
+    }
+
+    /// schematics are ASCII, so the input is checked for that rather than
+    /// for being UTF-8, and each byte becomes a cell of the grid
+    fn parse_bytes(input: &[u8], strategy: ExecutionStrategy) -> Result<Schematic> {
+        for (row, line) in lines(input).enumerate() {
+            check_ascii(line).map_err(|e| e.at_line(row + 1))?;
+        }
+        let first_width = lines(input).next().map(<[u8]>::len);
+        let width = lines(input).map(<[u8]>::len).max().unwrap_or(0);
+        let height = lines(input).count();
+        // the grid and which of its cells are near a symbol are a char and a
+        // bool per cell, which a giant schematic could run out of memory on
+        let cells = width.saturating_mul(height);
+        context::reserve(
+            cells.saturating_mul(mem::size_of::<char>() + mem::size_of::<bool>()),
+            "schematic grid",
+        )?;
+        progress::start("rows", height as u64);
+        // rows shorter than the rest are padded with periods, which are just
+        // empty space
+        let grid = Grid::from_rows(lines(input).enumerate().map(|(i, line)| {
+            if Some(line.len()) != first_width {
+                warn!(
+                    row = i,
+                    width = line.len(),
+                    "schematic row width differs from the first row"
+                );
+            }
+            progress::advance(1);
+            line.iter()
+                .map(|&byte| char::from(byte))
+                .chain(iter::repeat('.'))
+                .take(width)
+                .collect()
+        }))?;
+        progress::finish();
+
+        let rows: Vec<usize> = (0..grid.height()).collect();
+        let part_numbers: Vec<PartNumber> = strategy
+            .try_map(&rows, |_, &row| {
+                find_numbers(grid.row(row), row).map_err(|e| e.at_line(row + 1))
+            })?
+            .into_iter()
+            .flatten()
+            .collect();
+        telemetry::lines(grid.height());
+        telemetry::entries(part_numbers.len());
+        debug!(part_numbers = part_numbers.len(), "parsed schematic");
+
+        Ok(Schematic {
+            near_symbol: near_symbol(&grid),
+            grid,
+            part_numbers,
+        })
+    }
+
+    fn part1(schematic: &Schematic, strategy: ExecutionStrategy) -> Result<Answer> {
+        // a number is a part number when there's a symbol next to it
+        let parts = strategy.map(&schematic.part_numbers, |pn| {
+            let part = schematic.is_part_number(pn);
+            explain::step(|| match schematic.adjacent_symbols(pn).next() {
+                Some(((row, column), c)) => format!(
+                    "{} counted because {c:?} at ({row},{column}) is adjacent",
+                    pn.number
+                ),
+                None => format!("{} not counted, no symbol is adjacent", pn.number),
+            });
+            part.then_some(pn.number)
         });
+        if visualize::is_active() {
+            emit_part_numbers(schematic, &parts);
+        }
+        let valid_parts: Vec<u64> = parts.into_iter().flatten().collect();
+
+        // sum the numbers
+        telemetry::entries(valid_parts.len());
+        debug!(valid_parts = valid_parts.len(), "filtered part numbers");
+        Ok(overflow::policy().sum(valid_parts)?.into())
     }
 
-    // build a table to store our gear ratios
-    let mut unvalidated_gear_ratios: HashMap<SchematicSymbol, Vec<u64>> = HashMap::new();
+    fn part2(schematic: &Schematic, strategy: ExecutionStrategy) -> Result<Answer> {
+        // find the gears next to each number
+        let gears: Vec<Vec<Position>> = strategy.map(&schematic.part_numbers, |pn| {
+            schematic
+                .adjacent_symbols(pn)
+                .filter(|(_, symbol)| *symbol == '*')
+                .map(|(position, _)| position)
+                .collect()
+        });
+
+        // build a table of the numbers next to each gear
+        let mut unvalidated_gear_ratios: FastMap<Position, Vec<u64>> = FastMap::default();
+        for (pn, positions) in schematic.part_numbers.iter().zip(gears) {
+            for position in positions {
+                unvalidated_gear_ratios
+                    .entry(position)
+                    .or_default()
+                    .push(pn.number);
+            }
+        }
 
-    part_numbers.iter().for_each(|pn| {
-        for x in pn.begin..=pn.end {
-            if let Some(entry) = valid_positions.get(&(x, pn.row)) {
-                if entry.symbol != '*' {
-                    continue;
+        // validate our gear ratios
+        let valid_gear_ratios = unvalidated_gear_ratios.iter().filter(|(_, v)| v.len() == 2);
+        telemetry::entries(unvalidated_gear_ratios.len());
+        debug!(
+            candidates = unvalidated_gear_ratios.len(),
+            "collected gear candidates"
+        );
+        let policy = overflow::policy();
+        let ratios = valid_gear_ratios
+            .map(|(_, v)| policy.product(v.iter().copied()))
+            .collect::<Result<Vec<u128>>>()?;
+        let total = policy.sum(ratios)?;
+        if explain::is_active() {
+            explain_gears(&unvalidated_gear_ratios);
+        }
+        visualize::emit(|| {
+            let gears = unvalidated_gear_ratios
+                .iter()
+                .filter(|(_, numbers)| numbers.len() == 2)
+                .count();
+            unvalidated_gear_ratios.iter().fold(
+                Frame::new(rows(&schematic.grid))
+                    .annotate(format!("{gears} gears, their ratios sum to {total}")),
+                |frame, (&(row, column), numbers)| match numbers.len() {
+                    2 => frame.highlight(row, column, Mark::Accepted),
+                    _ => frame.highlight(row, column, Mark::Rejected),
+                },
+            )
+        });
+        Ok(total.into())
+    }
+}
+
+/// the grid as text, a row per line, for a frame
+fn rows(grid: &Grid<char>) -> Vec<String> {
+    (0..grid.height())
+        .map(|row| grid.row(row).iter().collect())
+        .collect()
+}
+
+/// explain which `*`s are gears, top to bottom, since the table of them is in
+/// no particular order
+fn explain_gears(candidates: &FastMap<Position, Vec<u64>>) {
+    let mut candidates: Vec<_> = candidates.iter().collect();
+    candidates.sort_unstable_by_key(|(position, _)| **position);
+    for (&(row, column), numbers) in candidates {
+        explain::step(|| match numbers[..] {
+            [first, second] => format!(
+                "'*' at ({row},{column}) is a gear, next to {first} and {second}, ratio {}",
+                u128::from(first) * u128::from(second)
+            ),
+            _ => format!(
+                "'*' at ({row},{column}) isn't a gear, it's next to {}",
+                explain::plural(numbers.len() as u128, "number", "numbers")
+            ),
+        });
+    }
+}
+
+/// emit a frame for each row with numbers in it, marking which of them are
+/// part numbers and which aren't, with the total so far
+fn emit_part_numbers(schematic: &Schematic, parts: &[Option<u64>]) {
+    let rows = rows(&schematic.grid);
+    let mut total: u128 = 0;
+    let numbers: Vec<(&PartNumber, &Option<u64>)> =
+        schematic.part_numbers.iter().zip(parts).collect();
+    for row in numbers.chunk_by(|(a, _), (b, _)| a.row == b.row) {
+        let mut frame = Frame::new(rows.clone());
+        for (pn, part) in row {
+            let mark = match part {
+                Some(number) => {
+                    total += u128::from(*number);
+                    Mark::Accepted
                 }
-                match unvalidated_gear_ratios.entry(*entry) {
-                    Occupied(mut existing_entry) => {
-                        existing_entry.get_mut().push(pn.number);
+                None => Mark::Rejected,
+            };
+            for (row, column) in pn.positions() {
+                frame = frame.highlight(row, column, mark);
+            }
+        }
+        let found = row.iter().filter(|(_, part)| part.is_some()).count();
+        visualize::emit(|| {
+            frame.annotate(format!(
+                "row {}: {found} of {} numbers are part numbers, {total} so far",
+                row[0].0.row + 1,
+                row.len()
+            ))
+        });
+    }
+}
+
+/// Whether a number is a part number, and whether a `*` is a gear, only
+/// depends on the rows either side of it, so the numbers in each row and what
+/// each row adds to the answers are kept, and an edit only redoes the rows it
+/// touches and the rows next to them
+#[derive(Default)]
+pub struct Rows {
+    rows: Vec<Vec<char>>,
+    /// the numbers in each row. Their `row`s go stale as the rows around
+    /// them are added and removed, so only the row they're kept in counts.
+    numbers: Vec<Result<Vec<PartNumber>>>,
+    /// the part numbers in the row, and the gear ratios of the gears in it
+    sums: Vec<Result<[u128; 2]>>,
+}
+
+impl Rows {
+    /// the cell at `row` and `column`, with everything outside the schematic
+    /// empty space, as short rows are padded when parsing
+    fn cell(&self, row: usize, column: usize) -> char {
+        let cell = self.rows.get(row).and_then(|cells| cells.get(column));
+        cell.copied().unwrap_or('.')
+    }
+
+    /// the rows either side of `row`, and `row` itself
+    fn around(&self, row: usize) -> Range<usize> {
+        row.saturating_sub(1)..(row + 2).min(self.rows.len())
+    }
+
+    fn row_sums(&self, row: usize) -> Result<[u128; 2]> {
+        let policy = overflow::policy();
+        let numbers = self.numbers[row].as_ref().map_err(Clone::clone)?;
+        let parts = numbers.iter().filter(|pn| {
+            let columns = pn.begin.saturating_sub(1)..=pn.end + 1;
+            self.around(row).any(|row| {
+                columns
+                    .clone()
+                    .any(|column| self.cell(row, column).is_a_symbol())
+            })
+        });
+        let parts = policy.sum(parts.map(|pn| pn.number))?;
+
+        let mut ratios = 0;
+        for (column, _) in self.rows[row]
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c == '*')
+        {
+            let mut touching = vec![];
+            for numbers in &self.numbers[self.around(row)] {
+                let numbers = numbers.as_ref().map_err(Clone::clone)?;
+                touching.extend(
+                    numbers
+                        .iter()
+                        .filter(|pn| pn.begin <= column + 1 && column <= pn.end + 1)
+                        .map(|pn| pn.number),
+                );
+            }
+            if touching.len() == 2 {
+                ratios = policy.add(ratios, policy.product(touching)?)?;
+            }
+        }
+        Ok([parts, ratios])
+    }
+
+    fn sum(&self, part: usize) -> Result<Answer> {
+        // a row that didn't parse fails the rows around it too, so it's
+        // reported first, at the line it's on now
+        for (row, numbers) in self.numbers.iter().enumerate() {
+            if let Err(e) = numbers {
+                return Err(e.clone().at_line(row + 1));
+            }
+        }
+        let sums = self
+            .sums
+            .iter()
+            .map(|sums| sums.as_ref().map(|sums| sums[part]).map_err(Clone::clone))
+            .collect::<Result<Vec<u128>>>()?;
+        Ok(overflow::policy().sum(sums)?.into())
+    }
+}
+
+impl Incremental for Rows {
+    fn splice(&mut self, changed: Range<usize>, lines: &[&str]) {
+        let start = changed.start;
+        let end = start + lines.len();
+        self.rows.splice(
+            changed.clone(),
+            lines.iter().map(|line| line.chars().collect()),
+        );
+        // a line that isn't ASCII fails like it would in a full parse
+        let numbers: Vec<_> = (start..end)
+            .zip(lines)
+            .map(|(row, line)| {
+                check_ascii(line.as_bytes())?;
+                find_numbers(&self.rows[row], row)
+            })
+            .collect();
+        self.numbers.splice(changed.clone(), numbers);
+        self.sums
+            .splice(changed, iter::repeat_with(|| Ok([0; 2])).take(lines.len()));
+
+        for row in start.saturating_sub(1)..(end + 1).min(self.rows.len()) {
+            self.sums[row] = self.row_sums(row);
+        }
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        self.sum(0).map_err(|e| e.with_part(1))
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        self.sum(1).map_err(|e| e.with_part(2))
+    }
+}
+
+/// A symbol in the schematic and where it is, as `aoc parse` shows it
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct SchematicSymbol {
+    row: usize,
+    column: usize,
+    symbol: char,
+}
+
+/// The schematic as `aoc parse` shows it: the numbers and symbols, without the
+/// periods between them
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct SchematicModel<'a> {
+    width: usize,
+    height: usize,
+    part_numbers: &'a [PartNumber],
+    symbols: Vec<SchematicSymbol>,
+}
+
+#[cfg(feature = "serde")]
+impl Model for Day3 {
+    fn model<'a, 'b: 'a>(schematic: &'a Schematic) -> impl Serialize + 'a {
+        let symbols = schematic
+            .grid
+            .cells()
+            .filter(|(_, c)| c.is_a_symbol())
+            .map(|((row, column), &symbol)| SchematicSymbol {
+                row,
+                column,
+                symbol,
+            })
+            .collect();
+        SchematicModel {
+            width: schematic.grid.width(),
+            height: schematic.grid.height(),
+            part_numbers: &schematic.part_numbers,
+            symbols,
+        }
+    }
+}
+
+aoc_core::register! {
+    solver: Day3,
+    examples: [PART_ONE_EXAMPLE, PART_TWO_EXAMPLE],
+    expected: [Some(Answer::U64(4361)), Some(Answer::U64(467835))],
+    validate: validate,
+    algorithms: &[
+        aoc_core::solver::Algorithm::solver("vec"),
+        #[cfg(feature = "arena")]
+        aoc_core::solver::Algorithm::new("arena", arena::solve_part_one, arena::solve_part_two),
+    ],
+    incremental: || alloc::boxed::Box::new(Rows::default()),
+    persist: Day3,
+    export: Day3,
+}
+
+/// Variant that keeps the part numbers, and the numbers collected for each
+/// gear, in a bumpalo arena rather than a `Vec` per row and per gear, so a
+/// large schematic costs a handful of allocations that are all freed together
+/// once the answer is found. The grid is parsed as usual.
+#[cfg(feature = "arena")]
+pub mod arena {
+    use bumpalo::{collections::Vec as ArenaVec, Bump};
+
+    use super::*;
+
+    /// the numbers in every row of `grid`, in reading order
+    fn find_all_numbers<'a>(grid: &Grid<char>, arena: &'a Bump) -> ArenaVec<'a, PartNumber> {
+        let mut part_numbers = ArenaVec::new_in(arena);
+        for row in 0..grid.height() {
+            let cells = grid.row(row);
+            let mut column = 0;
+            while column < cells.len() {
+                let begin = column;
+                let mut number: u64 = 0;
+                while let Some(digit) = cells.get(column).and_then(|c| c.to_digit(10)) {
+                    number = number.saturating_mul(10).saturating_add(digit.into());
+                    column += 1;
+                }
+                match column > begin {
+                    true => part_numbers.push(PartNumber {
+                        row,
+                        begin,
+                        end: column - 1,
+                        number,
+                    }),
+                    false => column += 1,
+                }
+            }
+        }
+        part_numbers
+    }
+
+    /// parse the schematic, its numbers into `arena`, and solve from them,
+    /// freeing everything in the arena when it's dropped
+    fn solve(
+        text: &str,
+        solve: impl Fn(&Grid<char>, &[PartNumber], &Bump) -> Result<u128>,
+    ) -> Result<Answer> {
+        // it's a solve of its own, with a memory budget of its own
+        context::fresh(|| {
+            let grid = Day3::parse(text, ExecutionStrategy::Sequential)?.grid;
+            let arena = Bump::new();
+            let part_numbers = find_all_numbers(&grid, &arena);
+            let answer = solve(&grid, &part_numbers, &arena)?;
+            debug!(
+                part_numbers = part_numbers.len(),
+                bytes = arena.allocated_bytes(),
+                "solved from arena"
+            );
+            Ok(answer.into())
+        })
+    }
+
+    #[instrument(name = "arena::solve_part_one", skip_all)]
+    pub fn solve_part_one(text: &str) -> Result<Answer> {
+        solve(text, |grid, part_numbers, _| {
+            let parts = part_numbers
+                .iter()
+                .filter(|pn| adjacent_symbols(grid, pn).next().is_some())
+                .map(|pn| pn.number);
+            overflow::policy().sum(parts)
+        })
+    }
+
+    #[instrument(name = "arena::solve_part_two", skip_all)]
+    pub fn solve_part_two(text: &str) -> Result<Answer> {
+        solve(text, |grid, part_numbers, arena| {
+            let mut gears: FastMap<Position, ArenaVec<'_, u64>> = FastMap::default();
+            for pn in part_numbers {
+                for (position, symbol) in adjacent_symbols(grid, pn) {
+                    if symbol == '*' {
+                        gears
+                            .entry(position)
+                            .or_insert_with(|| ArenaVec::new_in(arena))
+                            .push(pn.number);
                     }
-                    Vacant(new_entry) => {
-                        new_entry.insert(vec![pn.number]);
+                }
+            }
+            let policy = overflow::policy();
+            let ratios = gears
+                .values()
+                .filter(|numbers| numbers.len() == 2)
+                .map(|numbers| policy.product(numbers.iter().copied()))
+                .collect::<Result<Vec<u128>>>()?;
+            policy.sum(ratios)
+        })
+    }
+}
+
+/// Slow but obviously correct versions of both parts, comparing every number
+/// with every cell of the schematic, for tests to check the solvers against
+#[cfg(feature = "reference")]
+pub mod reference {
+    use super::*;
+
+    /// a number's value, row and first and last columns
+    type Number = (u128, usize, usize, usize);
+
+    fn numbers(rows: &[Vec<char>]) -> Result<Vec<Number>> {
+        let mut numbers = vec![];
+        for (row, cells) in rows.iter().enumerate() {
+            let mut begin = None;
+            for column in 0..=cells.len() {
+                let digit = cells.get(column).is_some_and(char::is_ascii_digit);
+                match (begin, digit) {
+                    (None, true) => begin = Some(column),
+                    (Some(start), false) => {
+                        let digits: String = cells[start..column].iter().collect();
+                        numbers.push((digits.parse()?, row, start, column - 1));
+                        begin = None;
                     }
+                    _ => {}
                 }
-                break;
             }
         }
-    });
-
-    // validate our gear ratios
-    let valid_gear_ratios = unvalidated_gear_ratios.iter().filter(|(_, v)| v.len() == 2);
-    let sum = valid_gear_ratios
-        .map(|(_, v)| v.iter().product::<u64>())
-        .sum();
-    Ok(sum)
+        Ok(numbers)
+    }
+
+    /// whether the cell at `row` and `column` touches `number`, diagonals
+    /// included
+    fn touches(&(_, number_row, begin, end): &Number, row: usize, column: usize) -> bool {
+        row + 1 >= number_row && row <= number_row + 1 && column + 1 >= begin && column <= end + 1
+    }
+
+    /// every cell in the schematic, with its position
+    fn cells(rows: &[Vec<char>]) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+        rows.iter().enumerate().flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(move |(column, c)| (row, column, *c))
+        })
+    }
+
+    fn rows(text: &str) -> Vec<Vec<char>> {
+        text.lines().map(|line| line.chars().collect()).collect()
+    }
+
+    pub fn part_one(text: &str) -> Result<u128> {
+        let rows = rows(text);
+        let mut total = 0;
+        for number in numbers(&rows)? {
+            if cells(&rows).any(|(row, column, c)| c.is_a_symbol() && touches(&number, row, column))
+            {
+                total += number.0;
+            }
+        }
+        Ok(total)
+    }
+
+    pub fn part_two(text: &str) -> Result<u128> {
+        let rows = rows(text);
+        let numbers = numbers(&rows)?;
+        let mut total = 0;
+        for (row, column, _) in cells(&rows).filter(|(_, _, c)| *c == '*') {
+            let touching: Vec<&Number> = numbers
+                .iter()
+                .filter(|number| touches(number, row, column))
+                .collect();
+            if let [first, second] = touching[..] {
+                total += first.0 * second.0;
+            }
+        }
+        Ok(total)
+    }
+}
+
+/// check the schematic is a rectangle of digits, periods and symbols, without
+/// solving
+pub fn validate(text: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut width = None;
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let columns = line.chars().count();
+        match width {
+            None => width = Some(columns),
+            // the solvers pad short rows with periods, so this only warns
+            Some(expected) if expected != columns => diagnostics.push(
+                Diagnostic::new(
+                    number,
+                    columns.min(expected) + 1,
+                    format!("row is {columns} characters wide, expected {expected}"),
+                )
+                .warning(),
+            ),
+            Some(_) => {}
+        }
+        for (column, c) in line.chars().enumerate() {
+            if c.is_whitespace() || !c.is_ascii() {
+                diagnostics.push(Diagnostic::new(
+                    number,
+                    column + 1,
+                    format!("unexpected character {c:?}"),
+                ));
+            }
+        }
+    }
+    diagnostics
 }
 
-pub fn print_answers(text: &str) -> Result<()> {
-    let part_one = solve_part_one(text)?;
-    let part_two = solve_part_two(text)?;
+/// both parts' answers, parsing the input once, for the caller to present
+#[cfg(feature = "std")]
+pub fn answers(text: &str) -> Result<aoc_core::solver::Answers> {
+    Day3.solve(text, ExecutionStrategy::Sequential)
+}
 
-    println!("part one: {part_one}");
-    println!("part two: {part_two}");
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::{context::SolveContext, error::MemoryExceeded};
+
+    /// the schematic's size and, for each number, where it is and the
+    /// symbols next to it
+    fn summary(schematic: &Schematic) -> String {
+        let mut summary = format!("{}x{}\n", schematic.grid.width(), schematic.grid.height());
+        for pn in &schematic.part_numbers {
+            let symbols: String = schematic.adjacent_symbols(pn).map(|(_, c)| c).collect();
+            summary.push_str(&format!(
+                "{} at row {}, columns {}-{}, next to {symbols:?}\n",
+                pn.number, pn.row, pn.begin, pn.end
+            ));
+        }
+        summary
+    }
+
+    #[test]
+    fn should_parse_the_example() -> Result<()> {
+        let schematic = Day3::parse(PART_ONE_EXAMPLE, ExecutionStrategy::Sequential)?;
+        insta::assert_snapshot!(summary(&schematic));
+        Ok(())
+    }
+
+    #[test]
+    fn should_find_part_numbers_from_the_bitmap() -> Result<()> {
+        let schematic = Day3::parse(PART_ONE_EXAMPLE, ExecutionStrategy::Sequential)?;
+        let not_parts: Vec<u64> = schematic
+            .part_numbers
+            .iter()
+            .filter(|pn| {
+                let part = schematic.is_part_number(pn);
+                assert_eq!(part, schematic.adjacent_symbols(pn).next().is_some());
+                !part
+            })
+            .map(|pn| pn.number)
+            .collect();
+        assert_eq!(not_parts, [114, 58]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_solve_from_bytes() -> Result<()> {
+        let crlf = PART_ONE_EXAMPLE.replace('\n', "\r\n");
+        assert_eq!(solve_part_one_bytes(crlf.as_bytes())?, 4361);
+        assert_eq!(solve_part_two_bytes(PART_TWO_EXAMPLE.as_bytes())?, 467835);
+        // a byte that isn't ASCII is an error rather than a symbol
+        let error = solve_part_one_bytes(b"12.\n.\xff.").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 3 part 1, line 2: unexpected byte 0xff in column 2, schematics are ASCII"
+        );
+        assert!(solve_part_two_bytes("12.\n.é.".as_bytes()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn should_refuse_an_edit_that_isnt_ascii() -> Result<()> {
+        let mut rows = Rows::default();
+        rows.splice(0..0, &["467..114..", "...*......", "..35..633."]);
+        assert_eq!(rows.part1()?, Answer::U64(502));
+        rows.splice(1..2, &["..é*......"]);
+        assert_eq!(
+            rows.part1().unwrap_err().to_string(),
+            "part 1, line 2: unexpected byte 0xc3 in column 3, schematics are ASCII"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_refuse_a_schematic_over_the_memory_budget() {
+        // the example's ten rows of ten cells, at five bytes a cell
+        let context = SolveContext::new(ExecutionStrategy::Sequential).with_memory_budget(500);
+        assert!(Day3.solve_with(PART_ONE_EXAMPLE, &context).is_ok());
+
+        let context = context.with_memory_budget(499);
+        let error = Day3.solve_with(PART_ONE_EXAMPLE, &context).unwrap_err();
+        assert_eq!(
+            error.memory,
+            Some(MemoryExceeded {
+                needed: 500,
+                budget: 499
+            })
+        );
+    }
 }
@@ -0,0 +1,25 @@
+use std::{env, fmt::Write, fs, path::Path};
+
+/// Generate `POINTS`, what a card with each number of matches up to 64 is
+/// worth: nothing for none, then 1 doubled for every match after the first.
+/// Every entry fits a `u64`, so looking one up never needs the overflow
+/// policy. Cards with more matches are worth more than a `u64` holds.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let mut points = String::new();
+    for matches in 0..=64u32 {
+        let worth = match matches {
+            0 => 0,
+            _ => 1u64 << (matches - 1),
+        };
+        writeln!(points, "    {worth},").unwrap();
+    }
+    let code = format!(
+        "/// what a card is worth, indexed by how many of its numbers match
+const POINTS: [u64; 65] = [
+{points}];
+"
+    );
+    let out_dir = env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    fs::write(Path::new(&out_dir).join("points.rs"), code).expect("OUT_DIR is writable");
+}
@@ -1,9 +1,36 @@
-use std::collections::{
-    hash_map::Entry::{Occupied, Vacant},
-    HashMap, HashSet,
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{format, vec::Vec};
+use core::ops::Range;
+
+#[cfg(feature = "serde")]
+use aoc_core::export::Model;
+use aoc_core::{
+    collections::{
+        hash_map::Entry::{Occupied, Vacant},
+        FastMap, FastSet,
+    },
+    context,
+    diagnostic::Diagnostic,
+    error::{AocError, Result},
+    explain,
+    incremental::Incremental,
+    overflow::{self, OverflowPolicy},
+    progress,
+    solver::{Answer, DynSolver, Solver},
+    strategy::ExecutionStrategy,
+    telemetry,
 };
+use aoc_utils::parse::{fields, lines, parse_u64, split_once};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument, trace, warn};
 
-use anyhow::{anyhow, Context, Result};
+/// the example input from the puzzle description for each part
+pub const PART_ONE_EXAMPLE: &str = include_str!("part1_example.txt");
+pub const PART_TWO_EXAMPLE: &str = include_str!("part1_example.txt");
 
 ///
 /// ```txt
@@ -45,54 +72,9 @@ use anyhow::{anyhow, Context, Result};
 /// So, in this example, the Elf's pile of scratchcards is worth 13 points.
 /// ```
 ///
-/// ```
-/// use day4::solve_part_one;
-/// use std::fs;
-///
-/// let text = fs::read_to_string("src/part1_example.txt").unwrap();
-/// let result = solve_part_one(&text).unwrap();
-/// assert_eq!(result, 13)
-/// ```
-///
-pub fn solve_part_one(text: &str) -> Result<u64> {
-    let mut total_points = 0;
-
-    for line in text.lines() {
-        // split card prefix
-        let (_id, useful_text) = line
-            .split_once(':')
-            .ok_or(anyhow!("malformatted line, no colon separated data"))?;
-
-        // split list of numbers
-        let (winning_numbers, our_numbers) = useful_text
-            .split_once('|')
-            .ok_or(anyhow!("malformatted line, no '|' separated data"))?;
-
-        let winning_numbers: Vec<u64> = winning_numbers
-            .split_ascii_whitespace()
-            .map(|number| number.parse::<u64>().map_err(|e| anyhow!(e)))
-            .collect::<Result<Vec<u64>>>()?;
-
-        let winning_numbers: HashSet<u64> = HashSet::from_iter(winning_numbers);
-
-        let our_numbers: Vec<u64> = our_numbers
-            .split_ascii_whitespace()
-            .map(|number| number.parse::<u64>().map_err(|e| anyhow!(e)))
-            .collect::<Result<Vec<u64>>>()?;
-
-        let number_of_matches = our_numbers
-            .iter()
-            .filter(|n| winning_numbers.contains(n))
-            .count();
-
-        if number_of_matches > 0 {
-            // points is (matches - 1) to the power of 2
-            let card_points = 1 << (number_of_matches - 1);
-            total_points += card_points;
-        }
-    }
-
-    Ok(total_points)
+#[instrument(skip_all)]
+pub fn solve_part_one(text: &str) -> Result<Answer> {
+    DynSolver::solve_part_one(&Day4, text, ExecutionStrategy::Sequential)
 }
 
 ///
@@ -127,103 +109,583 @@ pub fn solve_part_one(text: &str) -> Result<u64> {
 /// have 30 scratchcards!
 /// ```
 ///
-/// ```
-/// use day4::solve_part_two;
-/// use std::fs;
-///
-/// let text = fs::read_to_string("src/part1_example.txt").unwrap();
-/// let result = solve_part_two(&text).unwrap();
-/// assert_eq!(result, 30)
-/// ```
-///
-pub fn solve_part_two(text: &str) -> Result<u64> {
-    let mut card_counts: HashMap<usize, usize> = HashMap::new();
-    let mut lines = text.lines().peekable();
-    let mut sum: u64 = 0;
-
-    while let Some(line) = lines.next() {
-        // split card prefix
-        let (id, useful_text) = line
-            .split_once(':')
-            .ok_or(anyhow!("malformatted line, no colon separated data"))?;
-
-        // split number from card id
-        let (_, card_number) = id.split_once(' ').ok_or(anyhow!("malformatted card id"))?;
-        let card_number = card_number
-            .trim()
-            .parse()
-            .with_context(|| "failed to parse card number")?;
-
-        match card_counts.entry(card_number) {
-            Occupied(mut existing_entry) => {
-                *existing_entry.get_mut() += 1;
-            }
-            Vacant(new_entry) => {
-                new_entry.insert(1);
-            }
+#[instrument(skip_all)]
+pub fn solve_part_two(text: &str) -> Result<Answer> {
+    DynSolver::solve_part_two(&Day4, text, ExecutionStrategy::Sequential)
+}
+
+/// [`solve_part_one`] straight from the bytes of the input, such as a
+/// memory mapped file, without checking they're UTF-8 first
+#[instrument(skip_all)]
+pub fn solve_part_one_bytes(input: &[u8]) -> Result<Answer> {
+    DynSolver::solve_part_one_bytes(&Day4, input, ExecutionStrategy::Sequential)
+}
+
+/// [`solve_part_two`] straight from the bytes of the input
+#[instrument(skip_all)]
+pub fn solve_part_two_bytes(input: &[u8]) -> Result<Answer> {
+    DynSolver::solve_part_two_bytes(&Day4, input, ExecutionStrategy::Sequential)
+}
+
+/// A scratchcard, reduced to how many of our numbers are winning numbers
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Card {
+    number: usize,
+    matches: usize,
+}
+
+// `POINTS`, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/points.rs"));
+
+/// what a card with `matches` matches is worth, 2 to the power of one less
+/// than the matches. Only a card past the end of [`POINTS`], worth more than a
+/// `u64` holds, is worked out and goes through the overflow policy.
+fn points(matches: usize, policy: OverflowPolicy) -> Result<u128> {
+    match POINTS.get(matches) {
+        Some(&points) => Ok(points.into()),
+        None => policy.pow2(matches as u32 - 1),
+    }
+}
+
+/// the numbers in `list` when it's laid out as every card is, in columns three
+/// bytes wide holding a space and then one or two digits right aligned. Each
+/// number is worked out from its two bytes without branching on how many
+/// digits it has, a space counting as a leading zero, and the whole list is
+/// checked at once rather than byte by byte. A list laid out any other way is
+/// `None`, for [`count_matches`] to parse a field at a time.
+fn columns(list: &[u8]) -> Option<impl Iterator<Item = u32> + '_> {
+    let list = list.strip_suffix(b" ").unwrap_or(list);
+    if !list.len().is_multiple_of(3) {
+        return None;
+    }
+    let malformed = list.chunks_exact(3).fold(false, |malformed, column| {
+        let [space, tens, ones] = [column[0], column[1], column[2]];
+        malformed
+            | (space != b' ')
+            | ((tens != b' ') & !tens.is_ascii_digit())
+            | !ones.is_ascii_digit()
+    });
+    if malformed {
+        return None;
+    }
+    Some(
+        list.chunks_exact(3)
+            .map(|column| u32::from(column[1] & 0x0f) * 10 + u32::from(column[2] - b'0')),
+    )
+}
+
+/// how many of `our_numbers` are among `winning_numbers`, for lists that
+/// aren't in columns, such as hand written ones
+fn count_matches(winning_numbers: &[u8], our_numbers: &[u8]) -> Result<usize> {
+    let winning_numbers = fields(winning_numbers)
+        .map(parse_u64)
+        .collect::<Result<FastSet<u64>>>()?;
+
+    let mut number_of_matches = 0;
+    for number in fields(our_numbers) {
+        if winning_numbers.contains(&parse_u64(number)?) {
+            number_of_matches += 1;
         }
+    }
+    Ok(number_of_matches)
+}
 
-        // split list of numbers
-        let (winning_numbers, our_numbers) = useful_text
-            .split_once('|')
-            .ok_or(anyhow!("malformatted line, no '|' separated data"))?;
+/// parse a card, keeping only its number and how many of our numbers won
+fn parse_card(line: &[u8]) -> Result<Card> {
+    // split card prefix
+    let (id, useful_text) = split_once(line, b':')
+        .ok_or(AocError::new("malformatted line, no colon separated data"))?;
 
-        let winning_numbers: Vec<u64> = winning_numbers
-            .split_ascii_whitespace()
-            .map(|number| number.parse::<u64>().map_err(|e| anyhow!(e)))
-            .collect::<Result<Vec<u64>>>()?;
+    // split number from card id
+    let (_, card_number) = split_once(id, b' ').ok_or(AocError::new("malformatted card id"))?;
+    let card_number = parse_u64(card_number.trim_ascii())
+        .map_err(|e| AocError::new(format!("failed to parse card number: {e}")))?
+        as usize;
 
-        let winning_numbers: HashSet<u64> = HashSet::from_iter(winning_numbers);
+    // split list of numbers
+    let (winning_numbers, our_numbers) = split_once(useful_text, b'|')
+        .ok_or(AocError::new("malformatted line, no '|' separated data"))?;
 
-        let our_numbers: Vec<u64> = our_numbers
-            .split_ascii_whitespace()
-            .map(|number| number.parse::<u64>().map_err(|e| anyhow!(e)))
-            .collect::<Result<Vec<u64>>>()?;
+    let number_of_matches = match (columns(winning_numbers), columns(our_numbers)) {
+        (Some(winning_numbers), Some(our_numbers)) => {
+            let winning_numbers = winning_numbers.fold(0u128, |set, number| set | 1 << number);
+            our_numbers
+                .map(|number| (winning_numbers >> number) as usize & 1)
+                .sum()
+        }
+        _ => count_matches(winning_numbers, our_numbers)?,
+    };
 
-        let number_of_matches = our_numbers
-            .iter()
-            .filter(|n| winning_numbers.contains(n))
-            .count();
-
-        for i in 1..=number_of_matches {
-            let card_to_increment = card_number + i;
-            let value = match card_counts.get(&card_number) {
-                Some(n) => *n,
-                None => 1,
-            };
-            match card_counts.entry(card_to_increment) {
+    trace!(matches = number_of_matches, "scored card");
+    Ok(Card {
+        number: card_number,
+        matches: number_of_matches,
+    })
+}
+
+/// Both parts only care how many numbers on each card match, so the cards
+/// are parsed straight into their match counts
+pub struct Day4;
+
+impl Solver for Day4 {
+    const DAY: usize = 4;
+    const TITLE: &'static str = "Scratchcards";
+    const TAGS: &'static [&'static str] = &["sets", "dynamic programming"];
+    type Parsed<'a> = Vec<Card>;
+
+    fn parse(text: &str, strategy: ExecutionStrategy) -> Result<Vec<Card>> {
+        Self::parse_bytes(text.as_bytes(), strategy)
+    }
+
+    /// cards are all ASCII, so they're parsed from bytes whichever way the
+    /// input arrives
+    fn parse_bytes(input: &[u8], strategy: ExecutionStrategy) -> Result<Vec<Card>> {
+        let lines: Vec<&[u8]> = lines(input).collect();
+        context::reserve_for::<(&[u8], Card)>(lines.len(), "cards")?;
+        progress::start("cards", lines.len() as u64);
+        let cards = strategy.try_map(&lines, |i, line| {
+            context::checkpoint()?;
+            progress::advance(1);
+            parse_card(line).map_err(|e| e.at_line(i + 1))
+        })?;
+        progress::finish();
+
+        let mut previous_card: Option<usize> = None;
+        for card in &cards {
+            if let Some(previous) = previous_card {
+                if card.number != previous + 1 {
+                    warn!(
+                        previous,
+                        card_number = card.number,
+                        "card numbers are not sequential"
+                    );
+                }
+            }
+            previous_card = Some(card.number);
+        }
+        telemetry::lines(cards.len());
+        telemetry::entries(cards.len());
+        Ok(cards)
+    }
+
+    fn part1(cards: &Vec<Card>, _strategy: ExecutionStrategy) -> Result<Answer> {
+        let policy = overflow::policy();
+        let mut total_points: u128 = 0;
+        for card in cards {
+            if card.matches > 0 {
+                let card_points = points(card.matches, policy)?;
+                total_points = policy.add(total_points, card_points)?;
+                explain::step(|| {
+                    format!(
+                        "card {}: {}, worth {}",
+                        card.number,
+                        explain::plural(card.matches as u128, "match", "matches"),
+                        explain::plural(card_points, "point", "points")
+                    )
+                });
+            } else {
+                explain::step(|| format!("card {}: no matches, worth nothing", card.number));
+            }
+        }
+
+        debug!(cards = cards.len(), total_points, "scored scratchcards");
+        Ok(total_points.into())
+    }
+
+    fn part2(cards: &Vec<Card>, _strategy: ExecutionStrategy) -> Result<Answer> {
+        let policy = overflow::policy();
+        // a count for every card, and for the cards past the end that the
+        // last ones win copies of
+        let most_won = cards.iter().map(|card| card.matches).max().unwrap_or(0);
+        context::reserve_for::<(usize, u128)>(cards.len().saturating_add(most_won), "card counts")?;
+        let mut card_counts: FastMap<usize, u128> = FastMap::default();
+
+        for card in cards {
+            match card_counts.entry(card.number) {
                 Occupied(mut existing_entry) => {
-                    *existing_entry.get_mut() += value;
+                    let count = existing_entry.get_mut();
+                    *count = policy.add(*count, 1)?;
                 }
                 Vacant(new_entry) => {
-                    new_entry.insert(value);
+                    new_entry.insert(1);
+                }
+            }
+
+            explain::step(|| {
+                let count = card_counts.get(&card.number).copied().unwrap_or(1);
+                let instances = explain::plural(count, "instance", "instances");
+                match card.matches {
+                    0 => format!("card {}: {instances}, winning nothing", card.number),
+                    1 => format!(
+                        "card {}: {instances}, each winning a copy of card {}",
+                        card.number,
+                        card.number + 1
+                    ),
+                    matches => format!(
+                        "card {}: {instances}, each winning a copy of cards {} to {}",
+                        card.number,
+                        card.number + 1,
+                        card.number + matches
+                    ),
+                }
+            });
+            for i in 1..=card.matches {
+                let card_to_increment = card.number + i;
+                let value = match card_counts.get(&card.number) {
+                    Some(n) => *n,
+                    None => 1,
+                };
+                match card_counts.entry(card_to_increment) {
+                    Occupied(mut existing_entry) => {
+                        let count = existing_entry.get_mut();
+                        *count = policy.add(*count, value)?;
+                    }
+                    Vacant(new_entry) => {
+                        new_entry.insert(value);
+                    }
                 }
             }
         }
-        if lines.peek().is_none() {
-            // we've reached the last line. Drop all hashmap keys that are greater
-            // than this card number, as they do not exist and should not count towards
-            // our totals
-            sum = card_counts
+
+        // drop all hashmap keys that are greater than the last card number, as
+        // they do not exist and should not count towards our totals
+        let Some(last) = cards.last() else {
+            return Ok(Answer::U64(0));
+        };
+        let sum = policy.sum(
+            card_counts
                 .iter()
-                .filter(|(k, _)| **k <= card_number)
-                .map(|(_, v)| *v as u64)
-                .sum();
+                .filter(|(k, _)| **k <= last.number)
+                .map(|(_, v)| *v),
+        )?;
+        telemetry::entries(card_counts.len());
+        debug!(cards = last.number, total = sum, "counted scratchcards");
+        Ok(sum.into())
+    }
+}
+
+/// Parsing a card only depends on its line, so the cards are kept and an
+/// edit only parses the cards it touches. Copies cascade down the table, so
+/// both parts are then worked out from all of them, which is cheap next to
+/// parsing.
+#[derive(Default)]
+pub struct Cards {
+    cards: Vec<Result<Card>>,
+}
+
+impl Cards {
+    fn cards(&self) -> Result<Vec<Card>> {
+        self.cards
+            .iter()
+            .enumerate()
+            .map(|(i, card)| card.clone().map_err(|e| e.at_line(i + 1)))
+            .collect()
+    }
+}
+
+impl Incremental for Cards {
+    fn splice(&mut self, changed: Range<usize>, lines: &[&str]) {
+        let cards = lines.iter().map(|line| parse_card(line.as_bytes()));
+        self.cards.splice(changed, cards);
+    }
+
+    fn part1(&self) -> Result<Answer> {
+        let cards = self.cards().map_err(|e| e.with_part(1))?;
+        Day4::part1(&cards, ExecutionStrategy::Sequential).map_err(|e| e.with_part(1))
+    }
+
+    fn part2(&self) -> Result<Answer> {
+        let cards = self.cards().map_err(|e| e.with_part(2))?;
+        Day4::part2(&cards, ExecutionStrategy::Sequential).map_err(|e| e.with_part(2))
+    }
+}
+
+/// cards are already as small as they get, a number and how many matched
+#[cfg(feature = "serde")]
+impl Model for Day4 {
+    fn model<'a, 'b: 'a>(cards: &'a Vec<Card>) -> impl Serialize + 'a {
+        cards
+    }
+}
+
+aoc_core::register! {
+    solver: Day4,
+    examples: [PART_ONE_EXAMPLE, PART_TWO_EXAMPLE],
+    expected: [Some(Answer::U64(13)), Some(Answer::U64(30))],
+    validate: validate,
+    algorithms: &[
+        aoc_core::solver::Algorithm::solver("hashmap"),
+        aoc_core::solver::Algorithm {
+            name: "vec",
+            parts: [None, Some(vec::solve_part_two)],
+        },
+    ],
+    incremental: || alloc::boxed::Box::new(Cards::default()),
+    persist: Day4,
+    export: Day4,
+}
+
+/// Variant of part two that counts copies in a `Vec` indexed by card position
+/// rather than a `HashMap` keyed by card number. Cards are assumed to be listed
+/// in order, which lets it skip parsing the card numbers entirely.
+pub mod vec {
+    use super::*;
+
+    #[instrument(name = "vec::solve_part_two", skip_all)]
+    pub fn solve_part_two(text: &str) -> Result<Answer> {
+        // it's a solve of its own, with a memory budget of its own
+        context::fresh(|| {
+            let lines: Vec<&str> = text.lines().collect();
+            context::reserve_for::<(&str, u128)>(lines.len(), "card copies")?;
+            let policy = overflow::policy();
+            let mut copies = alloc::vec![1u128; lines.len()];
+            progress::start("cards", lines.len() as u64);
+
+            for (index, line) in lines.iter().enumerate() {
+                context::checkpoint()?;
+                let (_id, useful_text) = line
+                    .split_once(':')
+                    .ok_or(AocError::new("malformatted line, no colon separated data"))?;
+                let (winning_numbers, our_numbers) = useful_text
+                    .split_once('|')
+                    .ok_or(AocError::new("malformatted line, no '|' separated data"))?;
+
+                let winning_numbers = fields(winning_numbers.as_bytes())
+                    .map(parse_u64)
+                    .collect::<Result<FastSet<u64>>>()?;
+                let mut number_of_matches = 0;
+                for number in fields(our_numbers.as_bytes()) {
+                    if winning_numbers.contains(&parse_u64(number)?) {
+                        number_of_matches += 1;
+                    }
+                }
+
+                // cards past the end of the table don't exist, so their copies are dropped
+                let last = (index + number_of_matches).min(lines.len() - 1);
+                for won in index + 1..=last {
+                    copies[won] = policy.add(copies[won], copies[index])?;
+                }
+                progress::advance(1);
+            }
+            progress::finish();
+
+            let sum = policy.sum(copies)?;
+            debug!(cards = lines.len(), total = sum, "counted scratchcards");
+            Ok(sum.into())
+        })
+    }
+}
+
+/// Slow but obviously correct versions of both parts, comparing every number
+/// we have with every winning number and handing out copies a card at a
+/// time, for tests to check the solvers against
+#[cfg(feature = "reference")]
+pub mod reference {
+    use alloc::vec;
+
+    use super::*;
+
+    /// how many of each card's numbers are winning numbers, in order
+    fn matches(text: &str) -> Result<Vec<usize>> {
+        let mut matches = Vec::new();
+        for line in text.lines() {
+            let (_, numbers) = line
+                .split_once(':')
+                .ok_or(AocError::new("malformatted line, no colon separated data"))?;
+            let (winning, ours) = numbers
+                .split_once('|')
+                .ok_or(AocError::new("malformatted line, no '|' separated data"))?;
+            let winning = winning
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<core::result::Result<Vec<u64>, _>>()?;
+            let mut count = 0;
+            for number in ours.split_whitespace() {
+                if winning.contains(&number.parse()?) {
+                    count += 1;
+                }
+            }
+            matches.push(count);
+        }
+        Ok(matches)
+    }
+
+    pub fn part_one(text: &str) -> Result<u128> {
+        let mut total = 0;
+        for matches in matches(text)? {
+            if matches > 0 {
+                total += 1 << (matches - 1);
+            }
+        }
+        Ok(total)
+    }
+
+    pub fn part_two(text: &str) -> Result<u128> {
+        let matches = matches(text)?;
+        let mut copies = vec![1u128; matches.len()];
+        for (card, won) in matches.iter().enumerate() {
+            for next in card + 1..=card + won {
+                if next < copies.len() {
+                    copies[next] += copies[card];
+                }
+            }
         }
+        Ok(copies.iter().sum())
     }
-    Ok(sum)
 }
 
-pub fn print_answers(text: &str) -> Result<()> {
-    let part_one = solve_part_one(text)?;
-    let part_two = solve_part_two(text)?;
+/// check each card is in the `Card <n>: <numbers> | <numbers>` format and
+/// that cards are numbered in order, without solving
+pub fn validate(text: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = alloc::vec![];
+    let mut previous_card: Option<usize> = None;
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let Some((id, numbers)) = line.split_once(':') else {
+            diagnostics.push(Diagnostic::new(
+                number,
+                1,
+                "expected a line like \"Card 1: 41 48 | 83 86\"",
+            ));
+            continue;
+        };
+        match id
+            .strip_prefix("Card")
+            .map(|card| card.trim().parse::<usize>())
+        {
+            Some(Ok(card)) => {
+                if let Some(previous) = previous_card {
+                    if card != previous + 1 {
+                        diagnostics.push(Diagnostic::at(
+                            number,
+                            line,
+                            id,
+                            format!("card {card} follows card {previous}, cards should be numbered in order"),
+                        ).warning());
+                    }
+                }
+                previous_card = Some(card);
+            }
+            _ => diagnostics.push(Diagnostic::at(
+                number,
+                line,
+                id,
+                format!("expected a card id like \"Card 1\", found {id:?}"),
+            )),
+        }
+        let Some((winning_numbers, our_numbers)) = numbers.split_once('|') else {
+            diagnostics.push(Diagnostic::at(
+                number,
+                line,
+                numbers,
+                "expected two lists of numbers separated by '|'",
+            ));
+            continue;
+        };
+        for value in winning_numbers
+            .split_ascii_whitespace()
+            .chain(our_numbers.split_ascii_whitespace())
+        {
+            if value.parse::<u64>().is_err() {
+                diagnostics.push(Diagnostic::at(
+                    number,
+                    line,
+                    value,
+                    format!("{value:?} is not a number"),
+                ));
+            }
+        }
+    }
+    diagnostics
+}
 
-    println!("part one: {part_one}");
-    println!("part two: {part_two}");
-    Ok(())
+/// both parts' answers, parsing the input once, for the caller to present
+#[cfg(feature = "std")]
+pub fn answers(text: &str) -> Result<aoc_core::solver::Answers> {
+    Day4.solve(text, ExecutionStrategy::Sequential)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn vec_variant_should_match_hashmap_variant() -> Result<()> {
+        let text = include_str!("part1_example.txt");
+        assert_eq!(vec::solve_part_two(text)?, solve_part_two(text)?);
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_the_example() -> Result<()> {
+        let cards = Day4::parse(PART_ONE_EXAMPLE, ExecutionStrategy::Sequential)?;
+        insta::assert_debug_snapshot!(cards);
+        Ok(())
+    }
+
+    #[test]
+    fn should_score_cards_from_the_table() -> Result<()> {
+        let policy = OverflowPolicy::Checked;
+        assert_eq!(points(0, policy)?, 0);
+        for matches in 1..=POINTS.len() {
+            assert_eq!(points(matches, policy), policy.pow2(matches as u32 - 1));
+        }
+        assert!(points(POINTS.len(), policy).is_err());
+        assert_eq!(points(70, OverflowPolicy::Widen)?, 1 << 69);
+        Ok(())
+    }
+
+    #[test]
+    fn should_read_numbers_in_columns() {
+        let numbers = |list: &[u8]| columns(list).map(Iterator::collect::<Vec<_>>);
+        assert_eq!(numbers(b" 41  8 83 "), Some(vec![41, 8, 83]));
+        assert_eq!(numbers(b" 83  6 31"), Some(vec![83, 6, 31]));
+        // anything out of line is left to the field by field parser
+        assert_eq!(numbers(b" 41 8 83"), None);
+        assert_eq!(numbers(b" 41 4x 83"), None);
+        assert_eq!(numbers(b" 41 -4 83"), None);
+
+        let ragged = "Card 1: 41 48 83  86 17 | 83 86 6 31 17 9 48 53";
+        assert_eq!(solve_part_one(ragged), Ok(Answer::U64(8)));
+        assert!(solve_part_one("Card 1: 41 4x | 83").is_err());
+    }
+
+    #[test]
+    fn should_solve_from_bytes() -> Result<()> {
+        let crlf = PART_ONE_EXAMPLE.replace('\n', "\r\n");
+        assert_eq!(solve_part_one_bytes(crlf.as_bytes())?, 13);
+        assert_eq!(solve_part_two_bytes(PART_TWO_EXAMPLE.as_bytes())?, 30);
+        Ok(())
+    }
+
+    #[test]
+    fn should_follow_the_overflow_policy() -> Result<()> {
+        // 70 matches are worth 2^69 points, which doesn't fit in a u64
+        let numbers: String = (1..=70).map(|n| format!(" {n}")).collect();
+        let text = format!("Card 1:{numbers} |{numbers}");
+        assert!(solve_part_one(&text).is_err());
+        let widened = overflow::scoped(overflow::OverflowPolicy::Widen, || solve_part_one(&text))?;
+        assert_eq!(widened, Answer::U128(1 << 69));
+        Ok(())
+    }
+
+    #[test]
+    fn should_validate_cards() {
+        assert_eq!(validate(PART_ONE_EXAMPLE), vec![]);
+        let diagnostics = validate("Card 1: 41 4x | 83\nCard 3: 1 2 3");
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::new(1, 12, "\"4x\" is not a number"),
+                Diagnostic::new(
+                    2,
+                    1,
+                    "card 3 follows card 1, cards should be numbered in order"
+                )
+                .warning(),
+                Diagnostic::new(2, 8, "expected two lists of numbers separated by '|'"),
+            ]
+        );
+    }
 }